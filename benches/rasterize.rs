@@ -0,0 +1,237 @@
+//! Rasterizer benchmarks: the SoA / SIMD / row-culled rasterizers against
+//! the previous per-pixel scalar loops, and each SIMD kernel against its
+//! scalar fallback.
+//!
+//! Run with `cargo bench --bench rasterize`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use alice_font::glyph::font_render::{self, fast_sqrt};
+use alice_font::glyph::outline::{self, FillRule, PathCmd};
+use alice_font::glyph::simd::{self, Lines};
+use alice_font::glyph::{GlyphGenerator, GlyphSdf, GlyphSkeleton, GLYPH_SDF_SIZE};
+use alice_font::stroke::PenModel;
+use alice_font::MetaFontParams;
+
+/// Time spent measuring each case.
+const BUDGET: Duration = Duration::from_millis(400);
+
+/// Mean time per call of `f`, repeated until [`BUDGET`] is used up.
+fn measure(mut f: impl FnMut()) -> Duration {
+    f();
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while start.elapsed() < BUDGET {
+        f();
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+/// `before` and `after` of `tiles` tiles, per tile.
+fn report_tiles(name: &str, before: Duration, after: Duration, tiles: usize) {
+    let per_tile = |d: Duration| d.as_secs_f64() * 1e6 / tiles as f64;
+    println!(
+        "{name:<34} {:>8.1} µs/tile {:>8.1} µs/tile {:>7.1}×",
+        per_tile(before),
+        per_tile(after),
+        before.as_secs_f64() / after.as_secs_f64()
+    );
+}
+
+fn report(name: &str, scalar: Duration, vector: Duration) {
+    println!(
+        "{name:<34} {:>10.1} µs {:>10.1} µs {:>7.1}×",
+        scalar.as_secs_f64() * 1e6,
+        vector.as_secs_f64() * 1e6,
+        scalar.as_secs_f64() / vector.as_secs_f64()
+    );
+}
+
+/// The previous parametric inner loop: every sample of every stroke in
+/// turn, scalar, skipping to the next stroke once inside and giving up
+/// once deeper than any stroke could reach.
+fn legacy_parametric(pen: &PenModel, skel: &GlyphSkeleton, tile: &GlyphSdf) -> GlyphSdf {
+    let mut sdf = GlyphSdf::empty();
+    let samples: Vec<Vec<(f32, f32, f32)>> = skel.strokes[..skel.stroke_count]
+        .iter()
+        .map(|stroke| {
+            (0..=16)
+                .map(|i| {
+                    let t = i as f32 / 16.0;
+                    let p = stroke.position(t);
+                    (p.x, p.y, pen.half_width(stroke.tangent(t)))
+                })
+                .collect()
+        })
+        .collect();
+    let inv = 1.0 / (GLYPH_SDF_SIZE - 1) as f32;
+    let (w, h) = (
+        tile.bbox_max.x - tile.bbox_min.x,
+        tile.bbox_max.y - tile.bbox_min.y,
+    );
+    for py in 0..GLYPH_SDF_SIZE {
+        for px in 0..GLYPH_SDF_SIZE {
+            let x = tile.bbox_min.x + px as f32 * inv * w;
+            let y = tile.bbox_min.y + py as f32 * inv * h;
+            let mut min = f32::MAX;
+            for stroke in &samples {
+                for &(cx, cy, hw) in stroke {
+                    let d = fast_sqrt((x - cx) * (x - cx) + (y - cy) * (y - cy)) - hw;
+                    if d < min {
+                        min = d;
+                        if min < 0.0 {
+                            break;
+                        }
+                    }
+                }
+                if min < -(pen.base_width * 2.0) {
+                    break;
+                }
+            }
+            sdf.data[py * GLYPH_SDF_SIZE + px] = min;
+        }
+    }
+    sdf
+}
+
+/// The previous outline loop: a winding-number and a distance query per
+/// pixel of `tile`'s frame.
+fn legacy_outline(cmds: &[PathCmd], tile: &GlyphSdf) -> GlyphSdf {
+    let mut sdf = GlyphSdf::empty();
+    let inv = 1.0 / (GLYPH_SDF_SIZE - 1) as f32;
+    let (lo, hi) = (tile.bbox_min, tile.bbox_max);
+    for py in 0..GLYPH_SDF_SIZE {
+        for px in 0..GLYPH_SDF_SIZE {
+            let x = lo.x + px as f32 * inv * (hi.x - lo.x);
+            let y = lo.y + py as f32 * inv * (hi.y - lo.y);
+            let d = fast_sqrt(outline::distance_sq(cmds, x, y));
+            let inside = outline::is_inside(cmds, x, y, FillRule::NonZero);
+            sdf.data[py * GLYPH_SDF_SIZE + px] = if inside { -d } else { d };
+        }
+    }
+    sdf
+}
+
+/// Deterministic pseudo-random values in `[-1, 1)`.
+fn noise(n: usize, seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+        })
+        .collect()
+}
+
+fn main() {
+    println!("SIMD backend: {}", simd::BACKEND);
+    println!(
+        "{:<34} {:>16} {:>16} {:>8}",
+        "case", "before", "after", "speedup"
+    );
+
+    // Parametric glyphs: whole tiles per glyph set.
+    for (name, params) in [
+        ("parametric sans (A–Z a–z)", MetaFontParams::sans_regular()),
+        (
+            "parametric serif italic (A–Z a–z)",
+            MetaFontParams::serif_italic(),
+        ),
+    ] {
+        let gen = GlyphGenerator::new(&params);
+        let pen = PenModel::from_params(&params);
+        let glyphs: Vec<_> = (b'A'..=b'Z')
+            .chain(b'a'..=b'z')
+            .filter_map(|c| gen.build_char_skeleton(c as char))
+            .map(|skel| {
+                let tile = gen.generate_from_skeleton(&skel);
+                (skel, tile)
+            })
+            .collect();
+        let before = measure(|| {
+            for (skel, tile) in &glyphs {
+                black_box(legacy_parametric(&pen, skel, tile));
+            }
+        });
+        let after = measure(|| {
+            for (skel, _) in &glyphs {
+                black_box(gen.generate_from_skeleton(black_box(skel)));
+            }
+        });
+        report_tiles(name, before, after, glyphs.len());
+    }
+
+    // Embedded outlines.
+    for (name, chars) in [
+        ("outline latin (A g @ &)", "Ag@&"),
+        ("outline kana (あ ア の)", "あアの"),
+        ("outline kanji (株 語 鬱)", "株語鬱"),
+    ] {
+        let outlines: Vec<(Vec<PathCmd>, GlyphSdf)> = chars
+            .chars()
+            .filter_map(|ch| font_render::lookup(ch, false))
+            .map(|(o, _)| {
+                let cmds = o.decode();
+                let tile = font_render::rasterize_outline(&cmds, 1.0, FillRule::NonZero);
+                (cmds, tile)
+            })
+            .collect();
+        if outlines.is_empty() {
+            println!("{name:<34} (outline group disabled)");
+            continue;
+        }
+        let before = measure(|| {
+            for (cmds, tile) in &outlines {
+                black_box(legacy_outline(cmds, tile));
+            }
+        });
+        let after = measure(|| {
+            for (cmds, _) in &outlines {
+                black_box(font_render::rasterize_outline(cmds, 1.0, FillRule::NonZero));
+            }
+        });
+        report_tiles(name, before, after, outlines.len());
+    }
+
+    // Kernels: 64×64 queries against the same primitives.
+    println!(
+        "{:<34} {:>13} {:>13} {:>8}",
+        "kernel", "scalar", "simd", "speedup"
+    );
+    let queries: Vec<(f32, f32)> = noise(2 * 4096, 9)
+        .chunks_exact(2)
+        .map(|p| (p[0], p[1]))
+        .collect();
+    let (xs, ys) = (noise(320, 1), noise(320, 2));
+    let rs: Vec<f32> = noise(320, 3).iter().map(|r| r.abs() * 0.05).collect();
+    let scalar = measure(|| {
+        for &(x, y) in &queries {
+            black_box(simd::disc_distance_scalar(x, y, &xs, &ys, &rs, f32::MAX));
+        }
+    });
+    let vector = measure(|| {
+        for &(x, y) in &queries {
+            black_box(simd::disc_distance(x, y, &xs, &ys, &rs, f32::MAX));
+        }
+    });
+    report("kernel discs (320 per query)", scalar, vector);
+
+    let mut lines = Lines::new();
+    for w in xs.windows(2).zip(ys.windows(2)) {
+        lines.push((w.0[0], w.1[0]), (w.0[1], w.1[1]), 0.0);
+    }
+    lines.finish();
+    let scalar = measure(|| {
+        for &(x, y) in &queries {
+            black_box(lines.nearest_sq_scalar(x, y, f32::MAX));
+        }
+    });
+    let vector = measure(|| {
+        for &(x, y) in &queries {
+            black_box(lines.nearest_sq(x, y, f32::MAX));
+        }
+    });
+    report("kernel lines (319 per query)", scalar, vector);
+}
@@ -13,8 +13,17 @@ extern crate alloc;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::glyph::{dispatcher, GlyphGenerator, GlyphSdf, GLYPH_SDF_SIZE};
+use crate::glyph::digits::{self, FigureMetrics, FigureStyle};
+use crate::glyph::dispatcher::GlyphSourcePolicy;
+use crate::glyph::provider::{GlyphProvider, ProviderChain};
+#[cfg(feature = "sfnt")]
+use crate::glyph::sfnt::SfntFont;
+use crate::glyph::{
+    FontMetrics, GlyphGenerator, GlyphMetrics, GlyphProvenance, GlyphResult, GlyphSdf,
+    GLYPH_SDF_SIZE,
+};
 use crate::param::MetaFontParams;
+use crate::stroke::Point2;
 
 /// Maximum atlas grid dimension (tiles per side) for the legacy single-page
 /// `SdfAtlas`. New code should prefer [`SdfAtlasMulti`] which supports much
@@ -30,6 +39,11 @@ pub const MAX_ATLAS_DIM_PER_PAGE: usize = 64;
 /// Joyo kanji plus kana plus ASCII.
 pub const MAX_ATLAS_PAGES: usize = 8;
 
+/// Characters generated per window by [`SdfAtlasMulti::preload_parallel`];
+/// bounds the tiles held in memory before they are committed.
+#[cfg(feature = "std")]
+pub const PRELOAD_BATCH: usize = 256;
+
 /// Atlas entry — maps a character to its tile position
 #[derive(Debug, Clone, Copy)]
 pub struct AtlasEntry {
@@ -44,9 +58,16 @@ pub struct AtlasEntry {
     pub uv_y: f32,
     pub uv_w: f32,
     pub uv_h: f32,
-    /// Glyph metrics
+    /// Glyph metrics (mirrors of `metrics.advance` / `metrics.lsb`)
     pub advance: f32,
     pub lsb: f32,
+    /// Advance, side bearings and ink box
+    pub metrics: GlyphMetrics,
+    /// Tile frame (em units): the rectangle the tile's samples cover
+    pub bbox_min: Point2,
+    pub bbox_max: Point2,
+    /// Figure style the tile is set in (None when no style applies)
+    pub figures: Option<FigureStyle>,
     /// LRU timestamp (higher = more recent)
     pub last_used: u32,
 }
@@ -67,6 +88,14 @@ pub struct SdfAtlas {
     clock: u32,
     /// Number of occupied tiles
     occupied: usize,
+    /// Outline vs. parametric source selection
+    policy: GlyphSourcePolicy,
+    /// Glyph providers consulted in order
+    providers: ProviderChain,
+    /// Figure style of [`SdfAtlas::get_or_insert`]
+    figure_style: Option<FigureStyle>,
+    /// Figure widths of the current font, measured on first use
+    figure_metrics: Option<FigureMetrics>,
 }
 
 impl SdfAtlas {
@@ -89,6 +118,10 @@ impl SdfAtlas {
             generator: GlyphGenerator::new(&params),
             clock: 0,
             occupied: 0,
+            policy: GlyphSourcePolicy::default(),
+            providers: ProviderChain::builtin(),
+            figure_style: None,
+            figure_metrics: None,
         }
     }
 
@@ -120,6 +153,7 @@ impl SdfAtlas {
     pub fn set_params(&mut self, params: MetaFontParams) {
         self.params = params;
         self.generator = GlyphGenerator::new(&params);
+        self.figure_metrics = None;
         self.clear();
     }
 
@@ -139,8 +173,9 @@ impl SdfAtlas {
     pub fn lookup(&mut self, ch: char) -> Option<&AtlasEntry> {
         self.clock += 1;
         let clock = self.clock;
+        let figures = figure_key(ch, self.figure_style);
         for e in self.entries.iter_mut().flatten() {
-            if e.codepoint == ch {
+            if e.codepoint == ch && e.figures == figures {
                 e.last_used = clock;
                 return Some(e);
             }
@@ -150,12 +185,20 @@ impl SdfAtlas {
 
     /// Get or insert a glyph, returns atlas entry
     pub fn get_or_insert(&mut self, ch: char) -> AtlasEntry {
+        self.get_or_insert_styled(ch, self.figure_style)
+    }
+
+    /// [`SdfAtlas::get_or_insert`] with digits set in `style` rather than
+    /// the atlas's figure style. Tiles of different styles are cached
+    /// side by side.
+    pub fn get_or_insert_styled(&mut self, ch: char, style: Option<FigureStyle>) -> AtlasEntry {
         self.clock += 1;
         let clock = self.clock;
+        let figures = figure_key(ch, style);
 
         // Check if already cached
         for e in self.entries.iter_mut().flatten() {
-            if e.codepoint == ch {
+            if e.codepoint == ch && e.figures == figures {
                 e.last_used = clock;
                 return *e;
             }
@@ -164,7 +207,7 @@ impl SdfAtlas {
         // Generate glyph SDF via the Unicode-aware dispatcher. Non-ASCII
         // characters get a placeholder SDF until the corresponding script
         // module is implemented (see `docs/CJK_ROADMAP.md`).
-        let sdf = dispatcher::generate(ch, &self.params);
+        let GlyphResult { sdf, metrics, .. } = self.generate(ch, figures);
 
         // Find a free slot or evict LRU
         let slot = self.find_slot();
@@ -186,8 +229,12 @@ impl SdfAtlas {
             uv_y: (tile_y * GLYPH_SDF_SIZE) as f32 * inv_tex,
             uv_w: GLYPH_SDF_SIZE as f32 * inv_tex,
             uv_h: GLYPH_SDF_SIZE as f32 * inv_tex,
-            advance: sdf.advance,
-            lsb: sdf.lsb,
+            advance: metrics.advance,
+            lsb: metrics.lsb,
+            metrics,
+            bbox_min: sdf.bbox_min,
+            bbox_max: sdf.bbox_max,
+            figures,
             last_used: clock,
         };
 
@@ -199,6 +246,95 @@ impl SdfAtlas {
         entry
     }
 
+    /// Register a runtime font. Registered fonts are consulted in
+    /// registration order before the built-in glyph sources. Cached glyphs
+    /// are kept; call [`SdfAtlas::clear`] to re-render them from the new font.
+    #[cfg(feature = "sfnt")]
+    pub fn register_font(&mut self, font: SfntFont) {
+        self.providers.register(font);
+        self.figure_metrics = None;
+    }
+
+    /// Number of runtime fonts in the provider chain.
+    #[cfg(feature = "sfnt")]
+    #[must_use]
+    pub const fn font_count(&self) -> usize {
+        self.providers.font_count()
+    }
+
+    /// Select where glyphs come from (outline table vs. parametric
+    /// skeletons). Drops every cached glyph if the policy changes.
+    pub fn set_source_policy(&mut self, policy: GlyphSourcePolicy) {
+        if self.policy != policy {
+            self.policy = policy;
+            self.figure_metrics = None;
+            self.clear();
+        }
+    }
+
+    /// Current glyph-source policy.
+    #[must_use]
+    pub const fn source_policy(&self) -> &GlyphSourcePolicy {
+        &self.policy
+    }
+
+    /// Font-wide metrics of the glyphs this atlas renders (registered
+    /// providers and fonts included), under the current source policy.
+    #[must_use]
+    pub fn font_metrics(&self) -> FontMetrics {
+        self.providers.font_metrics(&self.params, &self.policy)
+    }
+
+    /// Register a custom glyph provider (icon set, custom script, ...).
+    /// Registered providers are consulted in registration order before the
+    /// built-in ones. Cached glyphs are kept; call [`SdfAtlas::clear`] to
+    /// re-render them.
+    pub fn register_provider(&mut self, provider: impl GlyphProvider + 'static) {
+        self.providers.register(provider);
+        self.figure_metrics = None;
+    }
+
+    /// Replace the whole provider chain, dropping every cached glyph.
+    pub fn set_providers(&mut self, providers: ProviderChain) {
+        self.providers = providers;
+        self.figure_metrics = None;
+        self.clear();
+    }
+
+    /// The provider chain glyphs are generated from.
+    #[must_use]
+    pub const fn providers(&self) -> &ProviderChain {
+        &self.providers
+    }
+
+    /// Set digits (and the tabular symbols) inserted by
+    /// [`SdfAtlas::get_or_insert`] in `style`; `None` (the default) keeps
+    /// them as the glyph sources draw them. Tiles of other styles stay
+    /// cached.
+    pub const fn set_figure_style(&mut self, style: Option<FigureStyle>) {
+        self.figure_style = style;
+    }
+
+    /// Figure style of [`SdfAtlas::get_or_insert`].
+    #[must_use]
+    pub const fn figure_style(&self) -> Option<FigureStyle> {
+        self.figure_style
+    }
+
+    fn generate(&mut self, ch: char, figures: Option<FigureStyle>) -> GlyphResult {
+        let result = self.providers.generate(ch, &self.params, &self.policy);
+        match figures {
+            Some(style) => {
+                let (providers, params, policy) = (&self.providers, &self.params, &self.policy);
+                let widths = *self
+                    .figure_metrics
+                    .get_or_insert_with(|| providers.figure_metrics(params, policy));
+                digits::apply(style, ch, result, params, &widths)
+            }
+            None => result,
+        }
+    }
+
     /// Find a free slot or evict the least-recently-used entry
     fn find_slot(&self) -> usize {
         // First try to find an empty slot
@@ -265,6 +401,15 @@ impl SdfAtlas {
         }
         self.pixels[tex_y * tex_w + tex_x]
     }
+
+    /// Sample `(col, row)` of an entry's tile (row 0 at `bbox_min.y`).
+    #[must_use]
+    pub fn sample_tile(&self, entry: &AtlasEntry, col: usize, row: usize) -> f32 {
+        self.sample(
+            usize::from(entry.tile_x) * GLYPH_SDF_SIZE + col,
+            usize::from(entry.tile_y) * GLYPH_SDF_SIZE + row,
+        )
+    }
 }
 
 // ============================================================================
@@ -285,7 +430,29 @@ pub struct AtlasEntryMulti {
     pub uv_h: f32,
     pub advance: f32,
     pub lsb: f32,
+    /// Advance, side bearings and ink box
+    pub metrics: GlyphMetrics,
+    /// Tile frame (em units): the rectangle the tile's samples cover
+    pub bbox_min: Point2,
+    pub bbox_max: Point2,
     pub last_used: u32,
+    /// Where the glyph came from (outline, parametric, placeholder, missing)
+    pub provenance: GlyphProvenance,
+    /// Skeleton strokes rendered (0 for outlines)
+    pub stroke_count: u16,
+    /// Skeleton strokes dropped at the per-glyph stroke limit
+    pub dropped_strokes: u16,
+}
+
+impl AtlasEntryMulti {
+    /// Is this a real glyph (neither a placeholder nor the missing tile)?
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        matches!(
+            self.provenance,
+            GlyphProvenance::Outline | GlyphProvenance::Parametric
+        ) && self.dropped_strokes == 0
+    }
 }
 
 /// A single page of [`SdfAtlasMulti`]. Each page is a square grid of tiles
@@ -343,6 +510,10 @@ pub struct SdfAtlasMulti {
     page_dim: usize,
     params: MetaFontParams,
     clock: u32,
+    policy: GlyphSourcePolicy,
+    providers: ProviderChain,
+    figure_style: Option<FigureStyle>,
+    figure_metrics: Option<FigureMetrics>,
 }
 
 impl SdfAtlasMulti {
@@ -363,6 +534,10 @@ impl SdfAtlasMulti {
             page_dim,
             params,
             clock: 0,
+            policy: GlyphSourcePolicy::default(),
+            providers: ProviderChain::builtin(),
+            figure_style: None,
+            figure_metrics: None,
         }
     }
 
@@ -415,38 +590,103 @@ impl SdfAtlasMulti {
         self.peek(ch).is_some()
     }
 
+    /// Every cached entry, page by page. Combine with
+    /// [`AtlasEntryMulti::is_complete`] to list missing or placeholder
+    /// glyphs, e.g. when checking a localized build.
+    pub fn entries(&self) -> impl Iterator<Item = &AtlasEntryMulti> {
+        self.pages.iter().flat_map(|p| p.entries.iter().flatten())
+    }
+
     /// Look up a character, refreshing its LRU timestamp if found.
     pub fn lookup(&mut self, ch: char) -> Option<AtlasEntryMulti> {
         self.clock += 1;
-        let clock = self.clock;
-        for page in &mut self.pages {
-            for entry in page.entries.iter_mut().flatten() {
-                if entry.codepoint == ch {
-                    entry.last_used = clock;
-                    return Some(*entry);
-                }
-            }
-        }
-        None
+        self.touch(ch)
     }
 
     /// Get an existing entry or insert a new one, evicting an LRU entry if
     /// every page is full.
     pub fn get_or_insert(&mut self, ch: char) -> AtlasEntryMulti {
         self.clock += 1;
-        let clock = self.clock;
+        if let Some(entry) = self.touch(ch) {
+            return entry;
+        }
+        let result = self.generate(ch);
+        self.insert(ch, result)
+    }
+
+    /// Batch insert.
+    pub fn preload(&mut self, chars: &[char]) {
+        for &ch in chars {
+            self.get_or_insert(ch);
+        }
+    }
+
+    /// [`preload`](Self::preload) with the tiles generated on up to
+    /// `threads` scoped threads (0 = the available parallelism).
+    ///
+    /// Glyphs are generated in windows of [`PRELOAD_BATCH`] characters and
+    /// committed in input order on the calling thread, so slots, LRU order
+    /// and page pixels are exactly those `preload` would produce, whatever
+    /// the thread count.
+    #[cfg(feature = "std")]
+    pub fn preload_parallel(&mut self, chars: &[char], threads: usize) {
+        for window in chars.chunks(PRELOAD_BATCH) {
+            let mut pending: Vec<char> = Vec::new();
+            for &ch in window {
+                if !pending.contains(&ch) && !self.contains(ch) {
+                    pending.push(ch);
+                }
+            }
+            let batch =
+                self.providers
+                    .generate_batch(&pending, &self.params, &self.policy, threads);
+            let mut ready: Vec<Option<GlyphResult>> = pending
+                .iter()
+                .zip(batch)
+                .map(|(&ch, result)| Some(self.set_figures(ch, result)))
+                .collect();
+            for &ch in window {
+                self.clock += 1;
+                if self.touch(ch).is_some() {
+                    continue;
+                }
+                // A glyph evicted earlier in the window is generated again,
+                // as `preload` would.
+                let result = pending
+                    .iter()
+                    .position(|&c| c == ch)
+                    .and_then(|i| ready[i].take())
+                    .unwrap_or_else(|| self.generate(ch));
+                self.insert(ch, result);
+            }
+        }
+    }
 
-        // Cached?
+    /// Refresh the LRU timestamp of `ch` to the current clock if cached.
+    fn touch(&mut self, ch: char) -> Option<AtlasEntryMulti> {
+        let clock = self.clock;
         for page in &mut self.pages {
             for entry in page.entries.iter_mut().flatten() {
                 if entry.codepoint == ch {
                     entry.last_used = clock;
-                    return *entry;
+                    return Some(*entry);
                 }
             }
         }
+        None
+    }
 
-        let sdf = dispatcher::generate(ch, &self.params);
+    /// Store a generated glyph in a free (or the LRU) slot, stamped with
+    /// the current clock.
+    fn insert(&mut self, ch: char, result: GlyphResult) -> AtlasEntryMulti {
+        let clock = self.clock;
+        let GlyphResult {
+            sdf,
+            provenance,
+            stroke_count,
+            dropped_strokes,
+            metrics,
+        } = result;
         let (page_id, slot) = self.find_slot();
         let page = &mut self.pages[page_id];
         let tile_x = slot % page.dim;
@@ -465,9 +705,15 @@ impl SdfAtlasMulti {
             uv_y: (tile_y * GLYPH_SDF_SIZE) as f32 * inv_tex,
             uv_w: GLYPH_SDF_SIZE as f32 * inv_tex,
             uv_h: GLYPH_SDF_SIZE as f32 * inv_tex,
-            advance: sdf.advance,
-            lsb: sdf.lsb,
+            advance: metrics.advance,
+            lsb: metrics.lsb,
+            metrics,
+            bbox_min: sdf.bbox_min,
+            bbox_max: sdf.bbox_max,
             last_used: clock,
+            provenance,
+            stroke_count: stroke_count.min(u16::MAX as usize) as u16,
+            dropped_strokes: dropped_strokes.min(u16::MAX as usize) as u16,
         };
         if page.entries[slot].is_none() {
             page.occupied += 1;
@@ -476,13 +722,6 @@ impl SdfAtlasMulti {
         entry
     }
 
-    /// Batch insert.
-    pub fn preload(&mut self, chars: &[char]) {
-        for &ch in chars {
-            self.get_or_insert(ch);
-        }
-    }
-
     /// Empty every page.
     pub fn clear(&mut self) {
         for page in &mut self.pages {
@@ -500,9 +739,106 @@ impl SdfAtlasMulti {
     /// Change the rendering parameters, dropping every cached glyph.
     pub fn set_params(&mut self, params: MetaFontParams) {
         self.params = params;
+        self.figure_metrics = None;
+        self.clear();
+    }
+
+    /// Register a runtime font. Registered fonts are consulted in
+    /// registration order before the built-in glyph sources. Cached glyphs
+    /// are kept; call [`SdfAtlasMulti::clear`] to re-render them.
+    #[cfg(feature = "sfnt")]
+    pub fn register_font(&mut self, font: SfntFont) {
+        self.providers.register(font);
+        self.figure_metrics = None;
+    }
+
+    /// Number of runtime fonts in the provider chain.
+    #[cfg(feature = "sfnt")]
+    #[must_use]
+    pub const fn font_count(&self) -> usize {
+        self.providers.font_count()
+    }
+
+    /// Select where glyphs come from (outline table vs. parametric
+    /// skeletons). Drops every cached glyph if the policy changes.
+    pub fn set_source_policy(&mut self, policy: GlyphSourcePolicy) {
+        if self.policy != policy {
+            self.policy = policy;
+            self.figure_metrics = None;
+            self.clear();
+        }
+    }
+
+    /// Current glyph-source policy.
+    #[must_use]
+    pub const fn source_policy(&self) -> &GlyphSourcePolicy {
+        &self.policy
+    }
+
+    /// Font-wide metrics of the glyphs this atlas renders (registered
+    /// providers and fonts included), under the current source policy.
+    #[must_use]
+    pub fn font_metrics(&self) -> FontMetrics {
+        self.providers.font_metrics(&self.params, &self.policy)
+    }
+
+    /// Register a custom glyph provider (icon set, custom script, ...).
+    /// Registered providers are consulted in registration order before the
+    /// built-in ones. Cached glyphs are kept; call [`SdfAtlasMulti::clear`] to
+    /// re-render them.
+    pub fn register_provider(&mut self, provider: impl GlyphProvider + 'static) {
+        self.providers.register(provider);
+        self.figure_metrics = None;
+    }
+
+    /// Replace the whole provider chain, dropping every cached glyph.
+    pub fn set_providers(&mut self, providers: ProviderChain) {
+        self.providers = providers;
+        self.figure_metrics = None;
         self.clear();
     }
 
+    /// The provider chain glyphs are generated from.
+    #[must_use]
+    pub const fn providers(&self) -> &ProviderChain {
+        &self.providers
+    }
+
+    /// Set digits (and the tabular symbols) in `style`; `None` (the
+    /// default) keeps them as the glyph sources draw them. Drops every
+    /// cached glyph if the style changes.
+    pub fn set_figure_style(&mut self, style: Option<FigureStyle>) {
+        if self.figure_style != style {
+            self.figure_style = style;
+            self.clear();
+        }
+    }
+
+    /// Figure style digits are set in, if any.
+    #[must_use]
+    pub const fn figure_style(&self) -> Option<FigureStyle> {
+        self.figure_style
+    }
+
+    fn generate(&mut self, ch: char) -> GlyphResult {
+        let result = self.providers.generate(ch, &self.params, &self.policy);
+        self.set_figures(ch, result)
+    }
+
+    /// `result` (the glyph of `ch`) in the atlas's figure style.
+    fn set_figures(&mut self, ch: char, result: GlyphResult) -> GlyphResult {
+        match figure_key(ch, self.figure_style) {
+            Some(style) => {
+                let (providers, params, policy) = (&self.providers, &self.params, &self.policy);
+                let widths = *self
+                    .figure_metrics
+                    .get_or_insert_with(|| providers.figure_metrics(params, policy));
+                digits::apply(style, ch, result, params, &widths)
+            }
+            None => result,
+        }
+    }
+
     /// Find the first empty slot across all pages, or evict the LRU
     /// entry if every page is full. Returns `(page_id, slot_index)`.
     fn find_slot(&self) -> (usize, usize) {
@@ -532,6 +868,11 @@ impl SdfAtlasMulti {
     }
 }
 
+/// The figure style `style` sets `ch` in, if it touches `ch` at all.
+fn figure_key(ch: char, style: Option<FigureStyle>) -> Option<FigureStyle> {
+    style.filter(|&s| digits::is_styled(ch, s))
+}
+
 fn blit_tile(page: &mut SdfAtlasPage, tile_x: usize, tile_y: usize, sdf: &GlyphSdf) {
     let tex_w = page.texture_size();
     let base_x = tile_x * GLYPH_SDF_SIZE;
@@ -803,6 +1144,137 @@ mod tests {
         assert!(pixels.iter().any(|p| p.abs() > 0.001));
     }
 
+    #[test]
+    fn multi_atlas_source_policy_invalidates_on_change() {
+        use crate::glyph::dispatcher::{GlyphCategory, GlyphSource};
+        let mut atlas = SdfAtlasMulti::new(1, 4, MetaFontParams::serif_italic());
+        atlas.preload(&['A', 'B']);
+        atlas.set_source_policy(GlyphSourcePolicy::default());
+        assert_eq!(atlas.occupied(), 2);
+
+        let policy = GlyphSourcePolicy::outline_first()
+            .with_override(GlyphCategory::Ascii, GlyphSource::ParametricOnly);
+        atlas.set_source_policy(policy.clone());
+        assert_eq!(atlas.occupied(), 0);
+        assert_eq!(atlas.source_policy(), &policy);
+
+        atlas.get_or_insert('A');
+        let skeleton = GlyphGenerator::new(&MetaFontParams::serif_italic()).generate(b'A');
+        let page = atlas.page_pixels(0).unwrap();
+        let tex_w = atlas.page_size();
+        for row in 0..GLYPH_SDF_SIZE {
+            for col in 0..GLYPH_SDF_SIZE {
+                let expected = skeleton.data[row * GLYPH_SDF_SIZE + col];
+                assert!((page[row * tex_w + col] - expected).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn multi_atlas_figure_style_invalidates_on_change() {
+        let mut atlas = SdfAtlasMulti::new(1, 4, MetaFontParams::sans_regular());
+        atlas.preload(&['1', '8']);
+        atlas.set_figure_style(None);
+        assert_eq!(atlas.occupied(), 2);
+
+        atlas.set_figure_style(Some(FigureStyle::TabularLining));
+        assert_eq!(atlas.occupied(), 0);
+        #[cfg(feature = "std")]
+        atlas.preload_parallel(&['1', '8'], 2);
+        let one = atlas.get_or_insert('1').metrics.advance;
+        let eight = atlas.get_or_insert('8').metrics.advance;
+        assert!((one - eight).abs() < 1e-5);
+    }
+
+    #[test]
+    fn multi_atlas_custom_provider() {
+        use crate::glyph::provider::ProviderKind;
+
+        struct Icons;
+        impl GlyphProvider for Icons {
+            fn kind(&self) -> ProviderKind {
+                ProviderKind::Outline
+            }
+            fn covers(&self, ch: char) -> bool {
+                ch == '\u{E001}'
+            }
+            fn metrics(&self, _ch: char, _params: &MetaFontParams) -> Option<GlyphMetrics> {
+                Some(GlyphMetrics::blank(1.25))
+            }
+            fn generate(&self, _ch: char, _params: &MetaFontParams) -> Option<GlyphResult> {
+                let mut sdf = GlyphSdf::empty();
+                sdf.advance = 1.25;
+                Some(GlyphResult::outline(sdf))
+            }
+        }
+
+        let mut atlas = SdfAtlasMulti::new(1, 4, MetaFontParams::sans_regular());
+        let notdef = atlas.get_or_insert('\u{E001}');
+        assert_eq!(notdef.provenance, GlyphProvenance::Missing);
+        atlas.register_provider(Icons);
+        atlas.clear();
+        assert!((atlas.get_or_insert('\u{E001}').advance - 1.25).abs() < 1e-6);
+        assert_eq!(atlas.providers().len(), ProviderChain::builtin().len() + 1);
+
+        atlas.set_providers(ProviderChain::empty());
+        assert_eq!(atlas.occupied(), 0);
+        assert_eq!(
+            atlas.get_or_insert('A').provenance,
+            GlyphProvenance::Missing
+        );
+    }
+
+    #[test]
+    fn multi_atlas_reports_provenance() {
+        let mut atlas = SdfAtlasMulti::new(1, 4, MetaFontParams::sans_regular());
+        atlas.set_source_policy(GlyphSourcePolicy::parametric_only());
+        let a = atlas.get_or_insert('A');
+        assert_eq!(a.provenance, GlyphProvenance::Parametric);
+        assert!(a.stroke_count > 0);
+        assert!(a.is_complete());
+
+        let missing = atlas.get_or_insert('\u{E123}');
+        assert_eq!(missing.provenance, GlyphProvenance::Missing);
+        assert_eq!(missing.stroke_count, 0);
+
+        let flagged: Vec<char> = atlas
+            .entries()
+            .filter(|e| !e.is_complete())
+            .map(|e| e.codepoint)
+            .collect();
+        assert_eq!(flagged, vec!['\u{E123}']);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn multi_atlas_parallel_preload_matches_serial() {
+        // More characters than slots, with repeats, so the window both
+        // evicts and re-generates glyphs.
+        let chars: Vec<char> = "あいうえおかきくけこ明日本語AaBb一二三あかさたなA★╭"
+            .chars()
+            .collect();
+        for threads in [0, 4] {
+            let mut serial = SdfAtlasMulti::new(2, 3, MetaFontParams::sans_regular());
+            serial.get_or_insert('本');
+            serial.preload(&chars);
+            let mut parallel = SdfAtlasMulti::new(2, 3, MetaFontParams::sans_regular());
+            parallel.get_or_insert('本');
+            parallel.preload_parallel(&chars, threads);
+            let key =
+                |e: &AtlasEntryMulti| (e.codepoint, e.page_id, e.tile_x, e.tile_y, e.last_used);
+            let a: Vec<_> = serial.entries().map(key).collect();
+            let b: Vec<_> = parallel.entries().map(key).collect();
+            assert_eq!(a, b, "{threads} threads");
+            for page in 0..serial.num_pages() {
+                assert!(serial.page_pixels(page) == parallel.page_pixels(page));
+            }
+            assert_eq!(
+                serial.get_or_insert('Z').last_used,
+                parallel.get_or_insert('Z').last_used
+            );
+        }
+    }
+
     #[test]
     fn multi_atlas_page_pixels_out_of_range() {
         let atlas = SdfAtlasMulti::new(1, 2, MetaFontParams::sans_regular());
@@ -25,6 +25,14 @@ pub enum DiacriticType {
     Caron,
     /// マクロン (¯)。
     Macron,
+    /// ブレーヴェ (˘)。
+    Breve,
+    /// 上付きドット (˙)。
+    DotAbove,
+    /// ダブルアキュート (˝)。
+    DoubleAcute,
+    /// オゴネク (˛)。
+    Ogonek,
 }
 
 /// ダイアクリティカルマークの配置。
@@ -50,6 +58,10 @@ impl DiacriticType {
             Self::Ring => 0x030A,
             Self::Caron => 0x030C,
             Self::Macron => 0x0304,
+            Self::Breve => 0x0306,
+            Self::DotAbove => 0x0307,
+            Self::DoubleAcute => 0x030B,
+            Self::Ogonek => 0x0328,
         }
     }
 
@@ -57,7 +69,7 @@ impl DiacriticType {
     #[must_use]
     pub const fn default_position(&self) -> DiacriticPosition {
         match self {
-            Self::Cedilla => DiacriticPosition::Below,
+            Self::Cedilla | Self::Ogonek => DiacriticPosition::Below,
             _ => DiacriticPosition::Above,
         }
     }
@@ -81,138 +93,178 @@ struct DecompEntry {
     diacritic: DiacriticType,
 }
 
-/// よく使われる合成文字の分解テーブル。
-const DECOMP_TABLE: &[DecompEntry] = &[
-    DecompEntry {
-        composed: 'À',
-        base: 'A',
-        diacritic: DiacriticType::Grave,
-    },
-    DecompEntry {
-        composed: 'Á',
-        base: 'A',
-        diacritic: DiacriticType::Acute,
-    },
-    DecompEntry {
-        composed: 'Â',
-        base: 'A',
-        diacritic: DiacriticType::Circumflex,
-    },
-    DecompEntry {
-        composed: 'Ã',
-        base: 'A',
-        diacritic: DiacriticType::Tilde,
-    },
-    DecompEntry {
-        composed: 'Ä',
-        base: 'A',
-        diacritic: DiacriticType::Diaeresis,
-    },
-    DecompEntry {
-        composed: 'Å',
-        base: 'A',
-        diacritic: DiacriticType::Ring,
-    },
-    DecompEntry {
-        composed: 'Ç',
-        base: 'C',
-        diacritic: DiacriticType::Cedilla,
-    },
-    DecompEntry {
-        composed: 'È',
-        base: 'E',
-        diacritic: DiacriticType::Grave,
-    },
-    DecompEntry {
-        composed: 'É',
-        base: 'E',
-        diacritic: DiacriticType::Acute,
-    },
-    DecompEntry {
-        composed: 'Ê',
-        base: 'E',
-        diacritic: DiacriticType::Circumflex,
-    },
-    DecompEntry {
-        composed: 'Ë',
-        base: 'E',
-        diacritic: DiacriticType::Diaeresis,
-    },
-    DecompEntry {
-        composed: 'Ñ',
-        base: 'N',
-        diacritic: DiacriticType::Tilde,
-    },
-    DecompEntry {
-        composed: 'Ö',
-        base: 'O',
-        diacritic: DiacriticType::Diaeresis,
-    },
-    DecompEntry {
-        composed: 'Ü',
-        base: 'U',
-        diacritic: DiacriticType::Diaeresis,
-    },
-    DecompEntry {
-        composed: 'à',
-        base: 'a',
-        diacritic: DiacriticType::Grave,
-    },
-    DecompEntry {
-        composed: 'á',
-        base: 'a',
-        diacritic: DiacriticType::Acute,
-    },
-    DecompEntry {
-        composed: 'â',
-        base: 'a',
-        diacritic: DiacriticType::Circumflex,
-    },
-    DecompEntry {
-        composed: 'ä',
-        base: 'a',
-        diacritic: DiacriticType::Diaeresis,
-    },
+const fn entry(composed: char, base: char, diacritic: DiacriticType) -> DecompEntry {
     DecompEntry {
-        composed: 'ç',
-        base: 'c',
-        diacritic: DiacriticType::Cedilla,
-    },
-    DecompEntry {
-        composed: 'è',
-        base: 'e',
-        diacritic: DiacriticType::Grave,
-    },
-    DecompEntry {
-        composed: 'é',
-        base: 'e',
-        diacritic: DiacriticType::Acute,
-    },
-    DecompEntry {
-        composed: 'ê',
-        base: 'e',
-        diacritic: DiacriticType::Circumflex,
-    },
-    DecompEntry {
-        composed: 'ë',
-        base: 'e',
-        diacritic: DiacriticType::Diaeresis,
-    },
-    DecompEntry {
-        composed: 'ñ',
-        base: 'n',
-        diacritic: DiacriticType::Tilde,
-    },
-    DecompEntry {
-        composed: 'ö',
-        base: 'o',
-        diacritic: DiacriticType::Diaeresis,
-    },
-    DecompEntry {
-        composed: 'ü',
-        base: 'u',
-        diacritic: DiacriticType::Diaeresis,
-    },
+        composed,
+        base,
+        diacritic,
+    }
+}
+
+/// Latin-1 Supplement / Latin Extended-A の正準分解テーブル
+/// (ベース文字 + 結合記号 1 つ)。
+const DECOMP_TABLE: &[DecompEntry] = &[
+    entry('À', 'A', DiacriticType::Grave),
+    entry('Á', 'A', DiacriticType::Acute),
+    entry('Â', 'A', DiacriticType::Circumflex),
+    entry('Ã', 'A', DiacriticType::Tilde),
+    entry('Ä', 'A', DiacriticType::Diaeresis),
+    entry('Å', 'A', DiacriticType::Ring),
+    entry('Ç', 'C', DiacriticType::Cedilla),
+    entry('È', 'E', DiacriticType::Grave),
+    entry('É', 'E', DiacriticType::Acute),
+    entry('Ê', 'E', DiacriticType::Circumflex),
+    entry('Ë', 'E', DiacriticType::Diaeresis),
+    entry('Ì', 'I', DiacriticType::Grave),
+    entry('Í', 'I', DiacriticType::Acute),
+    entry('Î', 'I', DiacriticType::Circumflex),
+    entry('Ï', 'I', DiacriticType::Diaeresis),
+    entry('Ñ', 'N', DiacriticType::Tilde),
+    entry('Ò', 'O', DiacriticType::Grave),
+    entry('Ó', 'O', DiacriticType::Acute),
+    entry('Ô', 'O', DiacriticType::Circumflex),
+    entry('Õ', 'O', DiacriticType::Tilde),
+    entry('Ö', 'O', DiacriticType::Diaeresis),
+    entry('Ù', 'U', DiacriticType::Grave),
+    entry('Ú', 'U', DiacriticType::Acute),
+    entry('Û', 'U', DiacriticType::Circumflex),
+    entry('Ü', 'U', DiacriticType::Diaeresis),
+    entry('Ý', 'Y', DiacriticType::Acute),
+    entry('à', 'a', DiacriticType::Grave),
+    entry('á', 'a', DiacriticType::Acute),
+    entry('â', 'a', DiacriticType::Circumflex),
+    entry('ã', 'a', DiacriticType::Tilde),
+    entry('ä', 'a', DiacriticType::Diaeresis),
+    entry('å', 'a', DiacriticType::Ring),
+    entry('ç', 'c', DiacriticType::Cedilla),
+    entry('è', 'e', DiacriticType::Grave),
+    entry('é', 'e', DiacriticType::Acute),
+    entry('ê', 'e', DiacriticType::Circumflex),
+    entry('ë', 'e', DiacriticType::Diaeresis),
+    entry('ì', 'i', DiacriticType::Grave),
+    entry('í', 'i', DiacriticType::Acute),
+    entry('î', 'i', DiacriticType::Circumflex),
+    entry('ï', 'i', DiacriticType::Diaeresis),
+    entry('ñ', 'n', DiacriticType::Tilde),
+    entry('ò', 'o', DiacriticType::Grave),
+    entry('ó', 'o', DiacriticType::Acute),
+    entry('ô', 'o', DiacriticType::Circumflex),
+    entry('õ', 'o', DiacriticType::Tilde),
+    entry('ö', 'o', DiacriticType::Diaeresis),
+    entry('ù', 'u', DiacriticType::Grave),
+    entry('ú', 'u', DiacriticType::Acute),
+    entry('û', 'u', DiacriticType::Circumflex),
+    entry('ü', 'u', DiacriticType::Diaeresis),
+    entry('ý', 'y', DiacriticType::Acute),
+    entry('ÿ', 'y', DiacriticType::Diaeresis),
+    entry('Ā', 'A', DiacriticType::Macron),
+    entry('ā', 'a', DiacriticType::Macron),
+    entry('Ă', 'A', DiacriticType::Breve),
+    entry('ă', 'a', DiacriticType::Breve),
+    entry('Ą', 'A', DiacriticType::Ogonek),
+    entry('ą', 'a', DiacriticType::Ogonek),
+    entry('Ć', 'C', DiacriticType::Acute),
+    entry('ć', 'c', DiacriticType::Acute),
+    entry('Ĉ', 'C', DiacriticType::Circumflex),
+    entry('ĉ', 'c', DiacriticType::Circumflex),
+    entry('Ċ', 'C', DiacriticType::DotAbove),
+    entry('ċ', 'c', DiacriticType::DotAbove),
+    entry('Č', 'C', DiacriticType::Caron),
+    entry('č', 'c', DiacriticType::Caron),
+    entry('Ď', 'D', DiacriticType::Caron),
+    entry('ď', 'd', DiacriticType::Caron),
+    entry('Ē', 'E', DiacriticType::Macron),
+    entry('ē', 'e', DiacriticType::Macron),
+    entry('Ĕ', 'E', DiacriticType::Breve),
+    entry('ĕ', 'e', DiacriticType::Breve),
+    entry('Ė', 'E', DiacriticType::DotAbove),
+    entry('ė', 'e', DiacriticType::DotAbove),
+    entry('Ę', 'E', DiacriticType::Ogonek),
+    entry('ę', 'e', DiacriticType::Ogonek),
+    entry('Ě', 'E', DiacriticType::Caron),
+    entry('ě', 'e', DiacriticType::Caron),
+    entry('Ĝ', 'G', DiacriticType::Circumflex),
+    entry('ĝ', 'g', DiacriticType::Circumflex),
+    entry('Ğ', 'G', DiacriticType::Breve),
+    entry('ğ', 'g', DiacriticType::Breve),
+    entry('Ġ', 'G', DiacriticType::DotAbove),
+    entry('ġ', 'g', DiacriticType::DotAbove),
+    entry('Ģ', 'G', DiacriticType::Cedilla),
+    entry('ģ', 'g', DiacriticType::Cedilla),
+    entry('Ĥ', 'H', DiacriticType::Circumflex),
+    entry('ĥ', 'h', DiacriticType::Circumflex),
+    entry('Ĩ', 'I', DiacriticType::Tilde),
+    entry('ĩ', 'i', DiacriticType::Tilde),
+    entry('Ī', 'I', DiacriticType::Macron),
+    entry('ī', 'i', DiacriticType::Macron),
+    entry('Ĭ', 'I', DiacriticType::Breve),
+    entry('ĭ', 'i', DiacriticType::Breve),
+    entry('Į', 'I', DiacriticType::Ogonek),
+    entry('į', 'i', DiacriticType::Ogonek),
+    entry('İ', 'I', DiacriticType::DotAbove),
+    entry('Ĵ', 'J', DiacriticType::Circumflex),
+    entry('ĵ', 'j', DiacriticType::Circumflex),
+    entry('Ķ', 'K', DiacriticType::Cedilla),
+    entry('ķ', 'k', DiacriticType::Cedilla),
+    entry('Ĺ', 'L', DiacriticType::Acute),
+    entry('ĺ', 'l', DiacriticType::Acute),
+    entry('Ļ', 'L', DiacriticType::Cedilla),
+    entry('ļ', 'l', DiacriticType::Cedilla),
+    entry('Ľ', 'L', DiacriticType::Caron),
+    entry('ľ', 'l', DiacriticType::Caron),
+    entry('Ń', 'N', DiacriticType::Acute),
+    entry('ń', 'n', DiacriticType::Acute),
+    entry('Ņ', 'N', DiacriticType::Cedilla),
+    entry('ņ', 'n', DiacriticType::Cedilla),
+    entry('Ň', 'N', DiacriticType::Caron),
+    entry('ň', 'n', DiacriticType::Caron),
+    entry('Ō', 'O', DiacriticType::Macron),
+    entry('ō', 'o', DiacriticType::Macron),
+    entry('Ŏ', 'O', DiacriticType::Breve),
+    entry('ŏ', 'o', DiacriticType::Breve),
+    entry('Ő', 'O', DiacriticType::DoubleAcute),
+    entry('ő', 'o', DiacriticType::DoubleAcute),
+    entry('Ŕ', 'R', DiacriticType::Acute),
+    entry('ŕ', 'r', DiacriticType::Acute),
+    entry('Ŗ', 'R', DiacriticType::Cedilla),
+    entry('ŗ', 'r', DiacriticType::Cedilla),
+    entry('Ř', 'R', DiacriticType::Caron),
+    entry('ř', 'r', DiacriticType::Caron),
+    entry('Ś', 'S', DiacriticType::Acute),
+    entry('ś', 's', DiacriticType::Acute),
+    entry('Ŝ', 'S', DiacriticType::Circumflex),
+    entry('ŝ', 's', DiacriticType::Circumflex),
+    entry('Ş', 'S', DiacriticType::Cedilla),
+    entry('ş', 's', DiacriticType::Cedilla),
+    entry('Š', 'S', DiacriticType::Caron),
+    entry('š', 's', DiacriticType::Caron),
+    entry('Ţ', 'T', DiacriticType::Cedilla),
+    entry('ţ', 't', DiacriticType::Cedilla),
+    entry('Ť', 'T', DiacriticType::Caron),
+    entry('ť', 't', DiacriticType::Caron),
+    entry('Ũ', 'U', DiacriticType::Tilde),
+    entry('ũ', 'u', DiacriticType::Tilde),
+    entry('Ū', 'U', DiacriticType::Macron),
+    entry('ū', 'u', DiacriticType::Macron),
+    entry('Ŭ', 'U', DiacriticType::Breve),
+    entry('ŭ', 'u', DiacriticType::Breve),
+    entry('Ů', 'U', DiacriticType::Ring),
+    entry('ů', 'u', DiacriticType::Ring),
+    entry('Ű', 'U', DiacriticType::DoubleAcute),
+    entry('ű', 'u', DiacriticType::DoubleAcute),
+    entry('Ų', 'U', DiacriticType::Ogonek),
+    entry('ų', 'u', DiacriticType::Ogonek),
+    entry('Ŵ', 'W', DiacriticType::Circumflex),
+    entry('ŵ', 'w', DiacriticType::Circumflex),
+    entry('Ŷ', 'Y', DiacriticType::Circumflex),
+    entry('ŷ', 'y', DiacriticType::Circumflex),
+    entry('Ÿ', 'Y', DiacriticType::Diaeresis),
+    entry('Ź', 'Z', DiacriticType::Acute),
+    entry('ź', 'z', DiacriticType::Acute),
+    entry('Ż', 'Z', DiacriticType::DotAbove),
+    entry('ż', 'z', DiacriticType::DotAbove),
+    entry('Ž', 'Z', DiacriticType::Caron),
+    entry('ž', 'z', DiacriticType::Caron),
 ];
 
 /// 合成文字を分解。
@@ -333,6 +385,26 @@ mod tests {
         assert_ne!(DiacriticType::Acute, DiacriticType::Grave);
     }
 
+    #[test]
+    fn latin_extended_a_decomposable() {
+        for (c, base, diacritic) in [
+            ('Ł', None, None),
+            ('ğ', Some('g'), Some(DiacriticType::Breve)),
+            ('ő', Some('o'), Some(DiacriticType::DoubleAcute)),
+            ('ą', Some('a'), Some(DiacriticType::Ogonek)),
+            ('İ', Some('I'), Some(DiacriticType::DotAbove)),
+            ('ž', Some('z'), Some(DiacriticType::Caron)),
+        ] {
+            let d = decompose_char(c);
+            assert_eq!(d.as_ref().map(|d| d.base_char), base, "{c}");
+            assert_eq!(d.map(|d| d.diacritics[0]), diacritic, "{c}");
+        }
+        assert_eq!(
+            DiacriticType::Ogonek.default_position(),
+            DiacriticPosition::Below
+        );
+    }
+
     #[test]
     fn all_uppercase_decomposable() {
         for &c in &[
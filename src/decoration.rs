@@ -0,0 +1,290 @@
+//! Text decorations — underline, double underline, strikethrough, overline
+//!
+//! Decorations are emitted as plain geometry (horizontal strokes in em
+//! units) next to the shaped glyphs, so renderers draw them with the same
+//! quad or SDF pipeline as everything else. Positions and thicknesses come
+//! from [`FontMetrics`], which measures them from the font's own `_` and
+//! `-` glyphs.
+//!
+//! With ink skipping on, underlines and overlines are broken where they
+//! would touch the ink of a glyph — the descenders of g, j, p, y, or kana that
+//! dip below the baseline. The glyph's SDF tile gives the distance from
+//! the stroke to the ink directly, so the gaps follow the actual shapes
+//! and keep one stroke thickness of clearance. Strikethroughs are never
+//! broken.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use alloc::vec::Vec;
+
+use crate::glyph::{FontMetrics, GLYPH_SDF_SIZE};
+use crate::stroke::Point2;
+
+/// Distance between the two strokes of a double underline, in stroke
+/// thicknesses (edge to edge).
+const DOUBLE_UNDERLINE_GAP: f32 = 1.0;
+/// Gap between the ascent and an overline, in stroke thicknesses.
+const OVERLINE_GAP: f32 = 0.5;
+/// Clearance kept between skipped decorations and the ink, in stroke
+/// thicknesses.
+const SKIP_INK_CLEARANCE: f32 = 1.0;
+
+/// Which decorations a line gets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Decorations(u8);
+
+impl Decorations {
+    /// No decoration.
+    pub const NONE: Self = Self(0);
+    /// Single underline.
+    pub const UNDERLINE: Self = Self(1);
+    /// Two underlines; replaces [`Decorations::UNDERLINE`] when both are set.
+    pub const DOUBLE_UNDERLINE: Self = Self(1 << 1);
+    /// Line through the middle of the lowercase letters.
+    pub const STRIKETHROUGH: Self = Self(1 << 2);
+    /// Line above the ascenders.
+    pub const OVERLINE: Self = Self(1 << 3);
+}
+
+flag_set!(Decorations);
+
+/// Kind of a decoration stroke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecorationKind {
+    Underline,
+    Strikethrough,
+    Overline,
+}
+
+impl DecorationKind {
+    /// Is this kind broken around the ink when skipping is on?
+    #[must_use]
+    pub const fn skips_ink(self) -> bool {
+        matches!(self, Self::Underline | Self::Overline)
+    }
+}
+
+/// A horizontal decoration stroke.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecorationLine {
+    /// What the stroke decorates
+    pub kind: DecorationKind,
+    /// Left end (em units from line start)
+    pub x_start: f32,
+    /// Right end (em units from line start)
+    pub x_end: f32,
+    /// Centre of the stroke relative to the baseline (negative = below);
+    /// the line's `y_offset` applies as for glyphs
+    pub y: f32,
+    /// Stroke thickness (em units)
+    pub thickness: f32,
+}
+
+/// Unbroken strokes for `decorations` from `x_start` to `x_end`.
+#[must_use]
+pub fn lines(
+    decorations: Decorations,
+    metrics: &FontMetrics,
+    x_start: f32,
+    x_end: f32,
+) -> Vec<DecorationLine> {
+    let line = |kind, y, thickness| DecorationLine {
+        kind,
+        x_start,
+        x_end,
+        y,
+        thickness,
+    };
+    let (y, t) = (metrics.underline_position, metrics.underline_thickness);
+    let mut out = Vec::new();
+    if decorations.contains(Decorations::DOUBLE_UNDERLINE) {
+        out.push(line(DecorationKind::Underline, y, t));
+        out.push(line(
+            DecorationKind::Underline,
+            y - t * (1.0 + DOUBLE_UNDERLINE_GAP),
+            t,
+        ));
+    } else if decorations.contains(Decorations::UNDERLINE) {
+        out.push(line(DecorationKind::Underline, y, t));
+    }
+    if decorations.contains(Decorations::OVERLINE) {
+        out.push(line(
+            DecorationKind::Overline,
+            metrics.ascent + t * (0.5 + OVERLINE_GAP),
+            t,
+        ));
+    }
+    if decorations.contains(Decorations::STRIKETHROUGH) {
+        out.push(line(
+            DecorationKind::Strikethrough,
+            metrics.strikeout_position,
+            metrics.strikeout_thickness,
+        ));
+    }
+    out
+}
+
+/// Horizontal spans (em, relative to the glyph's pen origin) that `line`
+/// must leave out to keep its clearance from the ink of a glyph tile.
+///
+/// `sample(col, row)` reads the tile (row 0 at `bbox_min.y`), whose
+/// samples span `bbox_min..bbox_max`. A column is blocked when the SDF
+/// anywhere across the stroke is closer to the ink than the clearance.
+pub fn ink_spans(
+    line: &DecorationLine,
+    sample: impl Fn(usize, usize) -> f32,
+    bbox_min: Point2,
+    bbox_max: Point2,
+) -> Vec<(f32, f32)> {
+    let last = (GLYPH_SDF_SIZE - 1) as f32;
+    let step_x = (bbox_max.x - bbox_min.x) / last;
+    let step_y = (bbox_max.y - bbox_min.y) / last;
+    let clearance = line.thickness * SKIP_INK_CLEARANCE;
+    let pad = step_x * 0.5;
+    let (lo, hi) = (line.y - line.thickness * 0.5, line.y + line.thickness * 0.5);
+    let mut spans = Vec::new();
+    if step_x <= 0.0 || step_y <= 0.0 || hi + clearance < bbox_min.y || lo - clearance > bbox_max.y
+    {
+        return spans;
+    }
+    // Rows across the stroke, plus the nearest one on either side so
+    // strokes thinner than a texel are still tested.
+    let row = |y: f32| ((y - bbox_min.y) / step_y).clamp(0.0, last);
+    let (first, top) = (row(lo) as usize, row(hi));
+    let end = top as usize + usize::from((top as usize as f32) < top);
+    let mut open: Option<f32> = None;
+    for col in 0..GLYPH_SDF_SIZE {
+        let x = bbox_min.x + col as f32 * step_x;
+        let blocked = (first..=end).any(|r| sample(col, r) < clearance);
+        match (blocked, open) {
+            (true, None) => open = Some(x - pad),
+            (false, Some(start)) => {
+                spans.push((start, x - step_x + pad));
+                open = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = open {
+        spans.push((start, bbox_max.x + pad));
+    }
+    spans
+}
+
+/// `line` with the x spans in `gaps` cut out. Pieces shorter than the
+/// stroke thickness are dropped.
+#[must_use]
+pub fn skip(line: DecorationLine, gaps: &[(f32, f32)]) -> Vec<DecorationLine> {
+    let mut gaps: Vec<(f32, f32)> = gaps
+        .iter()
+        .copied()
+        .filter(|&(a, b)| b > line.x_start && a < line.x_end)
+        .collect();
+    gaps.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+    let mut out = Vec::new();
+    let mut x = line.x_start;
+    for (a, b) in gaps.into_iter().chain([(line.x_end, line.x_end)]) {
+        if a - x >= line.thickness {
+            out.push(DecorationLine {
+                x_start: x,
+                x_end: a,
+                ..line
+            });
+        }
+        x = x.max(b);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::dispatcher::{generate_result, GlyphSourcePolicy};
+    use crate::param::MetaFontParams;
+
+    fn metrics() -> FontMetrics {
+        FontMetrics::new(
+            &MetaFontParams::sans_regular(),
+            &GlyphSourcePolicy::default(),
+        )
+    }
+
+    #[test]
+    fn lines_follow_font_metrics() {
+        let m = metrics();
+        let all = Decorations::UNDERLINE | Decorations::STRIKETHROUGH | Decorations::OVERLINE;
+        let out = lines(all, &m, 0.0, 2.0);
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0].kind, DecorationKind::Underline);
+        assert!((out[0].y - m.underline_position).abs() < 1e-6 && out[0].y < 0.0);
+        assert!(out[1].y - out[1].thickness * 0.5 > m.ascent);
+        assert!((out[2].y - m.strikeout_position).abs() < 1e-6);
+        assert!(out.iter().all(|l| l.x_start == 0.0 && l.x_end == 2.0));
+        assert!(lines(Decorations::NONE, &m, 0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn double_underline_replaces_single() {
+        let m = metrics();
+        let out = lines(
+            Decorations::UNDERLINE | Decorations::DOUBLE_UNDERLINE,
+            &m,
+            0.0,
+            1.0,
+        );
+        assert_eq!(out.len(), 2);
+        let gap = (out[0].y - out[1].y) - out[0].thickness;
+        assert!((gap - out[0].thickness * DOUBLE_UNDERLINE_GAP).abs() < 1e-6);
+    }
+
+    #[test]
+    fn descenders_block_the_underline() {
+        let params = MetaFontParams::sans_regular();
+        let m = metrics();
+        let underline = lines(Decorations::UNDERLINE, &m, 0.0, 1.0)[0];
+        let spans = |ch| {
+            let sdf = generate_result(ch, &params, &GlyphSourcePolicy::default()).sdf;
+            ink_spans(
+                &underline,
+                |c, r| sdf.data[r * GLYPH_SDF_SIZE + c],
+                sdf.bbox_min,
+                sdf.bbox_max,
+            )
+        };
+        for ch in ['g', 'j', 'p', 'y'] {
+            assert!(!spans(ch).is_empty(), "{ch}");
+        }
+        for ch in ['a', 'x', 'H', 'o', 'あ', 'の'] {
+            assert!(spans(ch).is_empty(), "{ch}");
+        }
+        // The embedded kana have descending tails.
+        #[cfg(feature = "outlines-kana")]
+        for ch in ['す', 'ゆ'] {
+            assert!(!spans(ch).is_empty(), "{ch}");
+        }
+        // The descender of p is on its left.
+        let p = spans('p');
+        let advance = generate_result('p', &params, &GlyphSourcePolicy::default())
+            .metrics
+            .advance;
+        assert_eq!(p.len(), 1);
+        assert!(p[0].0 + p[0].1 < advance, "{p:?}");
+    }
+
+    #[test]
+    fn skip_cuts_gaps_and_drops_slivers() {
+        let line = DecorationLine {
+            kind: DecorationKind::Underline,
+            x_start: 0.0,
+            x_end: 2.0,
+            y: -0.1,
+            thickness: 0.05,
+        };
+        let out = skip(line, &[(1.2, 1.5), (0.3, 0.6), (0.5, 0.7), (1.98, 2.4)]);
+        let ends: Vec<(f32, f32)> = out.iter().map(|l| (l.x_start, l.x_end)).collect();
+        assert_eq!(ends, [(0.0, 0.3), (0.7, 1.2), (1.5, 1.98)]);
+        assert_eq!(skip(line, &[]), [line]);
+        assert!(skip(line, &[(-1.0, 3.0)]).is_empty());
+    }
+}
@@ -0,0 +1,665 @@
+//! Box Drawing and Block Elements — U+2500 to U+259F
+//!
+//! Terminal graphics are geometry, not lettering, so they skip the stroke
+//! skeletons: every glyph is a handful of rectangles, arcs and diagonals
+//! evaluated as an exact signed distance field.
+//!
+//! - The tile frame is the character cell itself: `CELL_ADVANCE` wide and
+//!   spanning the descender to the ascender of the [`MetaFontParams`], so
+//!   the first and last sample columns and rows lie on the cell edges. At
+//!   a line pitch of `ascender + descender` (what
+//!   [`FontMetrics::from_params`](crate::glyph::FontMetrics::from_params)
+//!   reports) neighbouring cells meet without seams.
+//! - Geometry that touches an edge continues past it, so the distance at
+//!   the edge equals the distance the neighbouring cell sees.
+//! - Line weights follow `MetaFontParams::weight`: heavy lines are twice
+//!   the light ones, double lines are two light lines one line width apart.
+//! - Shades (░ ▒ ▓) are dot lattices whose period divides the cell.
+//!
+//! Slant and contrast are ignored; box drawing stays upright and
+//! monoline.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use alloc::vec::Vec;
+
+use crate::glyph::float::floor;
+use crate::glyph::font_render::fast_sqrt;
+use crate::glyph::outline::point_line_dist_sq;
+use crate::glyph::{GlyphMetrics, GlyphResult, GlyphSdf};
+use crate::param::MetaFontParams;
+use crate::stroke::Point2;
+
+/// Advance of every glyph in both blocks: a half-width terminal cell.
+pub const CELL_ADVANCE: f32 = 0.5;
+
+/// Light half-width relative to `MetaFontParams::stroke_half_width`.
+const LIGHT_SCALE: f32 = 0.7;
+/// Thinnest light half-width (em).
+const MIN_LIGHT: f32 = 0.008;
+/// Thickest light half-width (em); keeps a double line inside half the cell.
+const MAX_LIGHT: f32 = CELL_ADVANCE / 12.0;
+/// How far geometry touching the cell edge continues past it (em).
+const OVERHANG: f32 = 0.25;
+/// Fraction of each dash period that is inked.
+const DASH_FILL: f32 = 0.6;
+/// Shade lattice columns and rows per cell. Both even, so the two-cell
+/// shade patterns repeat exactly from cell to cell.
+const SHADE_COLUMNS: i32 = 4;
+const SHADE_ROWS: i32 = 8;
+
+/// Arm weights of U+2500 to U+257F, one character per arm in the order
+/// up, right, down, left: `.` none, `l` light, `h` heavy, `d` double.
+/// Empty entries are the dashed lines, arcs and diagonals.
+#[rustfmt::skip]
+const ARMS: [&str; 128] = [
+    // 2500 ─ ━ │ ┃ ┄ ┅ ┆ ┇ ┈ ┉ ┊ ┋ ┌ ┍ ┎ ┏
+    ".l.l", ".h.h", "l.l.", "h.h.", "", "", "", "", "", "", "", "", ".ll.", ".hl.", ".lh.", ".hh.",
+    // 2510 ┐ ┑ ┒ ┓ └ ┕ ┖ ┗ ┘ ┙ ┚ ┛ ├ ┝ ┞ ┟
+    "..ll", "..lh", "..hl", "..hh", "ll..", "lh..", "hl..", "hh..",
+    "l..l", "l..h", "h..l", "h..h", "lll.", "lhl.", "hll.", "llh.",
+    // 2520 ┠ ┡ ┢ ┣ ┤ ┥ ┦ ┧ ┨ ┩ ┪ ┫ ┬ ┭ ┮ ┯
+    "hlh.", "hhl.", "lhh.", "hhh.", "l.ll", "l.lh", "h.ll", "l.hl",
+    "h.hl", "h.lh", "l.hh", "h.hh", ".lll", ".llh", ".hll", ".hlh",
+    // 2530 ┰ ┱ ┲ ┳ ┴ ┵ ┶ ┷ ┸ ┹ ┺ ┻ ┼ ┽ ┾ ┿
+    ".lhl", ".lhh", ".hhl", ".hhh", "ll.l", "ll.h", "lh.l", "lh.h",
+    "hl.l", "hl.h", "hh.l", "hh.h", "llll", "lllh", "lhll", "lhlh",
+    // 2540 ╀ ╁ ╂ ╃ ╄ ╅ ╆ ╇ ╈ ╉ ╊ ╋ ╌ ╍ ╎ ╏
+    "hlll", "llhl", "hlhl", "hllh", "hhll", "llhh", "lhhl", "hhlh",
+    "lhhh", "hlhh", "hhhl", "hhhh", "", "", "", "",
+    // 2550 ═ ║ ╒ ╓ ╔ ╕ ╖ ╗ ╘ ╙ ╚ ╛ ╜ ╝ ╞ ╟
+    ".d.d", "d.d.", ".dl.", ".ld.", ".dd.", "..ld", "..dl", "..dd",
+    "ld..", "dl..", "dd..", "l..d", "d..l", "d..d", "ldl.", "dld.",
+    // 2560 ╠ ╡ ╢ ╣ ╤ ╥ ╦ ╧ ╨ ╩ ╪ ╫ ╬ ╭ ╮ ╯
+    "ddd.", "l.ld", "d.dl", "d.dd", ".dld", ".ldl", ".ddd", "ld.d",
+    "dl.l", "dd.d", "ldld", "dldl", "dddd", "", "", "",
+    // 2570 ╰ ╱ ╲ ╳ ╴ ╵ ╶ ╷ ╸ ╹ ╺ ╻ ╼ ╽ ╾ ╿
+    "", "", "", "", "...l", "l...", ".l..", "..l.",
+    "...h", "h...", ".h..", "..h.", ".h.l", "l.h.", ".l.h", "h.l.",
+];
+
+/// Does this module draw `ch`? Every code point of both blocks is covered.
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    matches!(ch as u32, 0x2500..=0x259F)
+}
+
+/// Generate the SDF for a box-drawing or block-element character, or
+/// `None` outside U+2500 to U+259F.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams) -> Option<GlyphSdf> {
+    let cell = Cell::new(params);
+    shapes(ch, &cell).map(|shapes| rasterize(&shapes, &cell))
+}
+
+/// Metrics of `ch` without rasterizing it. The ink box is clipped to the
+/// cell, so full-width glyphs have zero side bearings.
+#[must_use]
+pub fn metrics(ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+    let cell = Cell::new(params);
+    shapes(ch, &cell).map(|shapes| ink_metrics(&shapes, &cell))
+}
+
+/// [`generate`] with provenance and metrics.
+#[must_use]
+pub fn render(ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+    let cell = Cell::new(params);
+    let shapes = shapes(ch, &cell)?;
+    Some(GlyphResult::procedural(
+        rasterize(&shapes, &cell),
+        ink_metrics(&shapes, &cell),
+    ))
+}
+
+/// The character cell and line weights for one set of parameters.
+struct Cell {
+    min: Point2,
+    max: Point2,
+    centre: Point2,
+    light: f32,
+}
+
+impl Cell {
+    fn new(params: &MetaFontParams) -> Self {
+        let min = Point2::new(0.0, -params.descender);
+        let max = Point2::new(CELL_ADVANCE, params.ascender);
+        Self {
+            min,
+            max,
+            centre: Point2::new((min.x + max.x) * 0.5, (min.y + max.y) * 0.5),
+            light: (params.stroke_half_width() * LIGHT_SCALE).clamp(MIN_LIGHT, MAX_LIGHT),
+        }
+    }
+
+    fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    /// Point at fractions `(fx, fy)` of the cell.
+    fn at(&self, fx: f32, fy: f32) -> Point2 {
+        Point2::new(
+            self.min.x + fx * self.width(),
+            self.min.y + fy * self.height(),
+        )
+    }
+
+    /// Rectangle between the cell fractions `(fx0, fy0)` and `(fx1, fy1)`;
+    /// sides on the cell edge continue past it.
+    fn block(&self, fx0: f32, fy0: f32, fx1: f32, fy1: f32) -> Shape {
+        let out = |f: f32, lo: f32, hi: f32| {
+            if f <= 0.0 {
+                lo - OVERHANG
+            } else if f >= 1.0 {
+                hi + OVERHANG
+            } else {
+                lo + f * (hi - lo)
+            }
+        };
+        Shape::Rect {
+            min: Point2::new(
+                out(fx0, self.min.x, self.max.x),
+                out(fy0, self.min.y, self.max.y),
+            ),
+            max: Point2::new(
+                out(fx1, self.min.x, self.max.x),
+                out(fy1, self.min.y, self.max.y),
+            ),
+        }
+    }
+
+    /// Half-width of a single line of `weight`.
+    fn half_width(&self, weight: u8) -> f32 {
+        if weight == b'h' {
+            2.0 * self.light
+        } else {
+            self.light
+        }
+    }
+
+    /// Distance from the centre line to the centre of each double line.
+    fn double_offset(&self) -> f32 {
+        2.0 * self.light
+    }
+
+    /// How far the lines of an arm of `weight` reach sideways.
+    fn extent(&self, weight: u8) -> f32 {
+        match weight {
+            b'.' => 0.0,
+            b'd' => self.double_offset() + self.light,
+            w => self.half_width(w),
+        }
+    }
+}
+
+/// Geometry primitives, in em units.
+enum Shape {
+    /// Axis-aligned filled rectangle
+    Rect { min: Point2, max: Point2 },
+    /// Straight line with round ends
+    Segment { a: Point2, b: Point2, hw: f32 },
+    /// Quarter circle around `centre` on the side `(sx, sy)` points away
+    /// from, i.e. the quadrant where `(p - centre) · (sx, sy) <= 0` per axis
+    Arc {
+        centre: Point2,
+        radius: f32,
+        hw: f32,
+        sx: f32,
+        sy: f32,
+    },
+    /// Dot lattice covering the whole plane
+    Shade(Shade),
+}
+
+#[derive(Clone, Copy)]
+enum Shade {
+    /// ░ every other lattice cell in both directions (25 %)
+    Light,
+    /// ▒ checkerboard (50 %)
+    Medium,
+    /// ▓ the complement of ░ (75 %)
+    Dark,
+}
+
+/// Unit vectors of the arms: up, right, down, left.
+const DIRECTIONS: [(f32, f32); 4] = [(0.0, 1.0), (1.0, 0.0), (0.0, -1.0), (-1.0, 0.0)];
+
+fn shapes(ch: char, cell: &Cell) -> Option<Vec<Shape>> {
+    let cp = ch as u32;
+    let mut out = Vec::new();
+    match cp {
+        0x2504..=0x250B => {
+            let i = cp - 0x2504;
+            dashes(
+                &mut out,
+                cell,
+                i & 2 != 0,
+                i & 1 != 0,
+                3 + (i >> 2) as usize,
+            );
+        }
+        0x254C..=0x254F => {
+            let i = cp - 0x254C;
+            dashes(&mut out, cell, i & 2 != 0, i & 1 != 0, 2);
+        }
+        0x256D..=0x2570 => {
+            let (sx, sy) =
+                [(1.0, -1.0), (-1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)][(cp - 0x256D) as usize];
+            arc(&mut out, cell, sx, sy);
+        }
+        0x2571..=0x2573 => {
+            if cp != 0x2572 {
+                diagonal(&mut out, cell, cell.at(0.0, 0.0), cell.at(1.0, 1.0));
+            }
+            if cp != 0x2571 {
+                diagonal(&mut out, cell, cell.at(0.0, 1.0), cell.at(1.0, 0.0));
+            }
+        }
+        0x2500..=0x257F => arms(&mut out, cell, ARMS[(cp - 0x2500) as usize].as_bytes()),
+        0x2580 => out.push(cell.block(0.0, 0.5, 1.0, 1.0)),
+        0x2581..=0x2588 => out.push(cell.block(0.0, 0.0, 1.0, (cp - 0x2580) as f32 / 8.0)),
+        0x2589..=0x258F => out.push(cell.block(0.0, 0.0, (0x2590 - cp) as f32 / 8.0, 1.0)),
+        0x2590 => out.push(cell.block(0.5, 0.0, 1.0, 1.0)),
+        0x2591 => out.push(Shape::Shade(Shade::Light)),
+        0x2592 => out.push(Shape::Shade(Shade::Medium)),
+        0x2593 => out.push(Shape::Shade(Shade::Dark)),
+        0x2594 => out.push(cell.block(0.0, 7.0 / 8.0, 1.0, 1.0)),
+        0x2595 => out.push(cell.block(7.0 / 8.0, 0.0, 1.0, 1.0)),
+        0x2596..=0x259F => {
+            // Quadrant bits: upper left, upper right, lower left, lower right.
+            let mask = [4, 8, 1, 13, 9, 7, 11, 2, 6, 14][(cp - 0x2596) as usize];
+            for (bit, fx, fy) in [(1, 0.0, 0.5), (2, 0.5, 0.5), (4, 0.0, 0.0), (8, 0.5, 0.0)] {
+                if mask & bit != 0 {
+                    out.push(cell.block(fx, fy, fx + 0.5, fy + 0.5));
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(out)
+}
+
+/// Lines from the centre to the cell edges. Each line starts far enough
+/// back to close the joint with the perpendicular arms: single lines cross
+/// them completely, the two lines of a double arm stop at the inner line
+/// of a double neighbour and run through to the far side otherwise.
+fn arms(out: &mut Vec<Shape>, cell: &Cell, weights: &[u8]) {
+    let reach = [
+        cell.max.y - cell.centre.y,
+        cell.max.x - cell.centre.x,
+        cell.centre.y - cell.min.y,
+        cell.centre.x - cell.min.x,
+    ];
+    for arm in 0..4 {
+        let weight = weights[arm];
+        if weight == b'.' {
+            continue;
+        }
+        let (ux, uy) = DIRECTIONS[arm];
+        let end = reach[arm] + OVERHANG;
+        let (left, right) = ((arm + 3) % 4, (arm + 1) % 4);
+        let line = |out: &mut Vec<Shape>, offset: f32, side: usize, start: f32, hw: f32| {
+            let (vx, vy) = DIRECTIONS[side];
+            let at = |t: f32| {
+                Point2::new(
+                    cell.centre.x + ux * t + vx * offset,
+                    cell.centre.y + uy * t + vy * offset,
+                )
+            };
+            out.push(bar(at(start), at(end), hw));
+        };
+        if weight == b'd' {
+            let offset = cell.double_offset();
+            for (side, opposite) in [(left, right), (right, left)] {
+                let start = match weights[side] {
+                    b'.' => -cell.extent(weights[opposite]),
+                    b'd' => offset - cell.light,
+                    w => -cell.half_width(w),
+                };
+                line(out, offset, side, start, cell.light);
+            }
+        } else {
+            let start = -cell.extent(weights[left]).max(cell.extent(weights[right]));
+            line(out, 0.0, left, start, cell.half_width(weight));
+        }
+    }
+}
+
+/// Axis-aligned bar of half-width `hw` from `a` to `b` (same x or same y).
+fn bar(a: Point2, b: Point2, hw: f32) -> Shape {
+    let vertical = (a.x - b.x).abs() < (a.y - b.y).abs();
+    let (dx, dy) = if vertical { (hw, 0.0) } else { (0.0, hw) };
+    Shape::Rect {
+        min: Point2::new(a.x.min(b.x) - dx, a.y.min(b.y) - dy),
+        max: Point2::new(a.x.max(b.x) + dx, a.y.max(b.y) + dy),
+    }
+}
+
+/// `count` dashes per cell, centred in equal slots so the gaps split
+/// evenly across the cell edges.
+fn dashes(out: &mut Vec<Shape>, cell: &Cell, vertical: bool, heavy: bool, count: usize) {
+    let hw = cell.half_width(if heavy { b'h' } else { b'l' });
+    let slot = 1.0 / count as f32;
+    for k in 0..count {
+        let lo = (k as f32 + 0.5 * (1.0 - DASH_FILL)) * slot;
+        let hi = lo + DASH_FILL * slot;
+        let (a, b) = if vertical {
+            (cell.at(0.5, lo), cell.at(0.5, hi))
+        } else {
+            (cell.at(lo, 0.5), cell.at(hi, 0.5))
+        };
+        out.push(bar(a, b, hw));
+    }
+}
+
+/// Rounded corner joining the arms in directions `sx` (horizontal) and
+/// `sy` (vertical), with straight runs out to the cell edges.
+fn arc(out: &mut Vec<Shape>, cell: &Cell, sx: f32, sy: f32) {
+    let c = cell.centre;
+    let radius = (cell.width() * 0.5).min(cell.height() * 0.5);
+    let hw = cell.light;
+    let edge_x = if sx > 0.0 { cell.max.x } else { cell.min.x } + sx * OVERHANG;
+    let edge_y = if sy > 0.0 { cell.max.y } else { cell.min.y } + sy * OVERHANG;
+    out.push(Shape::Arc {
+        centre: Point2::new(c.x + sx * radius, c.y + sy * radius),
+        radius,
+        hw,
+        sx,
+        sy,
+    });
+    out.push(bar(
+        Point2::new(c.x + sx * radius, c.y),
+        Point2::new(edge_x, c.y),
+        hw,
+    ));
+    out.push(bar(
+        Point2::new(c.x, c.y + sy * radius),
+        Point2::new(c.x, edge_y),
+        hw,
+    ));
+}
+
+/// Light diagonal through the corners `a` and `b`, continued past both so
+/// it joins the diagonal in the next cell.
+fn diagonal(out: &mut Vec<Shape>, cell: &Cell, a: Point2, b: Point2) {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let k = OVERHANG / fast_sqrt(dx * dx + dy * dy);
+    out.push(Shape::Segment {
+        a: Point2::new(a.x - dx * k, a.y - dy * k),
+        b: Point2::new(b.x + dx * k, b.y + dy * k),
+        hw: cell.light,
+    });
+}
+
+/// Signed distance from `p` to the union of `shapes`.
+fn distance(shapes: &[Shape], cell: &Cell, p: Point2) -> f32 {
+    shapes
+        .iter()
+        .map(|shape| match *shape {
+            Shape::Rect { min, max } => rect_distance(p, min, max),
+            Shape::Segment { a, b, hw } => {
+                fast_sqrt(point_line_dist_sq(p.x, p.y, (a.x, a.y), (b.x, b.y))) - hw
+            }
+            Shape::Arc {
+                centre,
+                radius,
+                hw,
+                sx,
+                sy,
+            } => {
+                let (qx, qy) = (p.x - centre.x, p.y - centre.y);
+                if qx * sx <= 0.0 && qy * sy <= 0.0 {
+                    (fast_sqrt(qx * qx + qy * qy) - radius).abs() - hw
+                } else {
+                    let a = Point2::new(centre.x - sx * radius, centre.y);
+                    let b = Point2::new(centre.x, centre.y - sy * radius);
+                    p.distance(a).min(p.distance(b)) - hw
+                }
+            }
+            Shape::Shade(shade) => shade_distance(shade, cell, p),
+        })
+        .fold(f32::MAX, f32::min)
+}
+
+/// Signed distance to an axis-aligned rectangle.
+fn rect_distance(p: Point2, min: Point2, max: Point2) -> f32 {
+    let qx = (min.x - p.x).max(p.x - max.x);
+    let qy = (min.y - p.y).max(p.y - max.y);
+    let outside = fast_sqrt(qx.max(0.0) * qx.max(0.0) + qy.max(0.0) * qy.max(0.0));
+    outside + qx.max(qy).min(0.0)
+}
+
+/// Signed distance to the shade lattice: the nearest inked lattice cell
+/// among the 3×3 around `p`.
+fn shade_distance(shade: Shade, cell: &Cell, p: Point2) -> f32 {
+    let pitch_x = cell.width() / SHADE_COLUMNS as f32;
+    let pitch_y = cell.height() / SHADE_ROWS as f32;
+    // Lattice cells are centred on multiples of the pitch, so the cell
+    // edges cut through dots instead of running along their borders.
+    let (gx, gy) = (
+        (p.x - cell.min.x) / pitch_x + 0.5,
+        (p.y - cell.min.y) / pitch_y + 0.5,
+    );
+    let (i0, j0) = (floor(gx) as i32, floor(gy) as i32);
+    let inked = |i: i32, j: i32| match shade {
+        Shade::Light | Shade::Dark => i.rem_euclid(2) == 0 && j.rem_euclid(2) == 0,
+        Shade::Medium => (i + j).rem_euclid(2) == 0,
+    };
+    let mut d = f32::MAX;
+    for j in j0 - 1..=j0 + 1 {
+        for i in i0 - 1..=i0 + 1 {
+            if inked(i, j) {
+                let min = Point2::new(
+                    cell.min.x + (i as f32 - 0.5) * pitch_x,
+                    cell.min.y + (j as f32 - 0.5) * pitch_y,
+                );
+                let max = Point2::new(min.x + pitch_x, min.y + pitch_y);
+                d = d.min(rect_distance(p, min, max));
+            }
+        }
+    }
+    match shade {
+        Shade::Dark => -d,
+        _ => d,
+    }
+}
+
+/// Sample `shapes` on a tile whose frame is exactly the cell.
+fn rasterize(shapes: &[Shape], cell: &Cell) -> GlyphSdf {
+    let mut sdf = GlyphSdf::from_field(cell.min, cell.max, |p| distance(shapes, cell, p));
+    sdf.advance = CELL_ADVANCE;
+    sdf.lsb = ink_metrics(shapes, cell).lsb;
+    sdf
+}
+
+/// Ink box of `shapes`, clipped to the cell.
+fn ink_metrics(shapes: &[Shape], cell: &Cell) -> GlyphMetrics {
+    let mut lo = Point2::new(f32::MAX, f32::MAX);
+    let mut hi = Point2::new(f32::MIN, f32::MIN);
+    for shape in shapes {
+        let (a, b) = match *shape {
+            Shape::Rect { min, max } => (min, max),
+            Shape::Segment { a, b, hw } => (
+                Point2::new(a.x.min(b.x) - hw, a.y.min(b.y) - hw),
+                Point2::new(a.x.max(b.x) + hw, a.y.max(b.y) + hw),
+            ),
+            Shape::Arc {
+                centre,
+                radius,
+                hw,
+                sx,
+                sy,
+            } => {
+                let far = Point2::new(centre.x - sx * radius, centre.y - sy * radius);
+                (
+                    Point2::new(centre.x.min(far.x) - hw, centre.y.min(far.y) - hw),
+                    Point2::new(centre.x.max(far.x) + hw, centre.y.max(far.y) + hw),
+                )
+            }
+            Shape::Shade(_) => (cell.min, cell.max),
+        };
+        lo = Point2::new(lo.x.min(a.x), lo.y.min(a.y));
+        hi = Point2::new(hi.x.max(b.x), hi.y.max(b.y));
+    }
+    if lo.x > hi.x {
+        return GlyphMetrics::blank(CELL_ADVANCE);
+    }
+    let clip = |p: Point2| {
+        Point2::new(
+            p.x.clamp(cell.min.x, cell.max.x),
+            p.y.clamp(cell.min.y, cell.max.y),
+        )
+    };
+    GlyphMetrics::from_ink(CELL_ADVANCE, clip(lo), clip(hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::GLYPH_SDF_SIZE;
+
+    const SIZE: usize = GLYPH_SDF_SIZE;
+
+    fn tile(ch: char) -> GlyphSdf {
+        generate(ch, &MetaFontParams::sans_regular()).unwrap()
+    }
+
+    fn column(sdf: &GlyphSdf, x: usize) -> impl Iterator<Item = f32> + '_ {
+        (0..SIZE).map(move |y| sdf.data[y * SIZE + x])
+    }
+
+    fn row(sdf: &GlyphSdf, y: usize) -> &[f32] {
+        &sdf.data[y * SIZE..(y + 1) * SIZE]
+    }
+
+    #[test]
+    fn whole_blocks_are_covered() {
+        for cp in 0x2500..=0x259F {
+            let ch = char::from_u32(cp).unwrap();
+            assert!(covers(ch));
+            let sdf = tile(ch);
+            assert!(sdf.data.iter().any(|d| *d < 0.0), "{ch}");
+            assert!((sdf.advance - CELL_ADVANCE).abs() < 1e-6);
+        }
+        assert!(!covers('\u{24FF}') && !covers('\u{25A0}'));
+        assert!(generate('A', &MetaFontParams::sans_regular()).is_none());
+    }
+
+    #[test]
+    fn tile_frame_is_the_cell() {
+        let params = MetaFontParams::sans_regular();
+        let sdf = tile('█');
+        assert_eq!(sdf.bbox_min, Point2::new(0.0, -params.descender));
+        assert_eq!(sdf.bbox_max, Point2::new(CELL_ADVANCE, params.ascender));
+        assert!(sdf.data.iter().all(|d| *d < 0.0));
+        let m = metrics('█', &params).unwrap();
+        assert!(m.lsb.abs() < 1e-6 && m.rsb.abs() < 1e-6);
+    }
+
+    #[test]
+    fn lines_tile_seamlessly() {
+        // Horizontal lines and shades: the left and right edge columns
+        // match, so the glyph joins itself and every glyph with a left arm.
+        // Dashes leave half a gap on either edge.
+        for ch in ['─', '━', '═', '┼', '╋', '╬', '┄', '╌', '░', '▒', '▓'] {
+            let sdf = tile(ch);
+            let left: Vec<f32> = column(&sdf, 0).collect();
+            let right: Vec<f32> = column(&sdf, SIZE - 1).collect();
+            for (l, r) in left.iter().zip(&right) {
+                assert!((l - r).abs() < 1e-5, "{ch}");
+            }
+            let dashed = matches!(ch, '┄' | '╌');
+            assert_eq!(left.iter().any(|d| *d < 0.0), !dashed, "{ch}");
+        }
+        for ch in ['│', '┃', '║', '┼', '╬', '┆', '░', '▒', '▓'] {
+            let sdf = tile(ch);
+            for (b, t) in row(&sdf, 0).iter().zip(row(&sdf, SIZE - 1)) {
+                assert!((b - t).abs() < 1e-5, "{ch}");
+            }
+        }
+        // ├ continues into ─: the same samples are inked on the shared edge.
+        let (tee, line) = (tile('├'), tile('─'));
+        for (a, b) in column(&tee, SIZE - 1).zip(column(&line, SIZE - 1)) {
+            assert_eq!(a < 0.0, b < 0.0);
+        }
+    }
+
+    #[test]
+    fn weights_and_double_lines() {
+        let ink = |ch| column(&tile(ch), SIZE / 4).filter(|d| *d < 0.0).count();
+        let (light, heavy, double) = (ink('─'), ink('━'), ink('═'));
+        assert!(heavy > light, "{light} {heavy}");
+        assert!(double > light);
+        // A double line has a gap between its two strokes.
+        let gaps = column(&tile('═'), SIZE / 4)
+            .collect::<Vec<_>>()
+            .windows(2)
+            .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+            .count();
+        assert_eq!(gaps, 2);
+
+        let thin = MetaFontParams {
+            weight: 0.1,
+            ..MetaFontParams::sans_regular()
+        };
+        let thin_ink = column(&generate('─', &thin).unwrap(), SIZE / 4)
+            .filter(|d| *d < 0.0)
+            .count();
+        assert!(thin_ink < light);
+    }
+
+    #[test]
+    fn joints_are_closed() {
+        // The centre of every junction is inked, and so are the outer
+        // corners of double-line corners.
+        for cp in 0x250C..=0x254B_u32 {
+            let ch = char::from_u32(cp).unwrap();
+            assert!(tile(ch).is_inside(0.5, 0.5), "{ch}");
+        }
+        let cell = Cell::new(&MetaFontParams::sans_regular());
+        let corner = shapes('╔', &cell).unwrap();
+        let outer = cell.double_offset() + cell.light * 0.5;
+        let p = Point2::new(cell.centre.x - outer, cell.centre.y + outer);
+        assert!(distance(&corner, &cell, p) < 0.0);
+        assert!(distance(&corner, &cell, cell.centre) > 0.0);
+    }
+
+    #[test]
+    fn blocks_fill_their_fraction() {
+        let coverage =
+            |ch| tile(ch).data.iter().filter(|d| **d < 0.0).count() as f32 / (SIZE * SIZE) as f32;
+        for (ch, expected) in [
+            ('▀', 0.5),
+            ('▄', 0.5),
+            ('▌', 0.5),
+            ('▐', 0.5),
+            ('▂', 0.25),
+            ('▆', 0.75),
+            ('▚', 0.5),
+            ('▙', 0.75),
+            ('░', 0.25),
+            ('▒', 0.5),
+            ('▓', 0.75),
+        ] {
+            let c = coverage(ch);
+            assert!((c - expected).abs() < 0.06, "{ch}: {c}");
+        }
+    }
+
+    #[test]
+    fn arcs_and_diagonals_reach_the_edges() {
+        // ╭ leaves through the bottom and right edges only.
+        let arc = tile('╭');
+        assert!(row(&arc, 0).iter().any(|d| *d < 0.0));
+        assert!(column(&arc, SIZE - 1).any(|d| d < 0.0));
+        assert!(row(&arc, SIZE - 1).iter().all(|d| *d > 0.0));
+        assert!(column(&arc, 0).all(|d| d > 0.0));
+        let slash = tile('╱');
+        assert!(slash.data[0] < 0.0 && slash.data[SIZE * SIZE - 1] < 0.0);
+        assert!(slash.data[SIZE - 1] > 0.0);
+    }
+}
@@ -0,0 +1,212 @@
+//! Braille Patterns — U+2800 to U+28FF
+//!
+//! The low byte of the code point is the dot pattern: bit `k` raises dot
+//! `k + 1`, with dots 1–3 and 7 down the left column and 4–6 and 8 down
+//! the right. The 2 × 4 grid is spread evenly over the same half-width
+//! cell as [`box_drawing`](crate::glyph::box_drawing), so dots keep a
+//! uniform pitch across neighbouring cells and Braille plots line up in a
+//! terminal. Dot size follows `MetaFontParams::weight`.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use crate::glyph::box_drawing::CELL_ADVANCE;
+use crate::glyph::font_render::fast_sqrt;
+use crate::glyph::{GlyphMetrics, GlyphResult, GlyphSdf};
+use crate::param::MetaFontParams;
+use crate::stroke::Point2;
+
+/// Dot radius relative to `MetaFontParams::stroke_half_width`.
+const DOT_SCALE: f32 = 1.6;
+/// Smallest dot radius (em).
+const MIN_DOT: f32 = 0.02;
+/// Largest dot radius as a fraction of the dot pitch.
+const MAX_DOT: f32 = 0.4;
+
+/// Column and row (from the top) of dots 1 to 8.
+const DOTS: [(u32, u32); 8] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (1, 0),
+    (1, 1),
+    (1, 2),
+    (0, 3),
+    (1, 3),
+];
+
+/// Is `ch` a Braille pattern?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    matches!(ch as u32, 0x2800..=0x28FF)
+}
+
+/// Dot pattern of `ch` (bit `k` = dot `k + 1`), or `None` outside the
+/// block.
+#[must_use]
+pub const fn pattern(ch: char) -> Option<u8> {
+    if covers(ch) {
+        Some((ch as u32 - 0x2800) as u8)
+    } else {
+        None
+    }
+}
+
+/// Generate the SDF for a Braille pattern. U+2800 is a blank cell.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams) -> Option<GlyphSdf> {
+    render(ch, params).map(|result| result.sdf)
+}
+
+/// Metrics of `ch` without rasterizing it.
+#[must_use]
+pub fn metrics(ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+    let dots = Dots::new(pattern(ch)?, params);
+    Some(dots.metrics())
+}
+
+/// [`generate`] with provenance and metrics.
+#[must_use]
+pub fn render(ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+    let dots = Dots::new(pattern(ch)?, params);
+    let metrics = dots.metrics();
+    let mut sdf = GlyphSdf::from_field(dots.min, dots.max, |p| dots.distance(p));
+    sdf.advance = CELL_ADVANCE;
+    sdf.lsb = metrics.lsb;
+    Some(GlyphResult::procedural(sdf, metrics))
+}
+
+/// Raised dots of one pattern in the cell.
+struct Dots {
+    pattern: u8,
+    min: Point2,
+    max: Point2,
+    radius: f32,
+}
+
+impl Dots {
+    fn new(pattern: u8, params: &MetaFontParams) -> Self {
+        let min = Point2::new(0.0, -params.descender);
+        let max = Point2::new(CELL_ADVANCE, params.ascender);
+        let pitch = ((max.x - min.x) * 0.5).min((max.y - min.y) * 0.25);
+        Self {
+            pattern,
+            min,
+            max,
+            radius: (params.stroke_half_width() * DOT_SCALE).clamp(MIN_DOT, pitch * MAX_DOT),
+        }
+    }
+
+    /// Centres of the raised dots.
+    fn centres(&self) -> impl Iterator<Item = Point2> + '_ {
+        let (w, h) = (self.max.x - self.min.x, self.max.y - self.min.y);
+        DOTS.iter()
+            .enumerate()
+            .filter(|(k, _)| self.pattern & (1 << k) != 0)
+            .map(move |(_, &(col, row))| {
+                Point2::new(
+                    self.min.x + (col as f32 + 0.5) * w * 0.5,
+                    self.max.y - (row as f32 + 0.5) * h * 0.25,
+                )
+            })
+    }
+
+    fn distance(&self, p: Point2) -> f32 {
+        self.centres()
+            .map(|c| {
+                let (dx, dy) = (p.x - c.x, p.y - c.y);
+                fast_sqrt(dx * dx + dy * dy) - self.radius
+            })
+            .fold(f32::MAX, f32::min)
+    }
+
+    fn metrics(&self) -> GlyphMetrics {
+        let r = self.radius;
+        let bounds = self
+            .centres()
+            .fold(None, |acc: Option<(Point2, Point2)>, c| {
+                let (lo, hi) = acc.unwrap_or((c, c));
+                Some((
+                    Point2::new(lo.x.min(c.x), lo.y.min(c.y)),
+                    Point2::new(hi.x.max(c.x), hi.y.max(c.y)),
+                ))
+            });
+        bounds.map_or(GlyphMetrics::blank(CELL_ADVANCE), |(lo, hi)| {
+            GlyphMetrics::from_ink(
+                CELL_ADVANCE,
+                Point2::new(lo.x - r, lo.y - r),
+                Point2::new(hi.x + r, hi.y + r),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inked(ch: char) -> usize {
+        generate(ch, &MetaFontParams::sans_regular())
+            .unwrap()
+            .data
+            .iter()
+            .filter(|d| **d < 0.0)
+            .count()
+    }
+
+    #[test]
+    fn blank_and_full_patterns() {
+        assert_eq!(pattern('⠀'), Some(0));
+        assert_eq!(pattern('⣿'), Some(0xFF));
+        assert_eq!(pattern('A'), None);
+        assert_eq!(inked('⠀'), 0);
+        let params = MetaFontParams::sans_regular();
+        assert!(!metrics('⠀', &params).unwrap().has_ink());
+        // Eight equal dots.
+        let one = inked('⠁');
+        assert!(one > 0);
+        let all = inked('⣿') as f32 / one as f32;
+        assert!((all - 8.0).abs() < 0.5, "{all}");
+    }
+
+    #[test]
+    fn dots_follow_the_bit_pattern() {
+        let params = MetaFontParams::sans_regular();
+        // Dot 1 is top left, dot 8 bottom right, dot 7 bottom left.
+        let m1 = metrics('⠁', &params).unwrap();
+        let m8 = metrics('⢀', &params).unwrap();
+        let m7 = metrics('⡀', &params).unwrap();
+        assert!(m1.ink_min.x < m8.ink_min.x && m1.ink_min.y > m8.ink_min.y);
+        assert!((m7.ink_min.x - m1.ink_min.x).abs() < 1e-6);
+        assert!((m7.ink_min.y - m8.ink_min.y).abs() < 1e-6);
+        // ⠃ = dots 1 and 2, stacked in the left column.
+        let m = metrics('⠃', &params).unwrap();
+        assert!((m.ink_width() - (m1.ink_width())).abs() < 1e-6);
+        assert!(m.ink_max.y - m.ink_min.y > m1.ink_max.y - m1.ink_min.y);
+    }
+
+    #[test]
+    fn dots_keep_their_pitch_across_cells() {
+        // The gap between columns inside a cell equals the gap between the
+        // right column and the next cell's left column.
+        let params = MetaFontParams::sans_regular();
+        let left = metrics('⠁', &params).unwrap();
+        let right = metrics('⠈', &params).unwrap();
+        let inside = right.ink_min.x - left.ink_min.x;
+        let across = left.ink_min.x + CELL_ADVANCE - right.ink_min.x;
+        assert!((inside - across).abs() < 1e-6);
+        let heavy = MetaFontParams {
+            weight: 0.9,
+            ..params
+        };
+        assert!(
+            inked('⠁')
+                < generate('⠁', &heavy)
+                    .unwrap()
+                    .data
+                    .iter()
+                    .filter(|d| **d < 0.0)
+                    .count()
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! CJK Symbols and Punctuation — U+3000 to U+303F
+//!
+//! Ideographic space, 、。, the bracket pairs used in Japanese and Chinese
+//! text (「」『』〈〉《》【】〔〕), 々 〆 〇, the wave dash and the double
+//! prime quotes. Skeletons live in the same unit box as the kana, so
+//! punctuation lines up with the surrounding text: 、 and 。 sit in the
+//! lower-left quadrant, opening brackets hug the right half of the cell and
+//! closing brackets the left half.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use crate::glyph::helpers::add_ellipse;
+use crate::glyph::latin::{curve, seg};
+use crate::glyph::{GlyphGenerator, GlyphSdf, GlyphSkeleton};
+use crate::param::MetaFontParams;
+
+/// Every glyph in the block is full-width.
+const SYMBOL_ADVANCE: f32 = 1.0;
+
+/// Generate the SDF for a CJK symbol. Characters without a skeleton return
+/// an empty SDF with the full-width advance.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
+    skeleton(ch).map_or_else(
+        || {
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = SYMBOL_ADVANCE;
+            sdf
+        },
+        |skel| GlyphGenerator::new(params).generate_from_skeleton(&skel),
+    )
+}
+
+/// Does a skeleton exist for this CJK symbol?
+#[must_use]
+pub fn covers(ch: char) -> bool {
+    skeleton(ch).is_some()
+}
+
+/// Skeleton of `ch` in the builder shape shared by every skeleton module.
+/// The symbols do not depend on the pen, so `gen` is unused.
+pub(crate) fn build_skeleton(_gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    skeleton(ch)
+}
+
+fn skeleton(ch: char) -> Option<GlyphSkeleton> {
+    let mut s = GlyphSkeleton::empty();
+    s.advance = SYMBOL_ADVANCE;
+    match ch {
+        // Ideographic space: blank, full-width advance.
+        '\u{3000}' => {}
+        '、' => s.add_stroke(curve([
+            (0.14, 0.24),
+            (0.2, 0.18),
+            (0.25, 0.12),
+            (0.3, 0.05),
+        ])),
+        '。' => add_ellipse(&mut s, 0.22, 0.15, 0.09, 0.09, 0.0),
+        '〃' => {
+            s.add_stroke(seg(0.38, 0.85, 0.3, 0.65));
+            s.add_stroke(seg(0.58, 0.85, 0.5, 0.65));
+        }
+        '々' => {
+            s.add_stroke(seg(0.38, 0.92, 0.14, 0.56));
+            s.add_stroke(seg(0.28, 0.74, 0.82, 0.74));
+            s.add_stroke(curve([
+                (0.82, 0.74),
+                (0.74, 0.45),
+                (0.6, 0.24),
+                (0.36, 0.08),
+            ]));
+            s.add_stroke(seg(0.42, 0.5, 0.6, 0.38));
+        }
+        '〆' => {
+            s.add_stroke(curve([
+                (0.2, 0.82),
+                (0.55, 0.85),
+                (0.5, 0.45),
+                (0.86, 0.12),
+            ]));
+            s.add_stroke(curve([(0.82, 0.86), (0.6, 0.55), (0.4, 0.3), (0.14, 0.1)]));
+        }
+        '〇' => add_ellipse(&mut s, 0.5, 0.5, 0.38, 0.4, 0.0),
+        '〈' => angle(&mut s, 0.68, 0.38),
+        '〉' => angle(&mut s, 0.32, 0.62),
+        '《' => {
+            angle(&mut s, 0.62, 0.34);
+            angle(&mut s, 0.8, 0.52);
+        }
+        '》' => {
+            angle(&mut s, 0.38, 0.66);
+            angle(&mut s, 0.2, 0.48);
+        }
+        '「' => {
+            s.add_stroke(seg(0.86, 0.94, 0.56, 0.94));
+            s.add_stroke(seg(0.56, 0.94, 0.56, 0.3));
+        }
+        '」' => {
+            s.add_stroke(seg(0.44, 0.7, 0.44, 0.06));
+            s.add_stroke(seg(0.44, 0.06, 0.14, 0.06));
+        }
+        '『' => {
+            polyline(&mut s, &[(0.88, 0.96), (0.48, 0.96), (0.48, 0.26)]);
+            polyline(&mut s, &[(0.88, 0.84), (0.6, 0.84), (0.6, 0.26)]);
+            s.add_stroke(seg(0.88, 0.96, 0.88, 0.84));
+            s.add_stroke(seg(0.48, 0.26, 0.6, 0.26));
+        }
+        '』' => {
+            polyline(&mut s, &[(0.12, 0.04), (0.52, 0.04), (0.52, 0.74)]);
+            polyline(&mut s, &[(0.12, 0.16), (0.4, 0.16), (0.4, 0.74)]);
+            s.add_stroke(seg(0.12, 0.04, 0.12, 0.16));
+            s.add_stroke(seg(0.52, 0.74, 0.4, 0.74));
+        }
+        '【' => lenticular(&mut s, 0.62, 0.86),
+        '】' => lenticular(&mut s, 0.38, 0.14),
+        '〒' => {
+            s.add_stroke(seg(0.18, 0.86, 0.82, 0.86));
+            s.add_stroke(seg(0.18, 0.64, 0.82, 0.64));
+            s.add_stroke(seg(0.5, 0.64, 0.5, 0.08));
+        }
+        '〓' => {
+            for y in [0.66, 0.6, 0.4, 0.34] {
+                s.add_stroke(seg(0.14, y, 0.86, y));
+            }
+        }
+        '〔' => polyline(
+            &mut s,
+            &[(0.78, 0.96), (0.58, 0.82), (0.58, 0.18), (0.78, 0.04)],
+        ),
+        '〕' => polyline(
+            &mut s,
+            &[(0.22, 0.96), (0.42, 0.82), (0.42, 0.18), (0.22, 0.04)],
+        ),
+        '〜' => s.add_stroke(curve([
+            (0.08, 0.42),
+            (0.36, 0.72),
+            (0.64, 0.28),
+            (0.92, 0.58),
+        ])),
+        '〝' => {
+            s.add_stroke(seg(0.2, 0.92, 0.28, 0.72));
+            s.add_stroke(seg(0.34, 0.92, 0.42, 0.72));
+        }
+        '〞' => {
+            s.add_stroke(seg(0.6, 0.92, 0.68, 0.72));
+            s.add_stroke(seg(0.74, 0.92, 0.82, 0.72));
+        }
+        '〟' => {
+            s.add_stroke(seg(0.6, 0.28, 0.68, 0.08));
+            s.add_stroke(seg(0.74, 0.28, 0.82, 0.08));
+        }
+        '〰' => {
+            s.add_stroke(curve([(0.08, 0.5), (0.2, 0.68), (0.36, 0.68), (0.5, 0.5)]));
+            s.add_stroke(curve([(0.5, 0.5), (0.64, 0.32), (0.8, 0.32), (0.92, 0.5)]));
+        }
+        _ => return None,
+    }
+    Some(s)
+}
+
+/// Angle bracket with its tip at `tip_x` and its arms ending at `arm_x`.
+fn angle(s: &mut GlyphSkeleton, arm_x: f32, tip_x: f32) {
+    polyline(s, &[(arm_x, 0.94), (tip_x, 0.5), (arm_x, 0.06)]);
+}
+
+/// Black lenticular bracket: a heavy stem at `stem_x` with tapered arms
+/// reaching out to `arm_x`.
+fn lenticular(s: &mut GlyphSkeleton, stem_x: f32, arm_x: f32) {
+    let d = (arm_x - stem_x).signum() * 0.06;
+    for x in [stem_x, stem_x + d] {
+        s.add_stroke(seg(x, 0.92, x, 0.08));
+    }
+    s.add_stroke(curve([
+        (arm_x, 0.96),
+        (stem_x + d, 0.94),
+        (stem_x, 0.9),
+        (stem_x, 0.8),
+    ]));
+    s.add_stroke(curve([
+        (arm_x, 0.04),
+        (stem_x + d, 0.06),
+        (stem_x, 0.1),
+        (stem_x, 0.2),
+    ]));
+}
+
+/// Consecutive straight strokes through `points`.
+fn polyline(s: &mut GlyphSkeleton, points: &[(f32, f32)]) {
+    for w in points.windows(2) {
+        s.add_stroke(seg(w[0].0, w[0].1, w[1].0, w[1].1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::latin::extent;
+
+    const IMPLEMENTED: &[char] = &[
+        '、', '。', '〃', '々', '〆', '〇', '〈', '〉', '《', '》', '「', '」', '『', '』', '【',
+        '】', '〒', '〓', '〔', '〕', '〜', '〝', '〞', '〟', '〰',
+    ];
+
+    #[test]
+    fn implemented_glyphs_have_inside_pixels() {
+        let params = MetaFontParams::sans_regular();
+        for &ch in IMPLEMENTED {
+            let sdf = generate(ch, &params);
+            assert!((sdf.advance - SYMBOL_ADVANCE).abs() < 1e-6, "{ch}");
+            assert!(sdf.data.iter().any(|d| *d < 0.0), "{ch}");
+        }
+    }
+
+    #[test]
+    fn ideographic_space_is_blank_full_width() {
+        let skel = skeleton('\u{3000}').unwrap();
+        assert_eq!(skel.stroke_count, 0);
+        assert!((skel.advance - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn punctuation_sits_in_the_lower_left() {
+        for ch in ['、', '。'] {
+            let (_, hi) = extent(&skeleton(ch).unwrap());
+            assert!(hi.x < 0.5 && hi.y < 0.5, "{ch}");
+        }
+    }
+
+    #[test]
+    fn brackets_face_their_content() {
+        let centre = |ch| {
+            let (lo, hi) = extent(&skeleton(ch).unwrap());
+            (lo.x + hi.x) * 0.5
+        };
+        for (open, close) in [('「', '」'), ('〈', '〉'), ('【', '】'), ('〔', '〕')] {
+            assert!(centre(open) > 0.5, "{open}");
+            assert!(centre(close) < 0.5, "{close}");
+        }
+        assert!(!covers('〿'));
+    }
+}
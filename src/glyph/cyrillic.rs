@@ -0,0 +1,457 @@
+//! Cyrillic glyphs (U+0400 to U+045F, Ґ ґ)
+//!
+//! Covers Russian, Ukrainian, Belarusian, Bulgarian, Serbian and
+//! Macedonian. Letters that look like Latin or Greek ones (А В Е К М Н О Р
+//! С Т Х Ѕ І Ј, Г П Ф, а е о р с у х ...) reuse those skeletons. Most
+//! lowercase letters are small capitals of their uppercase forms, which is
+//! how upright Cyrillic is drawn; б is the main exception.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use super::helpers::{add_ellipse, add_half_arc};
+use super::latin::{append_mapped, curve, dotless, extent, seg};
+use super::{GlyphGenerator, GlyphSkeleton};
+use crate::composite::DiacriticType;
+use crate::stroke::Point2;
+
+/// Horizontal scale of small-capital lowercase relative to the capital
+/// (the vertical scale is `x_height / cap_height`).
+const SMALL_WIDTH: f32 = 0.85;
+/// Depth of the tails of Д Ц Щ Џ, as a fraction of the descender.
+const TAIL: f32 = 0.6;
+
+/// Does the Cyrillic generator draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    matches!(ch, '\u{0400}'..='\u{045F}' | '\u{0490}' | '\u{0491}')
+}
+
+/// Skeleton of a Cyrillic character, or `None` when [`covers`] is false.
+pub(crate) fn build_skeleton(gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    if !covers(ch) {
+        return None;
+    }
+    Some(gen.upright(|up| up.build_cyrillic_upright(ch)))
+}
+
+impl GlyphGenerator {
+    fn build_cyrillic_upright(&self, ch: char) -> GlyphSkeleton {
+        use DiacriticType::{Acute, Breve, Diaeresis, Grave};
+        let marked = |base: char, mark| {
+            let capital = base.is_uppercase();
+            let skel = if capital {
+                self.cyrillic_upper(base)
+            } else {
+                self.cyrillic_lower(base)
+            };
+            self.with_mark(skel, mark, capital)
+        };
+        match ch {
+            'Ѐ' => marked('Е', Grave),
+            'Ё' => marked('Е', Diaeresis),
+            'Ѓ' => marked('Г', Acute),
+            'Ї' => marked('І', Diaeresis),
+            'Й' => marked('И', Breve),
+            'Ќ' => marked('К', Acute),
+            'Ѝ' => marked('И', Grave),
+            'Ў' => marked('У', Breve),
+            'ѐ' => marked('е', Grave),
+            'ё' => marked('е', Diaeresis),
+            'ѓ' => marked('г', Acute),
+            'ї' => self.with_mark(dotless(self.ascii('i')), Diaeresis, false),
+            'й' => marked('и', Breve),
+            'ќ' => marked('к', Acute),
+            'ѝ' => marked('и', Grave),
+            'ў' => marked('у', Breve),
+            '\u{0400}'..='\u{042F}' | 'Ґ' => self.cyrillic_upper(ch),
+            _ => self.cyrillic_lower(ch),
+        }
+    }
+
+    /// Capital letters (U+0400 to U+042F, Ґ).
+    fn cyrillic_upper(&self, ch: char) -> GlyphSkeleton {
+        let cap = self.cap_height;
+        let desc = cap * 0.25;
+        let mut skel = GlyphSkeleton::empty();
+        match ch {
+            'А' => return self.ascii('A'),
+            'В' => return self.ascii('B'),
+            'Е' => return self.ascii('E'),
+            'К' => return self.ascii('K'),
+            'М' => return self.ascii('M'),
+            'Н' => return self.ascii('H'),
+            'О' => return self.ascii('O'),
+            'Р' => return self.ascii('P'),
+            'С' => return self.ascii('C'),
+            'Т' => return self.ascii('T'),
+            'Х' => return self.ascii('X'),
+            'Ѕ' => return self.ascii('S'),
+            'І' => return self.ascii('I'),
+            'Ј' => return self.ascii('J'),
+            'З' => return self.ascii('3'),
+            'Г' => return self.greek_upper('Γ'),
+            'П' => return self.greek_upper('Π'),
+            'Ф' => return self.greek_upper('Φ'),
+            'Я' => return mirrored(&self.ascii('R')),
+            'Э' | 'Є' => {
+                let c = self.ascii('C');
+                let (lo, hi) = extent(&c);
+                let mut skel = if ch == 'Э' { mirrored(&c) } else { c };
+                let (x0, x1) = if ch == 'Э' {
+                    (lo.x + (hi.x - lo.x) * 0.35, hi.x)
+                } else {
+                    (lo.x, lo.x + (hi.x - lo.x) * 0.65)
+                };
+                skel.add_stroke(seg(x0, cap * 0.5, x1, cap * 0.5));
+                return skel;
+            }
+            'Ґ' => {
+                let w = cap * 0.48;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(0.05, cap, w, cap));
+                skel.add_stroke(seg(w, cap, w, cap * 1.18));
+            }
+            'Б' => {
+                let w = cap * 0.55;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(0.05, cap, w, cap));
+                add_bowl(&mut skel, 0.05, w * 0.45, cap * 0.56);
+            }
+            'Д' => {
+                let w = cap * 0.7;
+                skel.advance = w + 0.1;
+                skel.add_stroke(curve([
+                    (w * 0.25, cap),
+                    (w * 0.25, cap * 0.4),
+                    (w * 0.2, 0.1),
+                    (0.08, 0.0),
+                ]));
+                skel.add_stroke(seg(w * 0.25, cap, w - 0.1, cap));
+                skel.add_stroke(seg(w - 0.1, cap, w - 0.1, 0.0));
+                skel.add_stroke(seg(0.02, 0.0, w, 0.0));
+                skel.add_stroke(seg(0.02, 0.0, 0.02, -desc * TAIL));
+                skel.add_stroke(seg(w, 0.0, w, -desc * TAIL));
+            }
+            'Ж' => {
+                let w = cap * 0.9;
+                let cx = w * 0.5;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(cx, 0.0, cx, cap));
+                for x in [0.05, w - 0.05] {
+                    skel.add_stroke(seg(cx, cap * 0.5, x, cap));
+                    skel.add_stroke(seg(cx, cap * 0.5, x, 0.0));
+                }
+            }
+            'И' => {
+                let w = cap * 0.62;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(w - 0.05, 0.0, w - 0.05, cap));
+                skel.add_stroke(seg(0.05, 0.0, w - 0.05, cap));
+            }
+            'Л' | 'Љ' => {
+                let w = cap * 0.62;
+                let x = w - 0.05;
+                skel.add_stroke(seg(x, 0.0, x, cap));
+                skel.add_stroke(seg(w * 0.3, cap, x, cap));
+                skel.add_stroke(curve([
+                    (w * 0.3, cap),
+                    (w * 0.3, cap * 0.3),
+                    (w * 0.2, 0.0),
+                    (0.02, 0.0),
+                ]));
+                skel.advance = w + 0.1;
+                if ch == 'Љ' {
+                    let b = cap * 0.3;
+                    add_bowl(&mut skel, x, b, cap * 0.56);
+                    skel.advance = x + b + 0.15;
+                }
+            }
+            'Њ' => {
+                let (x, b) = (cap * 0.45, cap * 0.3);
+                skel.advance = x + b + 0.15;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(x, 0.0, x, cap));
+                skel.add_stroke(seg(0.05, cap * 0.56, x, cap * 0.56));
+                add_bowl(&mut skel, x, b, cap * 0.56);
+            }
+            'Ц' | 'Щ' | 'Ш' | 'Џ' => {
+                let w = cap * if ch == 'Ц' || ch == 'Џ' { 0.62 } else { 0.85 };
+                let tail = ch == 'Ц' || ch == 'Щ';
+                let right = if tail { w - 0.1 } else { w - 0.05 };
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(right, 0.0, right, cap));
+                if ch == 'Ш' || ch == 'Щ' {
+                    let mid = (0.05 + right) * 0.5;
+                    skel.add_stroke(seg(mid, 0.0, mid, cap));
+                }
+                if tail {
+                    skel.add_stroke(seg(0.05, 0.0, w, 0.0));
+                    skel.add_stroke(seg(w, 0.0, w, -desc * TAIL));
+                } else {
+                    skel.add_stroke(seg(0.05, 0.0, right, 0.0));
+                }
+                if ch == 'Џ' {
+                    let mid = (0.05 + right) * 0.5;
+                    skel.add_stroke(seg(mid, 0.0, mid, -desc * TAIL));
+                }
+            }
+            'Ч' => {
+                let w = cap * 0.58;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(w - 0.05, 0.0, w - 0.05, cap));
+                skel.add_stroke(curve([
+                    (0.05, cap),
+                    (0.05, cap * 0.4),
+                    (w * 0.3, cap * 0.4),
+                    (w - 0.05, cap * 0.45),
+                ]));
+            }
+            'Ъ' | 'Ь' | 'Ы' => {
+                let x = if ch == 'Ъ' { cap * 0.2 } else { 0.05 };
+                let b = cap * 0.35;
+                skel.advance = x + b + 0.1;
+                skel.add_stroke(seg(x, 0.0, x, cap));
+                if ch == 'Ъ' {
+                    skel.add_stroke(seg(0.02, cap, x, cap));
+                }
+                add_bowl(&mut skel, x, b, cap * 0.56);
+                if ch == 'Ы' {
+                    let r = x + b + 0.15;
+                    skel.add_stroke(seg(r, 0.0, r, cap));
+                    skel.advance = r + 0.1;
+                }
+            }
+            'Ю' => {
+                let r = cap * 0.3;
+                let cx = 0.2 + r;
+                skel.advance = cx + r + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(0.05, cap * 0.5, 0.2, cap * 0.5));
+                add_ellipse(&mut skel, cx, cap * 0.5, r, cap * 0.5, 0.0);
+            }
+            'У' => {
+                let w = cap * 0.62;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, cap, w * 0.52, cap * 0.32));
+                skel.add_stroke(seg(w - 0.05, cap, w * 0.35, 0.05));
+                skel.add_stroke(curve([
+                    (w * 0.35, 0.05),
+                    (w * 0.28, -0.02),
+                    (w * 0.15, 0.0),
+                    (0.08, 0.02),
+                ]));
+            }
+            'Ђ' | 'Ћ' => {
+                let w = cap * 0.7;
+                let x = w * 0.28;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.02, cap, w * 0.55, cap));
+                skel.add_stroke(seg(x, 0.0, x, cap));
+                skel.add_stroke(curve([
+                    (x, cap * 0.55),
+                    (w * 0.6, cap * 0.7),
+                    (w, cap * 0.6),
+                    (w, cap * 0.3),
+                ]));
+                if ch == 'Ђ' {
+                    skel.add_stroke(curve([
+                        (w, cap * 0.3),
+                        (w, 0.0),
+                        (w * 0.8, -0.02),
+                        (w * 0.6, 0.0),
+                    ]));
+                } else {
+                    skel.add_stroke(seg(w, cap * 0.3, w, 0.0));
+                }
+            }
+            _ => return self.build_placeholder(b'?'),
+        }
+        skel
+    }
+
+    /// Small letters (U+0430 to U+045F, ґ).
+    fn cyrillic_lower(&self, ch: char) -> GlyphSkeleton {
+        let xh = self.x_height;
+        let asc = self.cap_height * 1.05;
+        let desc = self.cap_height * 0.25;
+        match ch {
+            'а' => self.ascii('a'),
+            'е' => self.ascii('e'),
+            'о' => self.ascii('o'),
+            'р' => self.ascii('p'),
+            'с' => self.ascii('c'),
+            'у' => self.ascii('y'),
+            'х' => self.ascii('x'),
+            'ѕ' => self.ascii('s'),
+            'і' => self.ascii('i'),
+            'ј' => self.ascii('j'),
+            'б' => {
+                let w = xh * 0.8;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.08;
+                add_ellipse(&mut skel, w * 0.5, xh * 0.5, w * 0.5 - 0.05, xh * 0.5, 0.0);
+                skel.add_stroke(curve([
+                    (0.05, xh * 0.5),
+                    (0.05, asc * 0.85),
+                    (w * 0.3, asc * 0.85),
+                    (w - 0.05, asc),
+                ]));
+                skel
+            }
+            'ђ' | 'ћ' => {
+                let w = xh * 0.8;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.08;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, asc));
+                skel.add_stroke(seg(0.0, asc * 0.82, 0.2, asc * 0.82));
+                skel.add_stroke(curve([
+                    (0.05, xh * 0.7),
+                    (0.1, xh),
+                    (w - 0.05, xh * 1.05),
+                    (w - 0.05, xh * 0.6),
+                ]));
+                if ch == 'ђ' {
+                    skel.add_stroke(curve([
+                        (w - 0.05, xh * 0.6),
+                        (w - 0.05, -desc * 0.8),
+                        (w * 0.7, -desc),
+                        (w * 0.5, -desc),
+                    ]));
+                } else {
+                    skel.add_stroke(seg(w - 0.05, xh * 0.6, w - 0.05, 0.0));
+                }
+                skel
+            }
+            // Everything else is a small capital.
+            _ => {
+                let upper = match ch {
+                    'ґ' => 'Ґ',
+                    '\u{0430}'..='\u{044F}' => char::from_u32(ch as u32 - 0x20).unwrap_or(ch),
+                    _ => char::from_u32(ch as u32 - 0x50).unwrap_or(ch),
+                };
+                self.small_capital(&self.cyrillic_upper(upper))
+            }
+        }
+    }
+
+    /// `upper` shrunk to the x-height.
+    fn small_capital(&self, upper: &GlyphSkeleton) -> GlyphSkeleton {
+        let sy = self.x_height / self.cap_height;
+        let sx = SMALL_WIDTH;
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = upper.advance * sx;
+        append_mapped(&mut skel, upper, |p| Point2::new(p.x * sx, p.y * sy));
+        skel
+    }
+}
+
+/// Bowl of Ь Ъ Ы Б Љ Њ: from the stem at `x`, `width` wide, closed at
+/// `top` and the baseline.
+fn add_bowl(skel: &mut GlyphSkeleton, x: f32, width: f32, top: f32) {
+    skel.add_stroke(seg(x, top, x + width * 0.5, top));
+    skel.add_stroke(seg(x, 0.0, x + width * 0.5, 0.0));
+    add_half_arc(
+        skel,
+        x + width * 0.5,
+        top * 0.5,
+        width * 0.5,
+        top * 0.5,
+        0,
+        0.0,
+    );
+}
+
+/// `src` mirrored left to right within its own extent (Я, Э).
+fn mirrored(src: &GlyphSkeleton) -> GlyphSkeleton {
+    let (lo, hi) = extent(src);
+    let mut skel = GlyphSkeleton::empty();
+    skel.advance = src.advance;
+    append_mapped(&mut skel, src, |p| Point2::new(lo.x + hi.x - p.x, p.y));
+    skel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::greek;
+    use crate::param::MetaFontParams;
+
+    fn gen() -> GlyphGenerator {
+        GlyphGenerator::new(&MetaFontParams::sans_regular())
+    }
+
+    #[test]
+    fn covers_russian_and_ukrainian() {
+        let gen = gen();
+        for cp in (0x0400..=0x045F).chain([0x0490, 0x0491]) {
+            let ch = char::from_u32(cp).unwrap();
+            let skel = build_skeleton(&gen, ch).unwrap();
+            assert_eq!(skel.placeholders, 0, "{ch} is a placeholder");
+            assert_eq!(skel.dropped, 0, "{ch} dropped strokes");
+            assert!(skel.stroke_count > 0, "{ch} has no strokes");
+        }
+        assert!(build_skeleton(&gen, 'Ѡ').is_none());
+        assert!(build_skeleton(&gen, 'A').is_none());
+    }
+
+    #[test]
+    fn lowercase_fits_x_height() {
+        let gen = gen();
+        for ch in "вгжзиклмнптчшъыьэюяє".chars() {
+            let (lo, hi) = extent(&build_skeleton(&gen, ch).unwrap());
+            assert!(hi.y <= gen.x_height + 0.01, "{ch} is too tall");
+            assert!(lo.y > -0.01, "{ch} descends");
+        }
+        for ch in ['д', 'ц', 'щ', 'џ'] {
+            let (lo, _) = extent(&build_skeleton(&gen, ch).unwrap());
+            assert!(lo.y < -0.02, "{ch} has no tail");
+        }
+        let (_, hi) = extent(&build_skeleton(&gen, 'б').unwrap());
+        assert!(hi.y > gen.x_height + 0.1);
+    }
+
+    #[test]
+    fn homoglyphs_reuse_latin_and_greek() {
+        let gen = gen();
+        for (cyr, latin) in [('А', 'A'), ('Н', 'H'), ('р', 'p'), ('у', 'y'), ('і', 'i')] {
+            let c = build_skeleton(&gen, cyr).unwrap();
+            let l = gen.build_skeleton(latin as u8);
+            assert_eq!(c.stroke_count, l.stroke_count, "{cyr}");
+            assert_eq!(c.strokes[0].p0, l.strokes[0].p0, "{cyr}");
+        }
+        let pe = build_skeleton(&gen, 'П').unwrap();
+        let pi = greek::build_skeleton(&gen, 'Π').unwrap();
+        assert_eq!(pe.stroke_count, pi.stroke_count);
+        for (a, b) in pe.strokes.iter().zip(&pi.strokes).take(pe.stroke_count) {
+            assert_eq!((a.p0, a.p3), (b.p0, b.p3));
+        }
+    }
+
+    #[test]
+    fn marks_and_mirrors() {
+        let gen = gen();
+        let i = build_skeleton(&gen, 'И').unwrap();
+        let short_i = build_skeleton(&gen, 'Й').unwrap();
+        assert_eq!(short_i.stroke_count, i.stroke_count + 1);
+        let (_, hi) = extent(&short_i);
+        assert!(hi.y > gen.cap_height + 0.05);
+        // ї keeps two dots, not the dot of і.
+        let yi = build_skeleton(&gen, 'ї').unwrap();
+        assert_eq!(
+            yi.stroke_count,
+            build_skeleton(&gen, 'і').unwrap().stroke_count + 1
+        );
+        // Я is R facing the other way: its leg ends on the left.
+        let r = gen.build_skeleton(b'R');
+        let ya = build_skeleton(&gen, 'Я').unwrap();
+        let (lo, hi) = extent(&r);
+        let last = ya.strokes[ya.stroke_count - 1].p3;
+        let r_last = r.strokes[r.stroke_count - 1].p3;
+        assert!((last.x - (lo.x + hi.x - r_last.x)).abs() < 1e-6);
+    }
+}
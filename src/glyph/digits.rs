@@ -1,15 +1,241 @@
-//! Digit glyphs 0-9 with tabular (fixed-width) advance
+//! Digit glyphs 0-9 with tabular (fixed-width) advance, and the figure
+//! styles applied on top of any font's digits
+//!
+//! A [`FigureStyle`] re-spaces and re-sets finished digit glyphs —
+//! embedded outlines, runtime fonts and skeletons alike — so the tile and
+//! its metrics move together:
+//!
+//! - [`FigureStyle::TabularLining`] centres 0–9 and the related symbols
+//!   (`+ - = $ ¢ £ ¥ ± × ÷`) in one shared cell, so counters and timers do
+//!   not jitter as their digits change.
+//! - [`FigureStyle::ProportionalLining`] fits each digit to its ink, with
+//!   the side bearings of `0`.
+//! - [`FigureStyle::Oldstyle`] sets 0 1 2 at x-height, drops 3 4 5 7 9
+//!   below the baseline and keeps 6 8 at full height, proportionally
+//!   spaced. The x-height figures are scaled tiles, thickened back by the
+//!   same optical rule as [`variants`](super::variants).
+//!
+//! The cell and side bearings come from the font's own digits, measured
+//! once into [`FigureMetrics`]. Atlases set figures with
+//! [`SdfAtlas::set_figure_style`](crate::atlas::SdfAtlas::set_figure_style)
+//! and keep the measurement until their parameters, policy or providers
+//! change.
 //!
 //! License: MIT
 //! Author: Moroya Sakamoto
 
 use super::helpers::add_ellipse;
-use super::{GlyphGenerator, GlyphSkeleton};
+use super::variants::OPTICAL_THINNING;
+use super::{
+    GlyphGenerator, GlyphMetrics, GlyphProvenance, GlyphResult, GlyphSkeleton, MIN_SIDE_BEARING,
+};
+use crate::param::MetaFontParams;
 use crate::stroke::{Point2, Stroke};
 
 /// Tabular advance width for all digits (ensures numeric columns align)
 const DIGIT_ADVANCE: f32 = 0.55;
 
+/// Symbols set on the tabular figure cell along with 0–9.
+const TABULAR_SYMBOLS: [char; 10] = ['+', '-', '=', '$', '¢', '£', '¥', '±', '×', '÷'];
+
+/// How digits are shaped and spaced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FigureStyle {
+    /// Full-height figures on one shared advance (`tnum`)
+    #[default]
+    TabularLining,
+    /// Full-height figures fitted to their ink (`pnum`)
+    ProportionalLining,
+    /// Figures with x-height, ascending and descending forms,
+    /// proportionally spaced (`onum`)
+    Oldstyle,
+}
+
+/// Is `ch` one of the digits 0–9?
+#[must_use]
+pub const fn is_figure(ch: char) -> bool {
+    ch.is_ascii_digit()
+}
+
+/// Does `style` change the glyph of `ch`?
+#[must_use]
+pub const fn is_styled(ch: char, style: FigureStyle) -> bool {
+    match style {
+        FigureStyle::TabularLining => is_figure(ch) || is_tabular_symbol(ch),
+        FigureStyle::ProportionalLining | FigureStyle::Oldstyle => is_figure(ch),
+    }
+}
+
+const fn is_tabular_symbol(ch: char) -> bool {
+    let mut i = 0;
+    while i < TABULAR_SYMBOLS.len() {
+        if TABULAR_SYMBOLS[i] == ch {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Widths the figure styles are set on, measured once per font.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FigureMetrics {
+    /// Tabular cell: the widest digit advance, widened so every related
+    /// symbol fits with [`MIN_SIDE_BEARING`] on each side
+    pub cell: f32,
+    /// Side bearing of proportional figures: the mean of `0`'s
+    pub bearing: f32,
+}
+
+impl FigureMetrics {
+    /// Measure from `lookup`, the unstyled metrics of the font's
+    /// characters.
+    #[must_use]
+    pub fn measure(lookup: impl Fn(char) -> Option<GlyphMetrics>) -> Self {
+        let digits = ('0'..='9').filter_map(&lookup).map(|m| m.advance);
+        let symbols = TABULAR_SYMBOLS
+            .iter()
+            .filter_map(|&c| lookup(c))
+            .map(|m| m.ink_width() + 2.0 * MIN_SIDE_BEARING);
+        let bearing = lookup('0')
+            .filter(GlyphMetrics::has_ink)
+            .map_or(MIN_SIDE_BEARING, |m| {
+                ((m.lsb + m.rsb) * 0.5).max(MIN_SIDE_BEARING)
+            });
+        Self {
+            cell: digits.chain(symbols).fold(0.0, f32::max),
+            bearing,
+        }
+    }
+}
+
+/// Where a styled figure goes: scaled about the pen origin by `scale`,
+/// thickened by `embolden` (em), then moved by `offset` on `advance`.
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    scale: f32,
+    embolden: f32,
+    offset: Point2,
+    advance: f32,
+}
+
+impl Placement {
+    /// Placement of `ch` (drawn with metrics `m`) in `style`, on the
+    /// widths `figures` of its font.
+    fn new(
+        style: FigureStyle,
+        ch: char,
+        m: &GlyphMetrics,
+        params: &MetaFontParams,
+        figures: &FigureMetrics,
+    ) -> Self {
+        let bearing = figures.bearing;
+        match style {
+            FigureStyle::TabularLining => {
+                let cell = figures.cell.max(m.ink_width() + 2.0 * MIN_SIDE_BEARING);
+                let centre = (m.ink_min.x + m.ink_max.x) * 0.5;
+                Self::moved(cell * 0.5 - centre, 0.0, cell)
+            }
+            FigureStyle::ProportionalLining => fitted(m, 1.0, 0.0, bearing),
+            FigureStyle::Oldstyle => {
+                let s = params.x_height / params.cap_height;
+                let embolden = params.stroke_half_width() * (1.0 - s) * (1.0 - OPTICAL_THINNING);
+                match ch {
+                    '0' | '1' | '2' => Self {
+                        embolden,
+                        ..fitted(&scaled(m, s, embolden), s, 0.0, bearing)
+                    },
+                    '6' | '8' => fitted(m, 1.0, 0.0, bearing),
+                    _ => fitted(m, 1.0, -m.ink_max.y * (1.0 - s), bearing),
+                }
+            }
+        }
+    }
+
+    const fn moved(dx: f32, dy: f32, advance: f32) -> Self {
+        Self {
+            scale: 1.0,
+            embolden: 0.0,
+            offset: Point2::new(dx, dy),
+            advance,
+        }
+    }
+
+    fn map(&self, p: Point2) -> Point2 {
+        Point2::new(p.x * self.scale, p.y * self.scale) + self.offset
+    }
+
+    fn apply_metrics(&self, m: &GlyphMetrics) -> GlyphMetrics {
+        if !m.has_ink() {
+            return GlyphMetrics::blank(self.advance);
+        }
+        let grow = Point2::new(self.embolden, self.embolden);
+        GlyphMetrics::from_ink(
+            self.advance,
+            self.map(m.ink_min) - grow,
+            self.map(m.ink_max) + grow,
+        )
+    }
+
+    fn apply(&self, mut result: GlyphResult) -> GlyphResult {
+        result.metrics = self.apply_metrics(&result.metrics);
+        let sdf = &mut result.sdf;
+        sdf.bbox_min = self.map(sdf.bbox_min);
+        sdf.bbox_max = self.map(sdf.bbox_max);
+        // Scaling the frame scales every distance with it.
+        if self.scale != 1.0 || self.embolden != 0.0 {
+            for d in &mut sdf.data {
+                *d = *d * self.scale - self.embolden;
+            }
+        }
+        sdf.advance = self.advance;
+        sdf.lsb = result.metrics.lsb;
+        result
+    }
+}
+
+/// Metrics `m` scaled by `s` about the pen origin and thickened by
+/// `embolden`.
+fn scaled(m: &GlyphMetrics, s: f32, embolden: f32) -> GlyphMetrics {
+    let grow = Point2::new(embolden, embolden);
+    GlyphMetrics::from_ink(
+        m.advance * s,
+        Point2::new(m.ink_min.x * s, m.ink_min.y * s) - grow,
+        Point2::new(m.ink_max.x * s, m.ink_max.y * s) + grow,
+    )
+}
+
+/// Placement that scales by `scale`, raises by `dy` and leaves `bearing`
+/// on both sides of the ink `m` (already scaled).
+fn fitted(m: &GlyphMetrics, scale: f32, dy: f32, bearing: f32) -> Placement {
+    if !m.has_ink() {
+        return Placement::moved(0.0, dy, m.advance);
+    }
+    Placement {
+        scale,
+        embolden: 0.0,
+        offset: Point2::new(bearing - m.ink_min.x, dy),
+        advance: m.ink_width() + 2.0 * bearing,
+    }
+}
+
+/// `result` (the glyph of `ch`) in figure style `style`, on the widths
+/// `figures` of its font. Characters the style does not touch and missing
+/// glyphs pass through.
+#[must_use]
+pub fn apply(
+    style: FigureStyle,
+    ch: char,
+    result: GlyphResult,
+    params: &MetaFontParams,
+    figures: &FigureMetrics,
+) -> GlyphResult {
+    if !is_styled(ch, style) || result.provenance == GlyphProvenance::Missing {
+        return result;
+    }
+    Placement::new(style, ch, &result.metrics, params, figures).apply(result)
+}
+
 impl GlyphGenerator {
     pub(crate) fn build_digit_0(&self) -> GlyphSkeleton {
         let h = self.cap_height;
@@ -232,3 +458,152 @@ impl GlyphGenerator {
         skel
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::dispatcher::GlyphSourcePolicy;
+    use crate::glyph::provider::ProviderChain;
+
+    /// `ch` from the built-in chain, its digits set in `style`.
+    fn styled(
+        ch: char,
+        params: &MetaFontParams,
+        policy: &GlyphSourcePolicy,
+        style: Option<FigureStyle>,
+    ) -> GlyphResult {
+        let chain = ProviderChain::builtin();
+        let result = chain.generate(ch, params, policy);
+        match style {
+            Some(style) => apply(
+                style,
+                ch,
+                result,
+                params,
+                &chain.figure_metrics(params, policy),
+            ),
+            None => result,
+        }
+    }
+
+    #[test]
+    fn tabular_figures_share_one_cell() {
+        for (params, policy) in [
+            (MetaFontParams::sans_regular(), GlyphSourcePolicy::default()),
+            (
+                MetaFontParams::display_heavy(),
+                GlyphSourcePolicy::parametric_only(),
+            ),
+        ] {
+            let tabular = Some(FigureStyle::TabularLining);
+            let cell = styled('0', &params, &policy, tabular).metrics.advance;
+            for ch in ('0'..='9').chain(TABULAR_SYMBOLS) {
+                let m = styled(ch, &params, &policy, tabular).metrics;
+                assert!((m.advance - cell).abs() < 1e-5, "{ch}: {m:?}");
+                assert!((m.lsb - m.rsb).abs() < 1e-4, "{ch}: {m:?}");
+                assert!(m.lsb >= MIN_SIDE_BEARING - 1e-5, "{ch}: {m:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn proportional_figures_fit_their_ink() {
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::default();
+        let proportional = Some(FigureStyle::ProportionalLining);
+        let zero = styled('0', &params, &policy, proportional).metrics;
+        let one = styled('1', &params, &policy, proportional).metrics;
+        assert!(one.advance < zero.advance);
+        assert!((one.lsb - zero.lsb).abs() < 1e-5 && (one.rsb - zero.lsb).abs() < 1e-5);
+        // Letters are not figures.
+        let plain = styled('A', &params, &policy, None);
+        assert_eq!(
+            styled('A', &params, &policy, proportional).sdf.data,
+            plain.sdf.data
+        );
+    }
+
+    #[test]
+    fn oldstyle_figures_rise_and_descend() {
+        let params = MetaFontParams::sans_regular();
+        let s = params.x_height / params.cap_height;
+        for base in [
+            GlyphSourcePolicy::default(),
+            GlyphSourcePolicy::parametric_only(),
+        ] {
+            let lining = |ch| styled(ch, &params, &base, None).metrics;
+            let old = |ch| styled(ch, &params, &base, Some(FigureStyle::Oldstyle)).metrics;
+            let top = |m: GlyphMetrics| m.ink_max.y;
+            // The x-height figures shrink, thickened back by a little.
+            assert!(top(old('0')) < top(lining('0')) * (s + 0.05));
+            assert!(top(old('0')) > top(lining('0')) * s);
+            assert!((top(old('8')) - top(lining('8'))).abs() < 1e-5);
+            assert!(old('7').ink_min.y < -0.1);
+            assert!((top(old('7')) - top(lining('7')) * s).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn styled_tiles_match_styled_metrics() {
+        let params = MetaFontParams::serif_regular();
+        let policy = GlyphSourcePolicy::parametric_first();
+        for style in [
+            FigureStyle::TabularLining,
+            FigureStyle::ProportionalLining,
+            FigureStyle::Oldstyle,
+        ] {
+            for ch in ['0', '1', '4', '9', '+'] {
+                let result = styled(ch, &params, &policy, Some(style));
+                let m = result.metrics;
+                assert!((result.sdf.advance - m.advance).abs() < 1e-6);
+                assert!((result.sdf.lsb - m.lsb).abs() < 1e-6);
+                // The moved tile still draws where the metrics say.
+                let ink = GlyphMetrics::from_sdf(&result.sdf);
+                let texel = (result.sdf.bbox_max.x - result.sdf.bbox_min.x) / 32.0;
+                assert!(
+                    (ink.ink_min.x - m.ink_min.x).abs() < texel,
+                    "{style:?} {ch}"
+                );
+                assert!(
+                    (ink.ink_max.y - m.ink_max.y).abs() < texel,
+                    "{style:?} {ch}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_figures_pass_through() {
+        let params = MetaFontParams::sans_regular();
+        let chain = ProviderChain::empty();
+        let policy = GlyphSourcePolicy::default();
+        let figures = chain.figure_metrics(&params, &policy);
+        assert_eq!(figures.bearing, MIN_SIDE_BEARING);
+        let missing = chain.generate('7', &params, &policy);
+        assert_eq!(missing.provenance, GlyphProvenance::Missing);
+        let styled = apply(
+            FigureStyle::Oldstyle,
+            '7',
+            chain.generate('7', &params, &policy),
+            &params,
+            &figures,
+        );
+        assert_eq!(styled.sdf.data, missing.sdf.data);
+        assert_eq!(styled.metrics, missing.metrics);
+    }
+
+    #[test]
+    fn no_style_keeps_source_figures() {
+        // The embedded outlines have a proportional `1`.
+        #[cfg(feature = "outlines-latin")]
+        {
+            let params = MetaFontParams::sans_regular();
+            let policy = GlyphSourcePolicy::default();
+            let one = styled('1', &params, &policy, None);
+            assert!(one.metrics.advance < styled('0', &params, &policy, None).metrics.advance);
+        }
+        assert!(!is_styled('a', FigureStyle::TabularLining));
+        assert!(is_styled('±', FigureStyle::TabularLining));
+        assert!(!is_styled('±', FigureStyle::Oldstyle));
+    }
+}
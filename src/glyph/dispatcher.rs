@@ -1,19 +1,41 @@
 //! Glyph dispatcher — routes Unicode code points to the appropriate
 //! glyph generator (ASCII, hiragana, katakana, kanji, ...).
 //!
-//! This module is the single entry point that the atlas calls when it
-//! needs to rasterize a glyph for an arbitrary `char`. It is part of the
-//! CJK extension; see `docs/CJK_DESIGN.md` for the design rationale.
+//! Routing itself is done by the built-in provider chain
+//! ([`crate::glyph::provider`]); this module owns the
+//! code-point categories and the [`GlyphSourcePolicy`] that orders it.
+//!
+//! Atlases resolve glyphs through their own
+//! [`ProviderChain`](crate::glyph::provider::ProviderChain), with runtime
+//! fonts and custom providers in front of the built-ins. The functions
+//! here ([`generate`], [`generate_result`], ...) are the built-in
+//! parametric fallback behind those: they run only the parametric
+//! generators and the embedded outline table, without allocating. This
+//! module is part of the CJK extension; see `docs/CJK_DESIGN.md` for the
+//! design rationale.
 //!
 //! License: MIT
 //! Author: Moroya Sakamoto
 
-use crate::glyph::{font_render, hiragana, kanji, katakana, GlyphGenerator, GlyphSdf};
+use crate::glyph::notdef::NotdefStyle;
+use crate::glyph::variants;
+use crate::glyph::vertical::{self, WritingMode};
+use crate::glyph::{provider, GlyphResult, GlyphSdf};
 use crate::param::MetaFontParams;
+use alloc::vec::Vec;
 
 /// ASCII printable range (matches the existing `GlyphGenerator` coverage).
 pub const ASCII_RANGE: (u32, u32) = (0x0020, 0x007E);
 
+/// Latin-1 Supplement and Latin Extended-A (U+00A0 to U+017F).
+pub const LATIN_RANGE: (u32, u32) = (0x00A0, 0x017F);
+
+/// Greek and Coptic block (U+0370 to U+03FF).
+pub const GREEK_RANGE: (u32, u32) = (0x0370, 0x03FF);
+
+/// Cyrillic block (U+0400 to U+04FF).
+pub const CYRILLIC_RANGE: (u32, u32) = (0x0400, 0x04FF);
+
 /// Hiragana code block (U+3040 to U+309F).
 pub const HIRAGANA_RANGE: (u32, u32) = (0x3040, 0x309F);
 
@@ -23,31 +45,191 @@ pub const KATAKANA_RANGE: (u32, u32) = (0x30A0, 0x30FF);
 /// CJK Unified Ideographs block (U+4E00 to U+9FFF) — the Joyo kanji live here.
 pub const CJK_UNIFIED_RANGE: (u32, u32) = (0x4E00, 0x9FFF);
 
+/// Arrows block (U+2190 to U+21FF).
+pub const ARROWS_RANGE: (u32, u32) = (0x2190, 0x21FF);
+
+/// Box Drawing and Block Elements blocks (U+2500 to U+259F).
+pub const BOX_DRAWING_RANGE: (u32, u32) = (0x2500, 0x259F);
+
+/// Geometric Shapes and Miscellaneous Symbols blocks (U+25A0 to U+26FF).
+pub const GEOMETRIC_SHAPES_RANGE: (u32, u32) = (0x25A0, 0x26FF);
+
+/// Braille Patterns block (U+2800 to U+28FF).
+pub const BRAILLE_RANGE: (u32, u32) = (0x2800, 0x28FF);
+
+/// CJK Symbols and Punctuation block (U+3000 to U+303F).
+pub const CJK_SYMBOLS_RANGE: (u32, u32) = (0x3000, 0x303F);
+
+/// Halfwidth and Fullwidth Forms block (U+FF00 to U+FFEF).
+pub const FULLWIDTH_RANGE: (u32, u32) = (0xFF00, 0xFFEF);
+
+/// Hangul Compatibility Jamo block (U+3130 to U+318F).
+pub const HANGUL_JAMO_RANGE: (u32, u32) = (0x3130, 0x318F);
+
+/// Hangul Syllables block (U+AC00 to U+D7AF).
+pub const HANGUL_RANGE: (u32, u32) = (0xAC00, 0xD7AF);
+
 /// Generate the SDF for an arbitrary character, routing by code-point range.
 ///
-/// Returns a non-empty `GlyphSdf` for supported characters and an empty
-/// placeholder (with `advance = 1.0`) for unsupported code points.
+/// Equivalent to [`generate_with_policy`] with the default
+/// [`GlyphSourcePolicy`] (embedded outlines first, parametric fallback).
+/// Returns a non-empty `GlyphSdf` for supported characters and the
+/// `.notdef` hex box for unsupported code points.
 #[must_use]
 pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
-    // First: BIZ UDPGothic outline table (highest quality, covers ASCII + CJK).
-    if let Some(sdf) = font_render::rasterize(ch, params) {
-        return sdf;
+    generate_with_policy(ch, params, &GlyphSourcePolicy::default())
+}
+
+/// Generate the SDF for `ch`, choosing between the embedded outline table
+/// and the parametric skeletons according to `policy`.
+///
+/// This runs the built-in [`crate::glyph::provider::ProviderChain`]
+/// without allocating; build a chain explicitly to add custom providers.
+#[must_use]
+pub fn generate_with_policy(
+    ch: char,
+    params: &MetaFontParams,
+    policy: &GlyphSourcePolicy,
+) -> GlyphSdf {
+    generate_result(ch, params, policy).sdf
+}
+
+/// Like [`generate_with_policy`], but also reports where the glyph came
+/// from (outline, parametric skeleton, placeholder or missing).
+#[must_use]
+pub fn generate_result(
+    ch: char,
+    params: &MetaFontParams,
+    policy: &GlyphSourcePolicy,
+) -> GlyphResult {
+    provider::generate_from(provider::BUILTIN.iter().copied(), ch, params, policy)
+}
+
+/// Like [`generate_result`], laid out for `mode`: in
+/// [`WritingMode::Vertical`] the long vowel mark, dashes and brackets turn,
+/// and 、。 and small kana move (see [`vertical`]).
+#[must_use]
+pub fn generate_oriented(
+    ch: char,
+    params: &MetaFontParams,
+    policy: &GlyphSourcePolicy,
+    mode: WritingMode,
+) -> GlyphResult {
+    vertical::orient(ch, generate_result(ch, params, policy), mode)
+}
+
+/// Where the dispatcher takes a glyph from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GlyphSource {
+    /// Embedded (or registered) outline, parametric skeleton as fallback.
+    #[default]
+    OutlineFirst,
+    /// Parametric skeleton, outline as fallback for uncovered characters.
+    ParametricFirst,
+    /// Parametric skeleton only; outlines are never consulted.
+    ParametricOnly,
+    /// Outline only; characters without an outline get a placeholder.
+    OutlineOnly,
+}
+
+/// Selects the glyph source per [`GlyphCategory`].
+///
+/// The default is [`GlyphSource::OutlineFirst`] for every category, which
+/// matches the historic behaviour of [`generate`]. Users of stylised
+/// presets (e.g. `serif_italic`) typically want
+/// [`GlyphSourcePolicy::parametric_first`] so that ASCII and kana follow
+/// the parameters instead of the BIZ UDPGothic outlines.
+///
+/// The policy also carries the [`NotdefStyle`] used when no source covers
+/// a character.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlyphSourcePolicy {
+    default: GlyphSource,
+    overrides: Vec<(GlyphCategory, GlyphSource)>,
+    notdef: NotdefStyle,
+}
+
+impl GlyphSourcePolicy {
+    /// Policy that uses `source` for every category.
+    #[must_use]
+    pub const fn uniform(source: GlyphSource) -> Self {
+        Self {
+            default: source,
+            overrides: Vec::new(),
+            notdef: NotdefStyle::HexBox,
+        }
     }
-    // Fallback: parametric skeleton (for chars not in the font table).
-    let cp = ch as u32;
-    if in_range(cp, ASCII_RANGE) {
-        let gen = GlyphGenerator::new(params);
-        gen.generate(ch as u8)
-    } else if in_range(cp, HIRAGANA_RANGE) {
-        hiragana::generate(ch, params)
-    } else if in_range(cp, KATAKANA_RANGE) {
-        katakana::generate(ch, params)
-    } else if in_range(cp, CJK_UNIFIED_RANGE) {
-        kanji::generate(ch, params)
-    } else {
-        let mut sdf = GlyphSdf::empty();
-        sdf.advance = 0.5;
-        sdf
+
+    /// Outline first, parametric fallback (the default).
+    #[must_use]
+    pub const fn outline_first() -> Self {
+        Self::uniform(GlyphSource::OutlineFirst)
+    }
+
+    /// Parametric first, outline fallback.
+    #[must_use]
+    pub const fn parametric_first() -> Self {
+        Self::uniform(GlyphSource::ParametricFirst)
+    }
+
+    /// Parametric skeletons only.
+    #[must_use]
+    pub const fn parametric_only() -> Self {
+        Self::uniform(GlyphSource::ParametricOnly)
+    }
+
+    /// Outlines only.
+    #[must_use]
+    pub const fn outline_only() -> Self {
+        Self::uniform(GlyphSource::OutlineOnly)
+    }
+
+    /// Builder-style per-category override.
+    #[must_use]
+    pub fn with_override(mut self, category: GlyphCategory, source: GlyphSource) -> Self {
+        self.set_override(category, source);
+        self
+    }
+
+    /// Use `source` for `category`, replacing any previous override.
+    pub fn set_override(&mut self, category: GlyphCategory, source: GlyphSource) {
+        match self.overrides.iter_mut().find(|(c, _)| *c == category) {
+            Some(entry) => entry.1 = source,
+            None => self.overrides.push((category, source)),
+        }
+    }
+
+    /// Builder-style `.notdef` style.
+    #[must_use]
+    pub const fn with_notdef(mut self, style: NotdefStyle) -> Self {
+        self.notdef = style;
+        self
+    }
+
+    /// How uncovered characters are drawn.
+    #[must_use]
+    pub const fn notdef(&self) -> NotdefStyle {
+        self.notdef
+    }
+
+    /// Drop the override for `category` so it follows the default again.
+    pub fn clear_override(&mut self, category: GlyphCategory) {
+        self.overrides.retain(|(c, _)| *c != category);
+    }
+
+    /// Source used for categories without an override.
+    #[must_use]
+    pub const fn default_source(&self) -> GlyphSource {
+        self.default
+    }
+
+    /// Effective source for `category`.
+    #[must_use]
+    pub fn source_for(&self, category: GlyphCategory) -> GlyphSource {
+        self.overrides
+            .iter()
+            .find(|(c, _)| *c == category)
+            .map_or(self.default, |&(_, s)| s)
     }
 }
 
@@ -58,12 +240,36 @@ pub const fn category(ch: char) -> GlyphCategory {
     let cp = ch as u32;
     if cp >= ASCII_RANGE.0 && cp <= ASCII_RANGE.1 {
         GlyphCategory::Ascii
+    } else if cp >= LATIN_RANGE.0 && cp <= LATIN_RANGE.1 {
+        GlyphCategory::Latin
+    } else if cp >= GREEK_RANGE.0 && cp <= GREEK_RANGE.1 {
+        GlyphCategory::Greek
+    } else if cp >= CYRILLIC_RANGE.0 && cp <= CYRILLIC_RANGE.1 {
+        GlyphCategory::Cyrillic
+    } else if (cp >= ARROWS_RANGE.0 && cp <= ARROWS_RANGE.1)
+        || (cp >= GEOMETRIC_SHAPES_RANGE.0 && cp <= GEOMETRIC_SHAPES_RANGE.1)
+    {
+        GlyphCategory::Symbols
+    } else if cp >= BOX_DRAWING_RANGE.0 && cp <= BOX_DRAWING_RANGE.1 {
+        GlyphCategory::BoxDrawing
+    } else if cp >= BRAILLE_RANGE.0 && cp <= BRAILLE_RANGE.1 {
+        GlyphCategory::Braille
+    } else if cp >= CJK_SYMBOLS_RANGE.0 && cp <= CJK_SYMBOLS_RANGE.1 {
+        GlyphCategory::CjkSymbols
     } else if cp >= HIRAGANA_RANGE.0 && cp <= HIRAGANA_RANGE.1 {
         GlyphCategory::Hiragana
     } else if cp >= KATAKANA_RANGE.0 && cp <= KATAKANA_RANGE.1 {
         GlyphCategory::Katakana
     } else if cp >= CJK_UNIFIED_RANGE.0 && cp <= CJK_UNIFIED_RANGE.1 {
         GlyphCategory::CjkUnified
+    } else if (cp >= HANGUL_RANGE.0 && cp <= HANGUL_RANGE.1)
+        || (cp >= HANGUL_JAMO_RANGE.0 && cp <= HANGUL_JAMO_RANGE.1)
+    {
+        GlyphCategory::Hangul
+    } else if cp >= FULLWIDTH_RANGE.0 && cp <= FULLWIDTH_RANGE.1 {
+        GlyphCategory::Fullwidth
+    } else if variants::covers(ch) {
+        GlyphCategory::Variants
     } else {
         GlyphCategory::Unsupported
     }
@@ -73,20 +279,29 @@ pub const fn category(ch: char) -> GlyphCategory {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GlyphCategory {
     Ascii,
+    Latin,
+    Greek,
+    Cyrillic,
+    Symbols,
+    BoxDrawing,
+    Braille,
+    CjkSymbols,
     Hiragana,
     Katakana,
     CjkUnified,
+    Hangul,
+    Fullwidth,
+    Variants,
     Unsupported,
 }
 
-#[inline]
-const fn in_range(cp: u32, range: (u32, u32)) -> bool {
-    cp >= range.0 && cp <= range.1
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::glyph::provider::GlyphProvider;
+    use crate::glyph::{
+        box_drawing, braille, font_render, hangul, notdef, GlyphGenerator, GlyphProvenance,
+    };
 
     #[test]
     fn category_ascii_letters() {
@@ -97,6 +312,141 @@ mod tests {
         assert_eq!(category('~'), GlyphCategory::Ascii);
     }
 
+    #[test]
+    fn category_latin_blocks() {
+        assert_eq!(category('\u{A0}'), GlyphCategory::Latin);
+        assert_eq!(category('é'), GlyphCategory::Latin);
+        assert_eq!(category('ſ'), GlyphCategory::Latin);
+        assert_eq!(category('\u{180}'), GlyphCategory::Unsupported);
+    }
+
+    #[test]
+    fn category_greek_and_cyrillic() {
+        assert_eq!(category('Ω'), GlyphCategory::Greek);
+        assert_eq!(category('ϐ'), GlyphCategory::Greek);
+        assert_eq!(category('Ж'), GlyphCategory::Cyrillic);
+        assert_eq!(category('ґ'), GlyphCategory::Cyrillic);
+    }
+
+    #[test]
+    fn greek_and_cyrillic_route_to_skeletons() {
+        let params = MetaFontParams::sans_regular();
+        let gen = GlyphGenerator::new(&params);
+        for ch in ['λ', 'Ж', 'ї'] {
+            let result = generate_result(ch, &params, &GlyphSourcePolicy::default());
+            assert_eq!(result.provenance, GlyphProvenance::Parametric, "{ch}");
+            assert_eq!(result.sdf.data, gen.generate_char(ch).unwrap().data);
+        }
+        // Uncovered letters in the blocks still fall back to notdef.
+        let result = generate_result('Ѡ', &params, &GlyphSourcePolicy::default());
+        assert_eq!(result.provenance, GlyphProvenance::Missing);
+    }
+
+    #[test]
+    fn cjk_symbols_and_fullwidth_forms_route_to_skeletons() {
+        assert_eq!(category('。'), GlyphCategory::CjkSymbols);
+        assert_eq!(category('〜'), GlyphCategory::CjkSymbols);
+        assert_eq!(category('Ａ'), GlyphCategory::Fullwidth);
+        assert_eq!(category('ｶ'), GlyphCategory::Fullwidth);
+        let params = MetaFontParams::sans_regular();
+        let parametric = GlyphSourcePolicy::parametric_only();
+        for ch in ['、', '「', '！', 'ｶ', '｡'] {
+            let result = generate_result(ch, &params, &parametric);
+            assert_eq!(result.provenance, GlyphProvenance::Parametric, "{ch}");
+        }
+        let full = generate_result('Ａ', &params, &parametric);
+        assert!((full.sdf.advance - 1.0).abs() < 1e-6);
+        let half = generate_result('ｶ', &params, &parametric);
+        assert!((half.sdf.advance - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn box_drawing_routes_to_cell_geometry() {
+        assert_eq!(category('─'), GlyphCategory::BoxDrawing);
+        assert_eq!(category('█'), GlyphCategory::BoxDrawing);
+        assert_eq!(category('\u{24FF}'), GlyphCategory::Unsupported);
+        let params = MetaFontParams::sans_regular();
+        for policy in [
+            GlyphSourcePolicy::default(),
+            GlyphSourcePolicy::parametric_only(),
+        ] {
+            let result = generate_result('╬', &params, &policy);
+            assert_eq!(result.provenance, GlyphProvenance::Parametric);
+            assert_eq!(
+                result.sdf.data,
+                box_drawing::generate('╬', &params).unwrap().data
+            );
+        }
+    }
+
+    #[test]
+    fn braille_and_geometric_shapes_route_to_generators() {
+        assert_eq!(category('⣿'), GlyphCategory::Braille);
+        assert_eq!(category('●'), GlyphCategory::Symbols);
+        assert_eq!(category('★'), GlyphCategory::Symbols);
+        assert_eq!(category('⇒'), GlyphCategory::Symbols);
+        let params = MetaFontParams::sans_regular();
+        let parametric = GlyphSourcePolicy::parametric_only();
+        for ch in ['⠿', '■', '☆', '←', '⇒', '→'] {
+            let result = generate_result(ch, &params, &parametric);
+            assert_eq!(result.provenance, GlyphProvenance::Parametric, "{ch}");
+        }
+        let dots = generate_result('⠿', &params, &GlyphSourcePolicy::default());
+        assert_eq!(dots.sdf.data, braille::generate('⠿', &params).unwrap().data);
+        // Shapes without a generator still get the hex box.
+        let missing = generate_result('◐', &params, &parametric);
+        assert_eq!(missing.provenance, GlyphProvenance::Missing);
+    }
+
+    #[test]
+    fn writing_mode_selects_vertical_forms() {
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::default();
+        for ch in ['ー', '「', '。', 'っ'] {
+            let h = generate_oriented(ch, &params, &policy, WritingMode::Horizontal);
+            assert_eq!(h.sdf.data, generate(ch, &params).data, "{ch}");
+            let v = generate_oriented(ch, &params, &policy, WritingMode::Vertical);
+            assert!(
+                v.sdf.data != h.sdf.data || v.sdf.bbox_min != h.sdf.bbox_min,
+                "{ch}"
+            );
+        }
+        let v = generate_oriented('あ', &params, &policy, WritingMode::Vertical);
+        assert_eq!(v.sdf.data, generate('あ', &params).data);
+    }
+
+    #[test]
+    fn variants_route_to_synthesized_forms() {
+        assert_eq!(category('ᴀ'), GlyphCategory::Variants);
+        assert_eq!(category('ⁿ'), GlyphCategory::Variants);
+        assert_eq!(category('₂'), GlyphCategory::Variants);
+        assert_eq!(category('²'), GlyphCategory::Latin);
+        let params = MetaFontParams::sans_regular();
+        for ch in ['ꜱ', '⁴', '₍', '²'] {
+            let result = generate_result(ch, &params, &GlyphSourcePolicy::parametric_only());
+            assert_eq!(result.provenance, GlyphProvenance::Parametric, "{ch}");
+            assert_eq!(
+                result.sdf.data,
+                provider::VariantsProvider
+                    .generate(ch, &params)
+                    .unwrap()
+                    .sdf
+                    .data,
+                "{ch}"
+            );
+        }
+    }
+
+    #[test]
+    fn hangul_routes_to_composer() {
+        assert_eq!(category('한'), GlyphCategory::Hangul);
+        assert_eq!(category('ㅎ'), GlyphCategory::Hangul);
+        let params = MetaFontParams::sans_regular();
+        let result = generate_result('한', &params, &GlyphSourcePolicy::default());
+        assert_eq!(result.provenance, GlyphProvenance::Parametric);
+        assert_eq!(result.sdf.data, hangul::generate('한', &params).data);
+    }
+
     #[test]
     fn category_hiragana_block() {
         assert_eq!(category('あ'), GlyphCategory::Hiragana);
@@ -122,8 +472,8 @@ mod tests {
     fn category_unsupported() {
         // Tab is below ASCII printable range.
         assert_eq!(category('\t'), GlyphCategory::Unsupported);
-        // Hangul is outside our currently-supported blocks.
-        assert_eq!(category('가'), GlyphCategory::Unsupported);
+        // Thai is outside our currently-supported blocks.
+        assert_eq!(category('ก'), GlyphCategory::Unsupported);
     }
 
     #[test]
@@ -155,7 +505,112 @@ mod tests {
 
     #[test]
     fn unsupported_returns_placeholder_with_advance() {
-        let sdf = generate('가', &MetaFontParams::sans_regular());
+        let sdf = generate('ก', &MetaFontParams::sans_regular());
         assert!(sdf.advance > 0.0);
     }
+
+    #[test]
+    fn default_policy_matches_generate() {
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::default();
+        for ch in ['A', 'あ', '明'] {
+            assert_eq!(
+                generate(ch, &params).data,
+                generate_with_policy(ch, &params, &policy).data
+            );
+        }
+    }
+
+    #[test]
+    fn parametric_only_bypasses_outlines() {
+        let params = MetaFontParams::serif_italic();
+        let sdf = generate_with_policy('A', &params, &GlyphSourcePolicy::parametric_only());
+        let skeleton = GlyphGenerator::new(&params).generate(b'A');
+        assert_eq!(sdf.data, skeleton.data);
+    }
+
+    #[test]
+    fn parametric_first_falls_back_to_outline() {
+        // No parametric generator covers U+2014, so the outline wins.
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::parametric_first();
+        let sdf = generate_with_policy('—', &params, &policy);
+        match font_render::rasterize('—', &params) {
+            Some(outline) => assert_eq!(outline.data, sdf.data),
+            None => assert_eq!(
+                sdf.data,
+                notdef::generate('—', &params, NotdefStyle::HexBox).data
+            ),
+        }
+    }
+
+    #[test]
+    fn parametric_first_draws_latin_skeleton() {
+        let params = MetaFontParams::sans_regular();
+        let sdf = generate_with_policy('é', &params, &GlyphSourcePolicy::parametric_first());
+        let skeleton = GlyphGenerator::new(&params).generate_char('é').unwrap();
+        assert_eq!(sdf.data, skeleton.data);
+    }
+
+    #[test]
+    fn outline_only_yields_notdef_without_outline() {
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::outline_only();
+        let sdf = generate_with_policy('ก', &params, &policy);
+        let tofu = notdef::generate('ก', &params, NotdefStyle::HexBox);
+        assert_eq!(sdf.data, tofu.data);
+
+        let blank = generate_with_policy('ก', &params, &policy.with_notdef(NotdefStyle::Blank));
+        assert!(blank.data.iter().all(|d| (*d - 1.0).abs() < 1e-6));
+        assert!((blank.advance - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn unsupported_renders_visible_notdef() {
+        let sdf = generate('\u{E000}', &MetaFontParams::sans_regular());
+        assert!(sdf.data.iter().any(|d| *d < 0.0));
+    }
+
+    #[test]
+    fn generate_result_reports_provenance() {
+        let params = MetaFontParams::sans_regular();
+        let parametric = GlyphSourcePolicy::parametric_only();
+        let a = generate_result('A', &params, &parametric);
+        assert_eq!(a.provenance, GlyphProvenance::Parametric);
+        assert!(a.stroke_count > 0 && !a.is_truncated());
+
+        let placeholder = generate_result('任', &params, &parametric);
+        assert_eq!(placeholder.provenance, GlyphProvenance::Placeholder);
+
+        let truncated = generate_result('検', &params, &parametric);
+        assert!(truncated.is_truncated());
+
+        let missing = generate_result('ก', &params, &parametric);
+        assert_eq!(missing.provenance, GlyphProvenance::Missing);
+        assert_eq!(missing.stroke_count, 0);
+
+        if font_render::has('A') {
+            let outline = generate_result('A', &params, &GlyphSourcePolicy::default());
+            assert_eq!(outline.provenance, GlyphProvenance::Outline);
+        }
+    }
+
+    #[test]
+    fn overrides_apply_per_category() {
+        let policy = GlyphSourcePolicy::outline_first()
+            .with_override(GlyphCategory::Ascii, GlyphSource::ParametricOnly)
+            .with_override(GlyphCategory::Ascii, GlyphSource::ParametricFirst);
+        assert_eq!(
+            policy.source_for(GlyphCategory::Ascii),
+            GlyphSource::ParametricFirst
+        );
+        assert_eq!(
+            policy.source_for(GlyphCategory::Hiragana),
+            GlyphSource::OutlineFirst
+        );
+
+        let mut policy = policy;
+        policy.clear_override(GlyphCategory::Ascii);
+        assert_eq!(policy, GlyphSourcePolicy::outline_first());
+    }
 }
@@ -0,0 +1,710 @@
+//! Fixed-point rasterization — bit-identical tiles on every target
+//!
+//! The float rasterizers are deterministic for one build, but not across
+//! targets: the SIMD backend, `f32::min`/`max` on signed zeros, and the
+//! order distances are accumulated in all depend on the platform, so
+//! lockstep clients on x86 and ARM can end up with different tiles.
+//!
+//! This module is a separate rasterization mode for skeletons and
+//! outlines. Geometry is quantized once to Q16.16 em units (a 1/65536 em
+//! grid); everything after that is integer arithmetic:
+//!
+//! - curves are evaluated by de Casteljau subdivision with rounded shifts
+//! - the pen width uses a CORDIC pen direction and the exact sine of the
+//!   tangent angle, instead of `atan2`/`sin` approximations
+//! - distances are integer square roots of exact squared distances
+//! - outlines are flattened to within [`FLATTEN_TOLERANCE`] and filled with
+//!   an integer winding count
+//!
+//! Each sample is then converted to `f32` exactly (every Q16.16 value below
+//! 256 em is representable), so the same input gives the same bits
+//! everywhere, in debug and release builds, with or without SIMD. Advances,
+//! side bearings and tile frames are snapped to the same grid.
+//!
+//! The skeleton builders and outline decoders that feed this module use
+//! only IEEE-754 basic operations, which are correctly rounded on every
+//! target. Tiles differ slightly from the float mode (well under a
+//! thousandth of an em); use one mode consistently. Compare tiles with
+//! [`GlyphSdf::content_hash`].
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::glyph::font_render::{self, TILE_EM_BOTTOM, TILE_EM_LEFT, TILE_EM_RIGHT, TILE_EM_TOP};
+use crate::glyph::outline::{self, FillRule, PathCmd, Segment};
+use crate::glyph::{
+    GlyphGenerator, GlyphMetrics, GlyphResult, GlyphSdf, GlyphSkeleton, GLYPH_SDF_SIZE,
+    MAX_GLYPH_STROKES, MIN_SIDE_BEARING,
+};
+use crate::param::MetaFontParams;
+use crate::stroke::{PenModel, Point2, Stroke};
+
+/// Fractional bits of the fixed-point format (Q16.16).
+pub const FRAC_BITS: u32 = 16;
+/// 1.0 em in fixed point.
+pub const ONE: i32 = 1 << FRAC_BITS;
+/// Largest distance (em) the polyline of a flattened curve may stray from
+/// the curve.
+pub const FLATTEN_TOLERANCE: f32 = 1.0 / 4096.0;
+
+/// Coordinates are clamped to ±this many em so products fit in `i64`.
+const COORD_LIMIT: i32 = 256 * ONE;
+/// Rounding bias for a right shift by `FRAC_BITS`.
+const HALF: i64 = 1 << (FRAC_BITS - 1);
+/// Parameter steps per skeleton stroke, as in the float rasterizer.
+const STEPS: i64 = 16;
+const SAMPLES: usize = STEPS as usize + 1;
+/// Most line segments one curve is flattened into.
+const MAX_FLATTEN_SEGMENTS: i64 = 64;
+/// π and π/2 in fixed point.
+const PI: i32 = 205_887;
+const HALF_PI: i32 = 102_944;
+/// `atan(2^-i)` in fixed point, for CORDIC.
+const ATAN: [i32; 16] = [
+    51472, 30386, 16055, 8150, 4091, 2047, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2,
+];
+/// CORDIC gain correction `∏ 1/√(1 + 2^-2i)` in fixed point.
+const CORDIC_GAIN: i32 = 39797;
+
+type Fx2 = (i32, i32);
+
+/// `v` em in fixed point, rounded to nearest (saturating; NaN → 0).
+#[must_use]
+pub fn to_fixed(v: f32) -> i32 {
+    let scaled = v * ONE as f32;
+    let rounded = if scaled < 0.0 {
+        scaled - 0.5
+    } else {
+        scaled + 0.5
+    };
+    rounded as i32
+}
+
+/// Fixed-point `v` as em. Exact for |v| < 256 em.
+#[must_use]
+pub fn to_f32(v: i32) -> f32 {
+    v as f32 * (1.0 / ONE as f32)
+}
+
+/// Render `ch` in fixed point: the embedded outline when there is one,
+/// otherwise the parametric skeleton. `None` for characters neither covers
+/// (procedural tiles such as box drawing have no fixed-point path).
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams) -> Option<GlyphSdf> {
+    render(ch, params).map(|result| result.sdf)
+}
+
+/// [`generate`] with provenance and metrics.
+#[must_use]
+pub fn render(ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+    if let Some(styled) = font_render::styled_outline(ch, params) {
+        let mut sdf = if styled.cmds.is_empty() {
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = snap(styled.advance);
+            sdf
+        } else {
+            rasterize_outline(&styled.cmds, styled.advance, FillRule::NonZero)
+        };
+        let embolden = to_fixed(styled.embolden);
+        if embolden != 0 && !styled.cmds.is_empty() {
+            for d in &mut sdf.data {
+                *d = to_f32(to_fixed(*d) - embolden);
+            }
+        }
+        let m = styled.metrics();
+        let metrics = GlyphMetrics::from_ink(
+            snap(m.advance),
+            Point2::new(snap(m.ink_min.x), snap(m.ink_min.y)),
+            Point2::new(snap(m.ink_max.x), snap(m.ink_max.y)),
+        );
+        return Some(GlyphResult::outline(sdf).with_metrics(metrics));
+    }
+    let (gen, skeleton) = GlyphGenerator::new(params).any_skeleton(ch)?;
+    Some(gen.render_fixed(&skeleton))
+}
+
+/// Fixed-point counterpart of [`font_render::rasterize_outline`]: same tile
+/// frame, fill rules and sign convention.
+#[must_use]
+pub fn rasterize_outline(cmds: &[PathCmd], advance: f32, fill: FillRule) -> GlyphSdf {
+    let left = to_fixed(TILE_EM_LEFT);
+    let bottom = to_fixed(TILE_EM_BOTTOM);
+    let right = to_fixed(TILE_EM_RIGHT);
+    let top = to_fixed(TILE_EM_TOP);
+
+    let mut sdf = GlyphSdf::empty();
+    sdf.advance = snap(advance);
+    sdf.bbox_min = Point2::new(to_f32(left), to_f32(bottom));
+    sdf.bbox_max = Point2::new(to_f32(right), to_f32(top));
+
+    let mut lines = Vec::new();
+    for seg in outline::segments(cmds) {
+        match seg {
+            Segment::Line(p0, p1) => lines.push(Line::new(coord(p0), coord(p1))),
+            Segment::Quad(p0, p1, p2) => flatten(&[coord(p0), coord(p1), coord(p2)], &mut lines),
+            Segment::Cubic(p0, p1, p2, p3) => {
+                flatten(&[coord(p0), coord(p1), coord(p2), coord(p3)], &mut lines)
+            }
+        }
+    }
+
+    let size = GLYPH_SDF_SIZE;
+    let mut crossings: Vec<(i32, i32)> = Vec::new();
+    let mut nearest_first: Vec<(i64, usize)> = Vec::with_capacity(lines.len());
+    for py in 0..size {
+        let y = grid(bottom, top, py);
+
+        // Inside test: winding of the crossings right of each pixel.
+        crossings.clear();
+        crossings.extend(lines.iter().filter_map(|l| l.crossing(y)));
+        crossings.sort_unstable();
+        let mut winding: i32 = crossings.iter().map(|c| c.1).sum();
+        let mut passed = 0;
+
+        nearest_first.clear();
+        nearest_first.extend(lines.iter().enumerate().map(|(i, l)| {
+            let dy = i64::from(l.y_min - y).max(i64::from(y - l.y_max)).max(0);
+            (dy * dy, i)
+        }));
+        nearest_first.sort_unstable();
+
+        for px in 0..size {
+            let x = grid(left, right, px);
+            while passed < crossings.len() && crossings[passed].0 <= x {
+                winding -= crossings[passed].1;
+                passed += 1;
+            }
+            let inside = match fill {
+                FillRule::NonZero => winding != 0,
+                FillRule::EvenOdd => winding & 1 != 0,
+            };
+
+            let mut best = i64::MAX;
+            for &(bound, i) in &nearest_first {
+                if bound >= best {
+                    break;
+                }
+                let l = &lines[i];
+                let dx = i64::from(l.x_min - x).max(i64::from(x - l.x_max)).max(0);
+                if dx * dx + bound >= best {
+                    continue;
+                }
+                best = best.min(l.distance_sq(x, y));
+            }
+            let dist = if best == i64::MAX {
+                COORD_LIMIT
+            } else {
+                isqrt(best)
+            };
+            sdf.data[py * size + px] = to_f32(if inside { -dist } else { dist });
+        }
+    }
+    sdf
+}
+
+impl GlyphGenerator {
+    /// Fixed-point counterpart of [`generate_from_skeleton`](Self::generate_from_skeleton).
+    #[must_use]
+    pub fn generate_fixed(&self, skeleton: &GlyphSkeleton) -> GlyphSdf {
+        self.render_fixed(skeleton).sdf
+    }
+
+    /// Fixed-point counterpart of [`render`](Self::render). Metrics are
+    /// measured from the same fixed-point samples as the tile.
+    #[must_use]
+    pub fn render_fixed(&self, skeleton: &GlyphSkeleton) -> GlyphResult {
+        let pen = Pen::new(&self.pen);
+        let strokes = &skeleton.strokes[..skeleton.stroke_count];
+        let mut samples = [Samples::EMPTY; MAX_GLYPH_STROKES];
+        for (stroke, s) in strokes.iter().zip(samples.iter_mut()) {
+            *s = Samples::new(stroke, &pen);
+        }
+        let samples = &samples[..strokes.len()];
+
+        let design_advance = to_fixed(skeleton.advance);
+        let mut sdf = GlyphSdf::empty();
+        if samples.is_empty() {
+            sdf.advance = to_f32(design_advance);
+            let metrics = GlyphMetrics::blank(sdf.advance);
+            return GlyphResult::parametric(sdf, skeleton, metrics);
+        }
+
+        // Metrics: ink is the union of pen discs, as in `metrics`.
+        let mut ink_lo = (i32::MAX, i32::MAX);
+        let mut ink_hi = (i32::MIN, i32::MIN);
+        for s in samples {
+            for i in 0..SAMPLES {
+                let hw = s.hw[i];
+                ink_lo = (ink_lo.0.min(s.x[i] - hw), ink_lo.1.min(s.y[i] - hw));
+                ink_hi = (ink_hi.0.max(s.x[i] + hw), ink_hi.1.max(s.y[i] + hw));
+            }
+        }
+        let advance = design_advance.max(ink_hi.0 - ink_lo.0 + 2 * to_fixed(MIN_SIDE_BEARING));
+        let metrics = GlyphMetrics::from_ink(
+            to_f32(advance),
+            Point2::new(to_f32(ink_lo.0), to_f32(ink_lo.1)),
+            Point2::new(to_f32(ink_hi.0), to_f32(ink_hi.1)),
+        );
+        sdf.advance = metrics.advance;
+        sdf.lsb = metrics.lsb;
+
+        // Tile frame: every other sample (as `compute_bbox`) plus three pen
+        // widths of padding.
+        let mut lo = (i32::MAX, i32::MAX);
+        let mut hi = (i32::MIN, i32::MIN);
+        for s in samples {
+            for i in (0..SAMPLES).step_by(2) {
+                lo = (lo.0.min(s.x[i]), lo.1.min(s.y[i]));
+                hi = (hi.0.max(s.x[i]), hi.1.max(s.y[i]));
+            }
+        }
+        let padding = 3 * pen.base;
+        let (lo, hi) = (
+            (lo.0 - padding, lo.1 - padding),
+            (hi.0 + padding, hi.1 + padding),
+        );
+        sdf.bbox_min = Point2::new(to_f32(lo.0), to_f32(lo.1));
+        sdf.bbox_max = Point2::new(to_f32(hi.0), to_f32(hi.1));
+        if hi.0 <= lo.0 || hi.1 <= lo.1 {
+            return GlyphResult::parametric(sdf, skeleton, metrics);
+        }
+
+        let size = GLYPH_SDF_SIZE;
+        let mut order = [(0i32, 0usize); MAX_GLYPH_STROKES];
+        let order = &mut order[..samples.len()];
+        for py in 0..size {
+            let y = grid(lo.1, hi.1, py);
+            // Strokes nearest to the row first; a stroke's distance is at
+            // least its box distance minus its widest pen disc.
+            for (si, slot) in order.iter_mut().enumerate() {
+                let s = &samples[si];
+                *slot = ((s.lo.1 - y).max(y - s.hi.1).max(0) - s.reach, si);
+            }
+            order.sort_unstable();
+
+            for px in 0..size {
+                let x = grid(lo.0, hi.0, px);
+                let mut best = i32::MAX;
+                for &(bound, si) in order.iter() {
+                    if bound >= best {
+                        break;
+                    }
+                    let s = &samples[si];
+                    let dx = i64::from((s.lo.0 - x).max(x - s.hi.0).max(0));
+                    let dy = i64::from((s.lo.1 - y).max(y - s.hi.1).max(0));
+                    if isqrt(dx * dx + dy * dy) - s.reach >= best {
+                        continue;
+                    }
+                    for i in 0..SAMPLES {
+                        let dx = i64::from(x - s.x[i]);
+                        let dy = i64::from(y - s.y[i]);
+                        best = best.min(isqrt(dx * dx + dy * dy) - s.hw[i]);
+                    }
+                }
+                sdf.data[py * size + px] = to_f32(best);
+            }
+        }
+        GlyphResult::parametric(sdf, skeleton, metrics)
+    }
+}
+
+/// `v` rounded to the fixed-point grid.
+fn snap(v: f32) -> f32 {
+    to_f32(to_fixed(v))
+}
+
+/// A point in fixed point, clamped to the coordinate range.
+fn coord((x, y): (f32, f32)) -> Fx2 {
+    (
+        to_fixed(x).clamp(-COORD_LIMIT, COORD_LIMIT),
+        to_fixed(y).clamp(-COORD_LIMIT, COORD_LIMIT),
+    )
+}
+
+/// Sample `i` of `GLYPH_SDF_SIZE` spread evenly over `lo..=hi`.
+fn grid(lo: i32, hi: i32, i: usize) -> i32 {
+    let span = i64::from(hi) - i64::from(lo);
+    let last = (GLYPH_SDF_SIZE - 1) as i64;
+    lo + ((span * i as i64 + last / 2) / last) as i32
+}
+
+/// Fixed-point product, rounded.
+fn mul(a: i32, b: i32) -> i32 {
+    ((i64::from(a) * i64::from(b) + HALF) >> FRAC_BITS) as i32
+}
+
+fn lerp(a: Fx2, b: Fx2, t: i32) -> Fx2 {
+    (a.0 + mul(b.0 - a.0, t), a.1 + mul(b.1 - a.1, t))
+}
+
+/// Point and derivative of a Bezier curve (3 or 4 control points) at `t`.
+fn eval(ctrl: &[Fx2], t: i32) -> (Fx2, Fx2) {
+    let mut p = [(0, 0); 4];
+    let n = ctrl.len();
+    p[..n].copy_from_slice(ctrl);
+    for level in (2..n).rev() {
+        for i in 0..level {
+            p[i] = lerp(p[i], p[i + 1], t);
+        }
+    }
+    let degree = n as i32 - 1;
+    let tangent = (degree * (p[1].0 - p[0].0), degree * (p[1].1 - p[0].1));
+    (lerp(p[0], p[1], t), tangent)
+}
+
+/// Floor of √v, in the units of the square root of `v`'s units.
+fn isqrt(v: i64) -> i32 {
+    v.unsigned_abs().isqrt() as i32
+}
+
+/// `(cos, sin)` of the pen line at `angle` (radians, fixed point), by
+/// CORDIC. The pen is a line, so the angle is only meaningful modulo π.
+fn cos_sin(angle: i32) -> Fx2 {
+    let mut a = angle.rem_euclid(PI);
+    if a > HALF_PI {
+        a -= PI;
+    }
+    let (mut x, mut y) = (CORDIC_GAIN, 0);
+    for (i, &step) in ATAN.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if a >= 0 {
+            (x, y, a) = (x - dx, y + dy, a - step);
+        } else {
+            (x, y, a) = (x + dx, y - dy, a + step);
+        }
+    }
+    (x, y)
+}
+
+/// [`PenModel`] in fixed point.
+struct Pen {
+    base: i32,
+    contrast: i32,
+    /// Pen direction
+    dir: Fx2,
+}
+
+impl Pen {
+    fn new(pen: &PenModel) -> Self {
+        Self {
+            base: to_fixed(pen.base_width),
+            contrast: to_fixed(pen.contrast),
+            dir: cos_sin(to_fixed(pen.pen_angle)),
+        }
+    }
+
+    /// `base · (1 − contrast · sin²(θ − pen angle))` for tangent angle θ,
+    /// with the sine taken from the cross product.
+    fn half_width(&self, (tx, ty): Fx2) -> i32 {
+        let (tx, ty) = (i64::from(tx), i64::from(ty));
+        let (cos, sin) = (i64::from(self.dir.0), i64::from(self.dir.1));
+        let len_sq = tx * tx + ty * ty;
+        let sin_sq = if len_sq == 0 {
+            // Degenerate tangent: the float pen treats it as angle 0.
+            mul(self.dir.1, self.dir.1)
+        } else {
+            let cross = i128::from(ty * cos - tx * sin);
+            (cross * cross / (i128::from(len_sq) << FRAC_BITS)) as i32
+        };
+        mul(self.base, ONE - mul(self.contrast, sin_sq))
+    }
+}
+
+/// One skeleton stroke sampled at `t = i / STEPS`.
+#[derive(Clone, Copy)]
+struct Samples {
+    x: [i32; SAMPLES],
+    y: [i32; SAMPLES],
+    hw: [i32; SAMPLES],
+    /// Sample bounding box
+    lo: Fx2,
+    hi: Fx2,
+    /// Widest half-width
+    reach: i32,
+}
+
+impl Samples {
+    const EMPTY: Self = Self {
+        x: [0; SAMPLES],
+        y: [0; SAMPLES],
+        hw: [0; SAMPLES],
+        lo: (0, 0),
+        hi: (0, 0),
+        reach: 0,
+    };
+
+    fn new(stroke: &Stroke, pen: &Pen) -> Self {
+        let ctrl = [stroke.p0, stroke.p1, stroke.p2, stroke.p3].map(|p| coord((p.x, p.y)));
+        let mut s = Self {
+            lo: (i32::MAX, i32::MAX),
+            hi: (i32::MIN, i32::MIN),
+            ..Self::EMPTY
+        };
+        for i in 0..SAMPLES {
+            let t = ((i as i64) << FRAC_BITS) / STEPS;
+            let (p, tangent) = eval(&ctrl, t as i32);
+            s.x[i] = p.0;
+            s.y[i] = p.1;
+            s.hw[i] = pen.half_width(tangent);
+            s.lo = (s.lo.0.min(p.0), s.lo.1.min(p.1));
+            s.hi = (s.hi.0.max(p.0), s.hi.1.max(p.1));
+            s.reach = s.reach.max(s.hw[i]);
+        }
+        s
+    }
+}
+
+/// Flatten a quadratic or cubic Bezier into `out`, with enough segments to
+/// stay within [`FLATTEN_TOLERANCE`] of the curve.
+fn flatten(ctrl: &[Fx2], out: &mut Vec<Line>) {
+    // Uniform subdivision into n pieces deviates by at most
+    // d(d−1)/8 · max|Δ²P| / n² for degree d.
+    let degree = ctrl.len() as i64 - 1;
+    let second_diff = ctrl
+        .windows(3)
+        .map(|w| {
+            let ddx = i64::from(w[0].0) - 2 * i64::from(w[1].0) + i64::from(w[2].0);
+            let ddy = i64::from(w[0].1) - 2 * i64::from(w[1].1) + i64::from(w[2].1);
+            ddx.abs() + ddy.abs()
+        })
+        .max()
+        .unwrap_or(0);
+    let tolerance = i64::from(to_fixed(FLATTEN_TOLERANCE));
+    let n_sq = (degree * (degree - 1) * second_diff + 8 * tolerance - 1) / (8 * tolerance);
+    let n = (i64::from(isqrt(n_sq)) + 1).min(MAX_FLATTEN_SEGMENTS);
+
+    let mut prev = ctrl[0];
+    for k in 1..=n {
+        let next = if k == n {
+            ctrl[ctrl.len() - 1]
+        } else {
+            eval(ctrl, ((k << FRAC_BITS) / n) as i32).0
+        };
+        out.push(Line::new(prev, next));
+        prev = next;
+    }
+}
+
+/// A flattened outline segment.
+struct Line {
+    p0: Fx2,
+    p1: Fx2,
+    x_min: i32,
+    x_max: i32,
+    y_min: i32,
+    y_max: i32,
+}
+
+impl Line {
+    fn new(p0: Fx2, p1: Fx2) -> Self {
+        Self {
+            p0,
+            p1,
+            x_min: p0.0.min(p1.0),
+            x_max: p0.0.max(p1.0),
+            y_min: p0.1.min(p1.1),
+            y_max: p0.1.max(p1.1),
+        }
+    }
+
+    /// Where the scanline `y` crosses this line, and the direction (+1
+    /// upward). Lines cover `y_min <= y < y_max`, so shared endpoints count
+    /// once.
+    fn crossing(&self, y: i32) -> Option<(i32, i32)> {
+        if y < self.y_min || y >= self.y_max {
+            return None;
+        }
+        let ((x0, y0), (x1, y1)) = (self.p0, self.p1);
+        let x = i64::from(x0)
+            + (i64::from(y) - i64::from(y0)) * (i64::from(x1) - i64::from(x0))
+                / (i64::from(y1) - i64::from(y0));
+        Some((x as i32, if y1 > y0 { 1 } else { -1 }))
+    }
+
+    /// Exact squared distance from `(x, y)` (rounded down off the ends).
+    fn distance_sq(&self, x: i32, y: i32) -> i64 {
+        let ((x0, y0), (x1, y1)) = (self.p0, self.p1);
+        let (dx, dy) = (i64::from(x1) - i64::from(x0), i64::from(y1) - i64::from(y0));
+        let (ax, ay) = (i64::from(x) - i64::from(x0), i64::from(y) - i64::from(y0));
+        let len_sq = dx * dx + dy * dy;
+        let dot = ax * dx + ay * dy;
+        if dot <= 0 || len_sq == 0 {
+            return ax * ax + ay * ay;
+        }
+        if dot >= len_sq {
+            let (bx, by) = (i64::from(x) - i64::from(x1), i64::from(y) - i64::from(y1));
+            return bx * bx + by * by;
+        }
+        let cross = i128::from(ax * dy - ay * dx);
+        (cross * cross / i128::from(len_sq)) as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skeleton_of(gen: &GlyphGenerator, ch: char) -> GlyphSkeleton {
+        gen.any_skeleton(ch).expect("skeleton").1
+    }
+
+    fn max_diff(a: &GlyphSdf, b: &GlyphSdf) -> f32 {
+        a.data
+            .iter()
+            .zip(&b.data)
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f32::max)
+    }
+
+    fn nested_squares() -> Vec<PathCmd> {
+        alloc::vec![
+            PathCmd::MoveTo(0.1, -0.1),
+            PathCmd::LineTo(0.9, -0.1),
+            PathCmd::LineTo(0.9, 0.7),
+            PathCmd::LineTo(0.1, 0.7),
+            PathCmd::MoveTo(0.3, 0.1),
+            PathCmd::LineTo(0.7, 0.1),
+            PathCmd::LineTo(0.7, 0.5),
+            PathCmd::LineTo(0.3, 0.5),
+        ]
+    }
+
+    fn curves() -> Vec<PathCmd> {
+        alloc::vec![
+            PathCmd::MoveTo(0.5, -0.15),
+            PathCmd::CubicTo(0.95, -0.15, 0.95, 0.75, 0.5, 0.75),
+            PathCmd::QuadTo(0.05, 0.75, 0.05, 0.3),
+            PathCmd::QuadTo(0.05, -0.15, 0.5, -0.15),
+            PathCmd::MoveTo(0.3, 0.3),
+            PathCmd::CubicTo(0.3, 0.6, 0.7, 0.6, 0.7, 0.3),
+        ]
+    }
+
+    #[test]
+    fn test_cordic_pen_direction() {
+        for angle in [0.0f32, 0.5, -0.5, 1.2, 1.5] {
+            let (c, s) = cos_sin(to_fixed(angle));
+            assert!((to_f32(c) - angle.cos()).abs() < 1e-4, "cos {angle}");
+            assert!((to_f32(s) - angle.sin()).abs() < 1e-4, "sin {angle}");
+        }
+        // The pen line at θ + π is the same line.
+        assert_eq!(cos_sin(to_fixed(0.5)), cos_sin(to_fixed(0.5) + PI));
+    }
+
+    #[test]
+    fn test_fixed_parametric_matches_float() {
+        for params in [
+            MetaFontParams::sans_regular(),
+            MetaFontParams::serif_italic(),
+        ] {
+            let gen = GlyphGenerator::new(&params);
+            for ch in ['A', 'g', 'O', 'あ', '明'] {
+                let skeleton = skeleton_of(&gen, ch);
+                let float = gen.render(&skeleton);
+                let fixed = gen.render_fixed(&skeleton);
+                assert!(max_diff(&float.sdf, &fixed.sdf) < 1e-3, "{ch}");
+                assert!((float.metrics.advance - fixed.metrics.advance).abs() < 1e-3);
+                assert!((float.sdf.bbox_min.x - fixed.sdf.bbox_min.x).abs() < 1e-4);
+                assert!((float.sdf.bbox_max.y - fixed.sdf.bbox_max.y).abs() < 1e-4);
+                assert_eq!(fixed.provenance, float.provenance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_outline_matches_float() {
+        for cmds in [nested_squares(), curves()] {
+            for fill in [FillRule::NonZero, FillRule::EvenOdd] {
+                let float = font_render::rasterize_outline(&cmds, 1.0, fill);
+                let fixed = rasterize_outline(&cmds, 1.0, fill);
+                for (i, (a, b)) in float.data.iter().zip(&fixed.data).enumerate() {
+                    assert!((a.abs() - b.abs()).abs() < 1e-3);
+                    if a.abs() > 2e-3 {
+                        assert_eq!(a < &0.0, b < &0.0, "sample {i}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_tiles_lie_on_the_grid() {
+        let on_grid = |v: f32| to_f32(to_fixed(v)) == v;
+        let gen = GlyphGenerator::new(&MetaFontParams::serif_italic());
+        let tiles = [
+            gen.render_fixed(&skeleton_of(&gen, 'g')).sdf,
+            rasterize_outline(&curves(), 0.61, FillRule::NonZero),
+        ];
+        for sdf in &tiles {
+            assert!(sdf.data.iter().all(|&d| on_grid(d)));
+            assert!([sdf.advance, sdf.lsb, sdf.bbox_min.x, sdf.bbox_max.y]
+                .into_iter()
+                .all(on_grid));
+        }
+    }
+
+    #[test]
+    fn test_fixed_empty_skeleton() {
+        let gen = GlyphGenerator::new(&MetaFontParams::sans_regular());
+        let result = gen.render_fixed(&GlyphSkeleton::empty());
+        assert!(result.sdf.data.iter().all(|&d| d == 1.0));
+        assert_eq!(result.metrics, GlyphMetrics::blank(0.5));
+        let space = rasterize_outline(&[], 0.25, FillRule::NonZero);
+        assert!(space.data.iter().all(|&d| d > 0.0));
+    }
+
+    #[test]
+    fn test_fixed_covers_parametric_sources() {
+        let params = MetaFontParams::sans_regular();
+        for ch in ['Ａ', 'ｱ', 'ᴀ', '⁴', '、', '한'] {
+            let sdf = generate(ch, &params).expect("fixed-point tile");
+            assert!(sdf.data.iter().any(|&d| d < 0.0), "{ch}");
+        }
+        assert!(generate('─', &params).is_none());
+    }
+
+    // Golden hashes: these tiles must hash the same on every target. A
+    // change here means lockstep clients on different versions disagree.
+
+    #[test]
+    fn test_golden_parametric_hashes() {
+        let cases = [
+            (MetaFontParams::sans_regular(), 'A', 0xf266_01ba_0426_22e3),
+            (MetaFontParams::sans_regular(), 'g', 0x7747_024b_99d9_c9be),
+            (MetaFontParams::serif_italic(), 'R', 0x2929_8311_e95f_bfda),
+            (MetaFontParams::sans_bold(), 'あ', 0x4d49_41e9_9300_675f),
+            (MetaFontParams::serif_regular(), '明', 0x8a4d_8547_0bc1_e628),
+        ];
+        for (params, ch, hash) in cases {
+            let gen = GlyphGenerator::new(&params);
+            let sdf = gen.generate_fixed(&skeleton_of(&gen, ch));
+            assert_eq!(sdf.content_hash(), hash, "{ch}");
+        }
+    }
+
+    #[test]
+    fn test_golden_outline_hashes() {
+        let cases = [
+            (nested_squares(), FillRule::NonZero, 0x2c31_6d3b_00a3_9e51),
+            (nested_squares(), FillRule::EvenOdd, 0x5236_e6eb_79d9_7351),
+            (curves(), FillRule::NonZero, 0xfe8e_ccb2_351c_9805),
+            (curves(), FillRule::EvenOdd, 0xfe8e_ccb2_351c_9805),
+        ];
+        for (cmds, fill, hash) in cases {
+            assert_eq!(rasterize_outline(&cmds, 0.75, fill).content_hash(), hash);
+        }
+    }
+
+    #[cfg(feature = "outlines-kana")]
+    #[test]
+    fn test_golden_embedded_outline_hash() {
+        let result = render('あ', &MetaFontParams::sans_regular()).expect("outline");
+        assert_eq!(result.provenance, crate::glyph::GlyphProvenance::Outline);
+        assert_eq!(result.sdf.content_hash(), 0x5014_9f54_c53e_4fd8);
+        let styled = crate::game::style_glyph(&result.sdf, &crate::game::GameTextStyle::outlined());
+        assert_eq!(styled.content_hash, 0x8820_1fb9_60ff_6465);
+    }
+}
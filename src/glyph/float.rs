@@ -0,0 +1,33 @@
+//! `f32` rounding without libm.
+//!
+//! `f32::floor` and friends need `std`; grid fitting and the box-drawing
+//! shade lattice use these instead so they work in `no_std` builds. Inputs
+//! are em or pixel values well inside the `i32` range.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+/// Largest integer not above `x`.
+pub(crate) fn floor(x: f32) -> f32 {
+    let i = x as i32 as f32;
+    if i > x {
+        i - 1.0
+    } else {
+        i
+    }
+}
+
+/// Smallest integer not below `x`.
+pub(crate) fn ceil(x: f32) -> f32 {
+    let i = x as i32 as f32;
+    if i < x {
+        i + 1.0
+    } else {
+        i
+    }
+}
+
+/// Nearest integer, halves rounded up.
+pub(crate) fn round(x: f32) -> f32 {
+    floor(x + 0.5)
+}
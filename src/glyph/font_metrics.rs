@@ -0,0 +1,254 @@
+//! Font-wide vertical metrics — ascent, descent, decorations and the CJK
+//! ideographic em box.
+//!
+//! Metrics are measured from the glyphs that will actually be drawn: the
+//! reference characters below are resolved through the provider chain with
+//! the active [`GlyphSourcePolicy`], so an outline-first policy reports the
+//! embedded outline table and a parametric policy reports the skeletons
+//! under the current pen. Any reference the chain cannot supply falls back
+//! to the corresponding [`MetaFontParams`] field.
+//!
+//! Conventions (em units, baseline at y = 0): `descent` is positive below
+//! the baseline like `MetaFontParams::descender`; decoration positions are
+//! the centre of the stroke, negative below the baseline.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use crate::glyph::dispatcher::GlyphSourcePolicy;
+use crate::glyph::provider::{self, GlyphProvider};
+use crate::glyph::GlyphMetrics;
+use crate::param::MetaFontParams;
+use crate::stroke::Point2;
+
+/// Letters whose ink defines the ascent.
+const ASCENDER_REFS: &str = "bdfhklH";
+/// Letters whose ink defines the descent.
+const DESCENDER_REFS: &str = "gjpqy";
+/// Flat-topped capitals for the cap height.
+const CAP_REFS: &str = "HIE";
+/// Flat-topped lowercase for the x-height.
+const X_REFS: &str = "xzv";
+/// Full-frame ideographs whose ink defines the ideographic character face.
+const IDEO_REFS: &str = "国回田口";
+
+/// Font-wide vertical metrics (em units).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontMetrics {
+    /// Height above the baseline that lines must reserve
+    pub ascent: f32,
+    /// Depth below the baseline that lines must reserve (positive)
+    pub descent: f32,
+    /// Extra leading between lines. Derived metrics carry none; scale with
+    /// `TextShaper::set_line_height` or set it explicitly.
+    pub line_gap: f32,
+    /// Height of flat capitals
+    pub cap_height: f32,
+    /// Height of flat lowercase letters
+    pub x_height: f32,
+    /// Centre of the underline stroke (negative = below the baseline)
+    pub underline_position: f32,
+    /// Underline stroke thickness
+    pub underline_thickness: f32,
+    /// Centre of the strikeout stroke
+    pub strikeout_position: f32,
+    /// Strikeout stroke thickness
+    pub strikeout_thickness: f32,
+    /// Ideographic em box: one em tall, one ideograph advance wide, centred
+    /// vertically on the character face
+    pub ideo_min: Point2,
+    pub ideo_max: Point2,
+    /// Ideographic character face (ICF): ink extent of full-frame ideographs
+    pub icf_min: Point2,
+    pub icf_max: Point2,
+}
+
+impl FontMetrics {
+    /// Metrics for the built-in glyph sources under `policy`.
+    #[must_use]
+    pub fn new(params: &MetaFontParams, policy: &GlyphSourcePolicy) -> Self {
+        Self::measure(params, |ch| {
+            provider::metrics_from(provider::BUILTIN.iter().copied(), ch, params, policy)
+        })
+    }
+
+    /// Metrics from `MetaFontParams` alone, ignoring glyph ink.
+    #[must_use]
+    pub fn from_params(params: &MetaFontParams) -> Self {
+        Self::measure(params, |_| None)
+    }
+
+    /// Metrics from the glyph metrics `lookup` returns for the reference
+    /// characters, falling back to `params` where it returns `None`.
+    #[must_use]
+    pub fn measure(
+        params: &MetaFontParams,
+        mut lookup: impl FnMut(char) -> Option<GlyphMetrics>,
+    ) -> Self {
+        let mut ink = |refs: &str| ink_union(refs, &mut lookup);
+        let top = |b: Option<(Point2, Point2)>| b.map(|(_, hi)| hi.y);
+
+        let cap_height = top(ink(CAP_REFS)).unwrap_or(params.cap_height);
+        let x_ink = ink(X_REFS);
+        let x_height = top(x_ink).unwrap_or(params.x_height);
+        // Lowest baseline ink (round pen caps dip below zero).
+        let baseline = x_ink.map_or(0.0, |(lo, _)| lo.y.min(0.0));
+        let ascent = top(ink(ASCENDER_REFS)).map_or(params.ascender, |y| y.max(params.ascender));
+        let descent =
+            ink(DESCENDER_REFS).map_or(params.descender, |(lo, _)| (-lo.y).max(params.descender));
+
+        // Decorations follow the underscore and hyphen when they have ink;
+        // the underline is kept a thickness and a half clear of the
+        // baseline ink, so ink skipping only breaks it at descenders.
+        let thickness = params.stroke_half_width();
+        let (underline_position, underline_thickness) = match ink("_") {
+            Some((lo, hi)) => {
+                let t = hi.y - lo.y;
+                (((lo.y + hi.y) * 0.5).min(baseline - 2.0 * t), t)
+            }
+            None => (-params.descender * 0.5, thickness),
+        };
+        let (strikeout_position, strikeout_thickness) = match ink("-") {
+            Some((lo, hi)) => ((lo.y + hi.y) * 0.5, hi.y - lo.y),
+            None => (x_height * 0.5, thickness),
+        };
+
+        let ideo_advance = IDEO_REFS
+            .chars()
+            .find_map(&mut lookup)
+            .map_or(1.0, |m| m.advance);
+        let (icf_min, icf_max) = ink_union(IDEO_REFS, &mut lookup).unwrap_or((
+            Point2::new(0.0, -params.descender),
+            Point2::new(ideo_advance, 1.0 - params.descender),
+        ));
+        let centre = (icf_min.y + icf_max.y) * 0.5;
+
+        Self {
+            ascent,
+            descent,
+            line_gap: 0.0,
+            cap_height,
+            x_height,
+            underline_position,
+            underline_thickness,
+            strikeout_position,
+            strikeout_thickness,
+            ideo_min: Point2::new(0.0, centre - 0.5),
+            ideo_max: Point2::new(ideo_advance, centre + 0.5),
+            icf_min,
+            icf_max,
+        }
+    }
+
+    /// Baseline-to-baseline distance: `ascent + descent + line_gap`.
+    #[must_use]
+    pub fn line_height(&self) -> f32 {
+        self.ascent + self.descent + self.line_gap
+    }
+
+    /// Ascent and descent widened to contain the ideographic em box, for
+    /// lines that mix Latin and CJK.
+    #[must_use]
+    pub fn with_ideographic_extent(mut self) -> Self {
+        self.ascent = self.ascent.max(self.ideo_max.y);
+        self.descent = self.descent.max(-self.ideo_min.y);
+        self
+    }
+}
+
+/// Union of the ink boxes of `refs`.
+fn ink_union(
+    refs: &str,
+    lookup: &mut impl FnMut(char) -> Option<GlyphMetrics>,
+) -> Option<(Point2, Point2)> {
+    refs.chars()
+        .filter_map(lookup)
+        .filter(GlyphMetrics::has_ink)
+        .map(|m| (m.ink_min, m.ink_max))
+        .reduce(|(lo, hi), (a, b)| {
+            (
+                Point2::new(lo.x.min(a.x), lo.y.min(a.y)),
+                Point2::new(hi.x.max(b.x), hi.y.max(b.y)),
+            )
+        })
+}
+
+/// Metrics for an explicit provider list (runtime fonts in front of the
+/// built-ins), see [`FontMetrics::measure`].
+pub(crate) fn from_providers<'a>(
+    providers: impl Iterator<Item = &'a dyn GlyphProvider> + Clone,
+    params: &MetaFontParams,
+    policy: &GlyphSourcePolicy,
+) -> FontMetrics {
+    FontMetrics::measure(params, |ch| {
+        provider::metrics_from(providers.clone(), ch, params, policy)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_only_matches_fields() {
+        let params = MetaFontParams::sans_regular();
+        let m = FontMetrics::from_params(&params);
+        assert_eq!(m.ascent, params.ascender);
+        assert_eq!(m.descent, params.descender);
+        assert_eq!(m.cap_height, params.cap_height);
+        assert_eq!(m.x_height, params.x_height);
+        assert!(m.underline_position < 0.0);
+        assert!((m.line_height() - (params.ascender + params.descender)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parametric_metrics_follow_skeletons() {
+        let params = MetaFontParams::sans_regular();
+        let m = FontMetrics::new(&params, &GlyphSourcePolicy::parametric_only());
+        let hw = params.stroke_half_width();
+        // Flat tops: cap height and x-height sit half a pen above the stems.
+        assert!((m.cap_height - (params.cap_height + hw)).abs() < 0.02);
+        assert!(m.x_height < m.cap_height && m.x_height > params.x_height - 0.02);
+        assert!(m.ascent >= m.cap_height && m.descent > 0.0);
+        assert!(m.underline_thickness > 0.0);
+        assert!(m.underline_position + m.underline_thickness * 0.5 < 0.0);
+        // Round pen caps dip below the baseline; the underline clears them.
+        assert!(m.underline_position + m.underline_thickness * 1.5 < -hw);
+        assert!(m.strikeout_position > 0.0 && m.strikeout_position < m.x_height);
+        // Heavier pens push the ink out.
+        let heavy = MetaFontParams {
+            weight: 1.0,
+            ..params
+        };
+        let h = FontMetrics::new(&heavy, &GlyphSourcePolicy::parametric_only());
+        assert!(h.cap_height > m.cap_height);
+        assert!(h.underline_thickness > m.underline_thickness);
+    }
+
+    #[test]
+    fn ideographic_box_contains_face() {
+        let params = MetaFontParams::sans_regular();
+        for policy in [
+            GlyphSourcePolicy::default(),
+            GlyphSourcePolicy::parametric_only(),
+        ] {
+            let m = FontMetrics::new(&params, &policy);
+            assert!((m.ideo_max.y - m.ideo_min.y - 1.0).abs() < 1e-6);
+            assert!(m.icf_min.x >= m.ideo_min.x && m.icf_max.x <= m.ideo_max.x);
+            assert!(m.icf_min.y >= m.ideo_min.y && m.icf_max.y <= m.ideo_max.y);
+            let mixed = m.with_ideographic_extent();
+            assert!(mixed.ascent >= m.ideo_max.y && mixed.line_height() >= m.line_height());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn outline_metrics_come_from_table() {
+        let params = MetaFontParams::sans_regular();
+        let outline = FontMetrics::new(&params, &GlyphSourcePolicy::outline_only());
+        let parametric = FontMetrics::new(&params, &GlyphSourcePolicy::parametric_only());
+        assert_ne!(outline.cap_height, parametric.cap_height);
+        let h = crate::glyph::font_render::styled_outline('H', &params).unwrap();
+        assert!((outline.cap_height - h.metrics().ink_max.y).abs() < 0.01);
+    }
+}
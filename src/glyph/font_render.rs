@@ -1,15 +1,55 @@
 //! BIZ UDPGothic outline → SDF rasterizer.
 //!
-//! Rasterizes glyphs from em-normalized outline data (imported from
-//! BIZ UDPGothic via tools/import-font) into a `GLYPH_SDF_SIZE`² signed
-//! distance field. Uses even-odd fill rule for inside test and nearest-edge
-//! distance for magnitude. Signed: negative inside, positive outside.
+//! Rasterizes glyphs from outline data (imported from BIZ UDPGothic via
+//! tools/import-font) into a `GLYPH_SDF_SIZE`² signed distance field. Uses
+//! a selectable [`FillRule`] (non-zero by default) for the inside test and
+//! the exact distance to the nearest line/curve for magnitude. Signed:
+//! negative inside, positive outside.
 //!
-//! Two weight tables are consulted:
-//!   - FONT_OUTLINES_REGULAR — used when `params.weight < 0.6`
-//!   - FONT_OUTLINES_BOLD    — used when `params.weight >= 0.6`
+//! Two weight masters are embedded, Regular (`weight` 0.45) and Bold
+//! (`weight` 0.75). [`MetaFontParams`] are applied continuously (see
+//! [`styled_outline`]):
+//!   - `weight` — masters are interpolated where their outlines are
+//!     compatible; otherwise the nearer master is emboldened/thinned by
+//!     offsetting the SDF
+//!   - `width` — horizontal scale of outline and advance
+//!   - `slant` — shear about the baseline
 //!
-//! No runtime TTF loading — the outline data is compiled in as Rust const.
+//! `contrast`, `roundness` and `serif` only affect the parametric generators.
+//!
+//! # Coverage features
+//!
+//! The tables are split into [`OutlineGroup`]s, each behind a cargo feature
+//! (all on by default):
+//!
+//! | Feature | Group | Contents |
+//! |---------|-------|----------|
+//! | `outlines-latin` | [`OutlineGroup::Latin`] | ASCII and other non-CJK symbols |
+//! | `outlines-kana` | [`OutlineGroup::Kana`] | kana, CJK punctuation, fullwidth forms |
+//! | `outlines-kanji` | [`OutlineGroup::Kanji`] | CJK ideographs and radicals |
+//! | `outlines-bold` | — | Bold tables for the enabled groups |
+//!
+//! Characters whose group is compiled out are simply not found by
+//! [`lookup`], so `dispatcher::generate` falls back to the parametric
+//! generators. Without `outlines-bold`, bold requests use the Regular table.
+//!
+//! # Embedded encoding
+//!
+//! Each table is a sorted index of `(codepoint, offset, advance)` into a
+//! shared byte stream (`outlines/*.bin`). A glyph record is:
+//!
+//!   1. varint `n` — number of path commands
+//!   2. `ceil(n / 4)` bytes of 2-bit opcodes, LSB first
+//!      (`0` move, `1` line, `2` quad, `3` cubic)
+//!   3. for every point of every command (1 / 1 / 2 / 3 points): zigzag
+//!      varint `dx`, `dy` in font units, relative to the previous point
+//!
+//! Coordinates are quantized to [`OUTLINE_UNITS_PER_EM`] (the source font's
+//! em, so the quantization is lossless for TrueType sources). Records are
+//! only decoded when a glyph is looked up; see [`EncodedOutline`].
+//!
+//! Runtime TrueType/OpenType loading lives in `glyph::sfnt` (feature
+//! `sfnt`) and reuses [`rasterize_outline`].
 //!
 //! License: outline data is derived from BIZ UDPGothic (Morisawa, SIL OFL 1.1).
 //! See `tools/import-font/fonts/OFL.txt` for terms.
@@ -17,81 +57,428 @@
 //! Author: Moroya Sakamoto
 
 extern crate alloc;
+use alloc::vec::Vec;
 
-use super::{GlyphSdf, GLYPH_SDF_SIZE};
+use super::outline::{self, FillRule, PathCmd, Segment};
+use super::simd;
+use super::{GlyphMetrics, GlyphSdf, GLYPH_SDF_SIZE};
 use crate::param::MetaFontParams;
 use crate::stroke::Point2;
 
-include!("font_outlines_data.rs");
+/// Outline table entry: (codepoint, byte offset into the table's stream,
+/// advance in font units).
+pub type OutlineEntry = (char, u32, u16);
+
+mod data {
+    // Unused when every `outlines-*` feature is off.
+    #[allow(unused_imports)]
+    use super::OutlineEntry;
+
+    include!("font_outlines_data.rs");
+}
+pub use data::OUTLINE_UNITS_PER_EM;
+
+/// Coverage group of the embedded outline tables; each is compiled in only
+/// when its cargo feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineGroup {
+    /// Everything outside the CJK blocks (feature `outlines-latin`).
+    Latin,
+    /// Kana, CJK symbols & punctuation, fullwidth forms (feature `outlines-kana`).
+    Kana,
+    /// CJK ideographs and radicals (feature `outlines-kanji`).
+    Kanji,
+}
+
+impl OutlineGroup {
+    /// Group a character's outline is stored in.
+    #[must_use]
+    pub const fn of(ch: char) -> Self {
+        match ch as u32 {
+            0x2E80..=0x2FDF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Self::Kanji,
+            0x3000..=0x30FF | 0xFF00..=0xFFEF => Self::Kana,
+            _ => Self::Latin,
+        }
+    }
+
+    /// True iff this group's Regular table is compiled in.
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        match self {
+            Self::Latin => cfg!(feature = "outlines-latin"),
+            Self::Kana => cfg!(feature = "outlines-kana"),
+            Self::Kanji => cfg!(feature = "outlines-kanji"),
+        }
+    }
+}
+
+/// Embedded `(index, stream)` for `group` at the requested weight, if
+/// compiled in. Without `outlines-bold`, bold requests get the Regular table.
+#[allow(unreachable_patterns)]
+fn table(group: OutlineGroup, bold: bool) -> Option<(&'static [OutlineEntry], &'static [u8])> {
+    match (group, bold) {
+        #[cfg(all(feature = "outlines-latin", feature = "outlines-bold"))]
+        (OutlineGroup::Latin, true) => Some((
+            data::FONT_OUTLINES_BOLD_LATIN,
+            data::FONT_OUTLINES_BOLD_LATIN_STREAM,
+        )),
+        #[cfg(feature = "outlines-latin")]
+        (OutlineGroup::Latin, _) => Some((
+            data::FONT_OUTLINES_REGULAR_LATIN,
+            data::FONT_OUTLINES_REGULAR_LATIN_STREAM,
+        )),
+        #[cfg(all(feature = "outlines-kana", feature = "outlines-bold"))]
+        (OutlineGroup::Kana, true) => Some((
+            data::FONT_OUTLINES_BOLD_KANA,
+            data::FONT_OUTLINES_BOLD_KANA_STREAM,
+        )),
+        #[cfg(feature = "outlines-kana")]
+        (OutlineGroup::Kana, _) => Some((
+            data::FONT_OUTLINES_REGULAR_KANA,
+            data::FONT_OUTLINES_REGULAR_KANA_STREAM,
+        )),
+        #[cfg(all(feature = "outlines-kanji", feature = "outlines-bold"))]
+        (OutlineGroup::Kanji, true) => Some((
+            data::FONT_OUTLINES_BOLD_KANJI,
+            data::FONT_OUTLINES_BOLD_KANJI_STREAM,
+        )),
+        #[cfg(feature = "outlines-kanji")]
+        (OutlineGroup::Kanji, _) => Some((
+            data::FONT_OUTLINES_REGULAR_KANJI,
+            data::FONT_OUTLINES_REGULAR_KANJI_STREAM,
+        )),
+        _ => None,
+    }
+}
 
 /// Tile em box — fixed across all glyphs so proportional sizes are consistent.
 /// The tile spans em [`TILE_EM_LEFT`, `TILE_EM_RIGHT`] × [`TILE_EM_BOTTOM`, `TILE_EM_TOP`].
 /// Baseline is at em y=0 (about 20% up from tile bottom).
-const TILE_EM_LEFT: f32 = 0.0;
-const TILE_EM_RIGHT: f32 = 1.0;
-const TILE_EM_BOTTOM: f32 = -0.2;
-const TILE_EM_TOP: f32 = 0.8;
+pub(crate) const TILE_EM_LEFT: f32 = 0.0;
+pub(crate) const TILE_EM_RIGHT: f32 = 1.0;
+pub(crate) const TILE_EM_BOTTOM: f32 = -0.2;
+pub(crate) const TILE_EM_TOP: f32 = 0.8;
+
+/// `MetaFontParams::weight` the Regular master is drawn at (`sans_regular`).
+const REGULAR_MASTER_WEIGHT: f32 = 0.45;
 
-/// Weight threshold: strokes >= 0.6 use the Bold table.
+/// `MetaFontParams::weight` the Bold master is drawn at (`sans_bold`).
+const BOLD_MASTER_WEIGHT: f32 = 0.75;
+
+/// Weight threshold: when the masters cannot be interpolated, strokes
+/// >= 0.6 start from the Bold master.
 const BOLD_WEIGHT_THRESHOLD: f32 = 0.6;
 
-/// Look up outline + advance for a character. Returns `None` if not present.
+/// SDF offset (em, per side) per unit of weight away from the chosen
+/// master. Calibrated so that Regular emboldened to 0.75 roughly matches
+/// the Bold master's stems (~0.024 em thicker per side).
+const EMBOLDEN_EM_PER_WEIGHT: f32 = 0.08;
+
+/// Limits on the SDF offset: thinning past ~0.02 em erases Regular's
+/// hairlines, emboldening past ~0.05 em fills counters.
+const MIN_EMBOLDEN_EM: f32 = -0.02;
+const MAX_EMBOLDEN_EM: f32 = 0.05;
+
+/// Accepted range for `MetaFontParams::width` on outline glyphs.
+const MIN_WIDTH_SCALE: f32 = 0.25;
+const MAX_WIDTH_SCALE: f32 = 2.0;
+
+/// Look up outline + advance (em) for a character. Returns `None` if not
+/// present or if its [`OutlineGroup`] is compiled out. The outline is
+/// returned still encoded.
 #[must_use]
-pub fn lookup(ch: char, bold: bool) -> Option<(&'static [&'static [(f32, f32)]], f32)> {
-    let table = if bold {
-        FONT_OUTLINES_BOLD
-    } else {
-        FONT_OUTLINES_REGULAR
-    };
-    table
-        .binary_search_by_key(&ch, |(c, _, _)| *c)
-        .ok()
-        .map(|i| (table[i].1, table[i].2))
+pub fn lookup(ch: char, bold: bool) -> Option<(EncodedOutline, f32)> {
+    let (table, stream) = table(OutlineGroup::of(ch), bold)?;
+    let i = table.binary_search_by_key(&ch, |(c, _, _)| *c).ok()?;
+    let (_, offset, advance) = table[i];
+    let bytes = stream.get(offset as usize..)?;
+    Some((
+        EncodedOutline { bytes },
+        f32::from(advance) / f32::from(OUTLINE_UNITS_PER_EM),
+    ))
+}
+
+/// Handle to one glyph record in an embedded outline stream.
+///
+/// Cheap to copy; nothing is decoded until [`EncodedOutline::commands`] or
+/// [`EncodedOutline::decode`] is called.
+#[derive(Debug, Clone, Copy)]
+pub struct EncodedOutline {
+    bytes: &'static [u8],
+}
+
+impl EncodedOutline {
+    /// True iff the glyph has no contours (space and other blank glyphs).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.first().is_none_or(|&b| b == 0)
+    }
+
+    /// Iterate over the em-normalized path commands.
+    #[must_use]
+    pub fn commands(&self) -> OutlineDecoder {
+        OutlineDecoder::new(self.bytes)
+    }
+
+    /// Decode all path commands.
+    #[must_use]
+    pub fn decode(&self) -> Vec<PathCmd> {
+        self.commands().collect()
+    }
+}
+
+/// Streaming decoder for one glyph record. Stops early on malformed input.
+#[derive(Debug, Clone)]
+pub struct OutlineDecoder {
+    bytes: &'static [u8],
+    ops: usize,
+    pos: usize,
+    index: usize,
+    count: usize,
+    x: i32,
+    y: i32,
 }
 
-/// True iff an outline exists for the given character in the Regular table.
+impl OutlineDecoder {
+    fn new(bytes: &'static [u8]) -> Self {
+        let mut pos = 0;
+        let count = read_varint(bytes, &mut pos).unwrap_or(0) as usize;
+        let ops = pos;
+        Self {
+            bytes,
+            ops,
+            pos: ops + count.div_ceil(4),
+            index: 0,
+            count,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    fn point(&mut self) -> Option<(f32, f32)> {
+        let dx = unzigzag(read_varint(self.bytes, &mut self.pos)?);
+        let dy = unzigzag(read_varint(self.bytes, &mut self.pos)?);
+        self.x += dx;
+        self.y += dy;
+        let inv = 1.0 / f32::from(OUTLINE_UNITS_PER_EM);
+        Some((self.x as f32 * inv, self.y as f32 * inv))
+    }
+
+    fn read_cmd(&mut self) -> Option<PathCmd> {
+        let op = (self.bytes.get(self.ops + self.index / 4)? >> ((self.index % 4) * 2)) & 3;
+        self.index += 1;
+        Some(match op {
+            0 => {
+                let (x, y) = self.point()?;
+                PathCmd::MoveTo(x, y)
+            }
+            1 => {
+                let (x, y) = self.point()?;
+                PathCmd::LineTo(x, y)
+            }
+            2 => {
+                let (x1, y1) = self.point()?;
+                let (x, y) = self.point()?;
+                PathCmd::QuadTo(x1, y1, x, y)
+            }
+            _ => {
+                let (x1, y1) = self.point()?;
+                let (x2, y2) = self.point()?;
+                let (x, y) = self.point()?;
+                PathCmd::CubicTo(x1, y1, x2, y2, x, y)
+            }
+        })
+    }
+}
+
+impl Iterator for OutlineDecoder {
+    type Item = PathCmd;
+
+    fn next(&mut self) -> Option<PathCmd> {
+        if self.index >= self.count {
+            return None;
+        }
+        let cmd = self.read_cmd();
+        if cmd.is_none() {
+            // Malformed record: stop instead of reading past it.
+            self.count = 0;
+        }
+        cmd
+    }
+}
+
+/// LEB128 unsigned varint.
+#[inline]
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut v = 0u32;
+    for shift in (0..35).step_by(7) {
+        let b = *bytes.get(*pos)?;
+        *pos += 1;
+        v |= u32::from(b & 0x7f) << shift;
+        if b & 0x80 == 0 {
+            return Some(v);
+        }
+    }
+    None
+}
+
+#[inline]
+const fn unzigzag(v: u32) -> i32 {
+    (v >> 1) as i32 ^ -((v & 1) as i32)
+}
+
+/// True iff an outline exists for the given character in the compiled-in
+/// Regular tables.
 #[must_use]
 pub fn has(ch: char) -> bool {
-    FONT_OUTLINES_REGULAR
-        .binary_search_by_key(&ch, |(c, _, _)| *c)
-        .is_ok()
+    table(OutlineGroup::of(ch), false)
+        .is_some_and(|(t, _)| t.binary_search_by_key(&ch, |(c, _, _)| *c).is_ok())
 }
 
 /// Rasterize the outline of `ch` into an SDF for the given font parameters.
 ///
-/// Returns `None` if no outline data is present for the character. Uses the
-/// Bold table when `params.weight >= 0.6`, otherwise the Regular table.
+/// Returns `None` if no outline data is present for the character. Weight,
+/// width and slant are applied as described in [`styled_outline`]. Filled
+/// with the non-zero winding rule.
 #[must_use]
 pub fn rasterize(ch: char, params: &MetaFontParams) -> Option<GlyphSdf> {
-    let bold = params.weight >= BOLD_WEIGHT_THRESHOLD;
-    let (contours, advance) = lookup(ch, bold)?;
-    if contours.is_empty() {
-        // Space or non-drawing glyph: carry the advance only.
-        let mut sdf = GlyphSdf::empty();
-        sdf.advance = advance;
-        return Some(sdf);
-    }
-    Some(rasterize_from_contours(contours, advance))
+    rasterize_with_fill(ch, params, FillRule::NonZero)
+}
+
+/// [`rasterize`] with an explicit fill rule.
+#[must_use]
+pub fn rasterize_with_fill(ch: char, params: &MetaFontParams, fill: FillRule) -> Option<GlyphSdf> {
+    styled_outline(ch, params).map(|styled| styled.rasterize(fill))
+}
+
+/// An embedded outline with [`MetaFontParams`] applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledOutline {
+    /// Path commands after master interpolation, width scale and slant.
+    pub cmds: Vec<PathCmd>,
+    /// Advance (em) after width scale.
+    pub advance: f32,
+    /// Outward SDF offset (em) still to apply for weight; negative thins.
+    pub embolden: f32,
+}
+
+impl StyledOutline {
+    /// Rasterize with `fill`, applying [`StyledOutline::embolden`].
+    #[must_use]
+    pub fn rasterize(&self, fill: FillRule) -> GlyphSdf {
+        if self.cmds.is_empty() {
+            // Space or non-drawing glyph: carry the advance only.
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = self.advance;
+            return sdf;
+        }
+        let mut sdf = rasterize_outline(&self.cmds, self.advance, fill);
+        sdf.lsb = self.metrics().lsb;
+        if self.embolden != 0.0 {
+            for d in &mut sdf.data {
+                *d -= self.embolden;
+            }
+        }
+        sdf
+    }
+
+    /// Advance, side bearings and ink box, including the embolden offset.
+    #[must_use]
+    pub fn metrics(&self) -> GlyphMetrics {
+        outline_metrics(&self.cmds, self.advance, self.embolden)
+    }
+}
+
+/// Metrics of an em-normalized outline whose ink is grown by `embolden`.
+#[must_use]
+pub fn outline_metrics(cmds: &[PathCmd], advance: f32, embolden: f32) -> GlyphMetrics {
+    match outline::bounds(cmds) {
+        Some(((x0, y0), (x1, y1))) => GlyphMetrics::from_ink(
+            advance,
+            Point2::new(x0 - embolden, y0 - embolden),
+            Point2::new(x1 + embolden, y1 + embolden),
+        ),
+        None => GlyphMetrics::blank(advance),
+    }
+}
+
+/// Decode the outline of `ch` and apply `params`.
+///
+/// Weight: between the two masters, compatible outlines (same command
+/// sequence) are interpolated point-wise. Otherwise — incompatible masters,
+/// weights outside `[0.45, 0.75]`, or `outlines-bold` disabled — the nearer
+/// available master is used and the remaining difference becomes
+/// [`StyledOutline::embolden`], a clamped SDF offset. Width scales x about
+/// the pen origin; slant shears about the baseline like the parametric
+/// generators' `Point2::slant`.
+#[must_use]
+pub fn styled_outline(ch: char, params: &MetaFontParams) -> Option<StyledOutline> {
+    let weight = params.weight;
+    let (regular, regular_adv) = lookup(ch, false)?;
+    let bold = if cfg!(feature = "outlines-bold") {
+        lookup(ch, true)
+    } else {
+        None
+    };
+
+    let t = (weight - REGULAR_MASTER_WEIGHT) / (BOLD_MASTER_WEIGHT - REGULAR_MASTER_WEIGHT);
+    let interpolated = match bold {
+        Some((bold, bold_adv)) if t > 0.0 && t < 1.0 => {
+            outline::lerp(&regular.decode(), &bold.decode(), t)
+                .map(|cmds| (cmds, regular_adv + (bold_adv - regular_adv) * t, 0.0))
+        }
+        _ => None,
+    };
+    let (cmds, advance, embolden) = interpolated.unwrap_or_else(|| {
+        let (master, advance, master_weight) = match bold {
+            Some((bold, bold_adv)) if weight >= BOLD_WEIGHT_THRESHOLD => {
+                (bold, bold_adv, BOLD_MASTER_WEIGHT)
+            }
+            _ => (regular, regular_adv, REGULAR_MASTER_WEIGHT),
+        };
+        let embolden = ((weight - master_weight) * EMBOLDEN_EM_PER_WEIGHT)
+            .clamp(MIN_EMBOLDEN_EM, MAX_EMBOLDEN_EM);
+        (master.decode(), advance, embolden)
+    });
+
+    let width = params.width.clamp(MIN_WIDTH_SCALE, MAX_WIDTH_SCALE);
+    let slant = params.slant;
+    let cmds = if width == 1.0 && slant == 0.0 {
+        cmds
+    } else {
+        cmds.into_iter()
+            .map(|c| {
+                c.map_points(|x, y| {
+                    let p = Point2::new(x * width, y).slant(slant);
+                    (p.x, p.y)
+                })
+            })
+            .collect()
+    };
+    Some(StyledOutline {
+        cmds,
+        advance: advance * width,
+        embolden,
+    })
 }
 
-/// Rasterize outline contours (em-normalized) into a `GLYPH_SDF_SIZE`² SDF.
+/// Rasterize an em-normalized outline into a `GLYPH_SDF_SIZE`² SDF.
 ///
 /// Signed distance: negative inside the glyph, positive outside. Inside/outside
-/// is determined by the even-odd fill rule. Magnitude is the Euclidean distance
-/// to the nearest contour edge.
+/// is decided by `fill`; the magnitude is the exact Euclidean distance to the
+/// nearest point of the outline, quadratic and cubic segments included.
 ///
 /// This is the shared rasterization core used by both the embedded BIZ UDPGothic
-/// table (via [`rasterize`]) and external tools that parse arbitrary TTF outlines
-/// at build time (e.g. `extoria-website-sdf-v2/tools/gen-atlas-multi`).
+/// table (via [`rasterize`]) and runtime fonts (`glyph::sfnt`).
 ///
 /// # Coordinate convention
 ///
-/// Contours are em-normalized: `units_per_em -> 1.0`, TTF y-up preserved,
+/// Outlines are em-normalized: `units_per_em -> 1.0`, TTF y-up preserved,
 /// x in `[0, ~advance_em]`, y in `[-descent_em, ascent_em]`. Points inside
 /// the em box `[TILE_EM_LEFT, TILE_EM_RIGHT] × [TILE_EM_BOTTOM, TILE_EM_TOP]`
 /// (i.e. `[0, 1] × [-0.2, 0.8]`) get sampled into the output SDF.
 #[must_use]
-pub fn rasterize_from_contours(contours: &[&[(f32, f32)]], advance: f32) -> GlyphSdf {
+pub fn rasterize_outline(cmds: &[PathCmd], advance: f32, fill: FillRule) -> GlyphSdf {
     let mut sdf = GlyphSdf::empty();
     sdf.advance = advance;
     sdf.bbox_min = Point2::new(TILE_EM_LEFT, TILE_EM_BOTTOM);
@@ -102,116 +489,199 @@ pub fn rasterize_from_contours(contours: &[&[(f32, f32)]], advance: f32) -> Glyp
     let em_h = TILE_EM_TOP - TILE_EM_BOTTOM;
     let inv_size_1 = 1.0 / (size - 1) as f32;
 
+    let segments: Vec<Segment> = outline::segments(cmds).collect();
+    let bounds: Vec<_> = segments.iter().map(Segment::control_bounds).collect();
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let mut nearest_first: Vec<(f32, usize)> = Vec::with_capacity(segments.len());
+    let mut lines = simd::Lines::new();
+    let mut curves: Vec<(f32, usize)> = Vec::new();
+
     for py in 0..size {
-        for px in 0..size {
-            let u = px as f32 * inv_size_1;
-            let v = py as f32 * inv_size_1;
-            let em_x = TILE_EM_LEFT + u * em_w;
-            let em_y = TILE_EM_BOTTOM + v * em_h;
-
-            let inside = point_in_polygons(contours, em_x, em_y);
-            let dist_sq = nearest_edge_distance_sq(contours, em_x, em_y);
-            let dist = fast_sqrt(dist_sq);
-            sdf.data[py * size + px] = if inside { -dist } else { dist };
+        let em_y = TILE_EM_BOTTOM + py as f32 * inv_size_1 * em_h;
+
+        // Inside test: the scanline's crossings are found once per row and
+        // swept left to right, instead of re-bisecting every segment for
+        // every pixel.
+        crossings.clear();
+        for seg in &segments {
+            seg.scanline_crossings(em_y, |x, dir| crossings.push((x, dir)));
         }
-    }
-    sdf
-}
+        crossings.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        let mut winding: i32 = crossings.iter().map(|c| c.1).sum();
+        let mut passed = 0;
 
-/// Even-odd inside test across all contours.
-fn point_in_polygons(contours: &[&[(f32, f32)]], px: f32, py: f32) -> bool {
-    let mut inside = false;
-    for c in contours {
-        if c.len() < 3 {
-            continue;
+        // Distance: segments ordered by their vertical distance to the row,
+        // so the search for each pixel stops at the first one that cannot
+        // beat the best distance so far. Lines go to the SIMD kernel.
+        nearest_first.clear();
+        nearest_first.extend(bounds.iter().enumerate().map(|(i, &((_, y0), (_, y1)))| {
+            let dy = (y0 - em_y).max(em_y - y1).max(0.0);
+            (dy * dy, i)
+        }));
+        nearest_first.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        lines.clear();
+        curves.clear();
+        for &(bound, i) in &nearest_first {
+            match segments[i] {
+                Segment::Line(p0, p1) => lines.push(p0, p1, bound),
+                _ => curves.push((bound, i)),
+            }
         }
-        for w in c.windows(2) {
-            let (x0, y0) = w[0];
-            let (x1, y1) = w[1];
-            if (y0 > py) != (y1 > py) {
-                let dy = y1 - y0;
-                if dy.abs() > 1e-9 {
-                    let t = (py - y0) / dy;
-                    let x_cross = x0 + (x1 - x0) * t;
-                    if px < x_cross {
-                        inside = !inside;
-                    }
+        if !lines.is_empty() {
+            lines.finish();
+        }
+
+        for px in 0..size {
+            let em_x = TILE_EM_LEFT + px as f32 * inv_size_1 * em_w;
+
+            while passed < crossings.len() && crossings[passed].0 <= em_x {
+                winding -= crossings[passed].1;
+                passed += 1;
+            }
+            let inside = match fill {
+                FillRule::NonZero => winding != 0,
+                FillRule::EvenOdd => winding & 1 != 0,
+            };
+
+            let mut best = lines.nearest_sq(em_x, em_y, f32::MAX);
+            for &(bound, i) in &curves {
+                if bound >= best {
+                    break;
                 }
+                let ((x0, _), (x1, _)) = bounds[i];
+                let dx = (x0 - em_x).max(em_x - x1).max(0.0);
+                if dx * dx + bound >= best {
+                    continue;
+                }
+                best = best.min(segments[i].distance_sq(em_x, em_y));
             }
+            let dist = fast_sqrt(best);
+            sdf.data[py * size + px] = if inside { -dist } else { dist };
         }
     }
-    inside
+    sdf
 }
 
-/// Squared distance from point to nearest contour edge.
-fn nearest_edge_distance_sq(contours: &[&[(f32, f32)]], px: f32, py: f32) -> f32 {
-    let mut best_sq = f32::MAX;
+/// Rasterize closed polylines (em-normalized) into a `GLYPH_SDF_SIZE`² SDF
+/// with the even-odd fill rule.
+///
+/// Kept for callers that flatten outlines themselves (e.g.
+/// `extoria-website-sdf-v2/tools/gen-atlas-multi`); new code should build
+/// [`PathCmd`]s and call [`rasterize_outline`].
+#[must_use]
+pub fn rasterize_from_contours(contours: &[&[(f32, f32)]], advance: f32) -> GlyphSdf {
+    let mut cmds = Vec::new();
     for c in contours {
-        for w in c.windows(2) {
-            let (x0, y0) = w[0];
-            let (x1, y1) = w[1];
-            let d_sq = point_seg_dist_sq(px, py, x0, y0, x1, y1);
-            if d_sq < best_sq {
-                best_sq = d_sq;
-            }
-        }
+        let Some((&(x, y), rest)) = c.split_first() else {
+            continue;
+        };
+        cmds.push(PathCmd::MoveTo(x, y));
+        cmds.extend(rest.iter().map(|&(x, y)| PathCmd::LineTo(x, y)));
     }
-    best_sq
-}
-
-#[inline]
-fn point_seg_dist_sq(px: f32, py: f32, x0: f32, y0: f32, x1: f32, y1: f32) -> f32 {
-    let dx = x1 - x0;
-    let dy = y1 - y0;
-    let len_sq = dx * dx + dy * dy;
-    let t = if len_sq < 1e-9 {
-        0.0
-    } else {
-        (((px - x0) * dx + (py - y0) * dy) / len_sq).clamp(0.0, 1.0)
-    };
-    let nx = x0 + t * dx;
-    let ny = y0 + t * dy;
-    let ex = px - nx;
-    let ey = py - ny;
-    ex * ex + ey * ey
+    rasterize_outline(&cmds, advance, FillRule::EvenOdd)
 }
 
 /// Quake III fast inverse square root, then multiply by `x` to get `sqrt(x)`.
+/// The SSE2 kernels in [`simd`] run the same sequence, so scalar and vector
+/// distances agree bit for bit.
 #[inline(always)]
-fn fast_sqrt(x: f32) -> f32 {
+#[must_use]
+pub fn fast_sqrt(x: f32) -> f32 {
     if x <= 0.0 {
         return 0.0;
     }
     let half = 0.5 * x;
     let i = f32::to_bits(x);
+    // Quake III Arena magic constant for fast inverse square root (1/√x).
+    // Attributed to Greg Walsh / John Carmack (id Software, 1999).
+    // Mechanism: IEEE 754 f32 bits encode value as (1 + mantissa) × 2^(exp-127).
+    // A right-shift by 1 halves the exponent in log2-space (approximates √),
+    // and subtracting from 0x5f3759df corrects the bias offset.
+    // The result is a first-order estimate; each Newton–Raphson step below
+    // halves the relative error. Two iterations yield ~4.7 × 10⁻⁷ relative
+    // error — within single-precision ULP for all normal positive inputs.
+    // Reference: Lomont, "Fast Inverse Square Root" (2003);
+    //            quake3-1.32b/code/game/q_math.c, Q_rsqrt().
     let i = 0x5f37_59df - (i >> 1);
     let y = f32::from_bits(i);
-    let y = y * (1.5 - half * y * y);
-    let y = y * (1.5 - half * y * y);
-    x * y
+    let y = y * (1.5 - half * y * y); // Newton–Raphson iteration 1
+    let y = y * (1.5 - half * y * y); // Newton–Raphson iteration 2
+    x * y // x * (1/√x) = √x
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const GROUPS: [OutlineGroup; 3] =
+        [OutlineGroup::Latin, OutlineGroup::Kana, OutlineGroup::Kanji];
+
     #[test]
-    fn tables_have_entries() {
-        assert!(FONT_OUTLINES_REGULAR.len() > 100);
-        assert!(FONT_OUTLINES_BOLD.len() > 100);
+    fn fast_sqrt_is_accurate() {
+        for &x in &[1e-6f32, 0.01, 0.5, 2.0, 1234.5] {
+            let s = fast_sqrt(x);
+            assert!((s * s - x).abs() <= x * 1e-5, "sqrt({x}) = {s}");
+        }
+        assert_eq!(fast_sqrt(0.0), 0.0);
     }
 
     #[test]
-    fn tables_are_sorted() {
-        for w in FONT_OUTLINES_REGULAR.windows(2) {
-            assert!(w[0].0 < w[1].0, "regular table is not sorted");
+    fn tables_are_sorted_and_grouped() {
+        for group in GROUPS {
+            for bold in [false, true] {
+                let Some((t, _)) = table(group, bold) else {
+                    assert!(!group.is_enabled());
+                    continue;
+                };
+                for w in t.windows(2) {
+                    assert!(w[0].0 < w[1].0, "{group:?} table is not sorted");
+                }
+                assert!(t.iter().all(|e| OutlineGroup::of(e.0) == group));
+            }
         }
-        for w in FONT_OUTLINES_BOLD.windows(2) {
-            assert!(w[0].0 < w[1].0, "bold table is not sorted");
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "outlines-latin",
+        feature = "outlines-kana",
+        feature = "outlines-kanji"
+    ))]
+    fn tables_have_entries() {
+        let count = |bold| {
+            GROUPS
+                .iter()
+                .filter_map(|g| table(*g, bold))
+                .map(|(t, _)| t.len())
+                .sum::<usize>()
+        };
+        assert!(count(false) > 100);
+        assert!(count(true) > 100);
+    }
+
+    #[test]
+    fn group_of_codepoints() {
+        assert_eq!(OutlineGroup::of('A'), OutlineGroup::Latin);
+        assert_eq!(OutlineGroup::of('→'), OutlineGroup::Latin);
+        assert_eq!(OutlineGroup::of('あ'), OutlineGroup::Kana);
+        assert_eq!(OutlineGroup::of('。'), OutlineGroup::Kana);
+        assert_eq!(OutlineGroup::of('（'), OutlineGroup::Kana);
+        assert_eq!(OutlineGroup::of('株'), OutlineGroup::Kanji);
+    }
+
+    #[test]
+    fn disabled_group_is_not_found() {
+        for (ch, group) in [
+            ('A', OutlineGroup::Latin),
+            ('あ', OutlineGroup::Kana),
+            ('株', OutlineGroup::Kanji),
+        ] {
+            assert_eq!(has(ch), group.is_enabled(), "{ch}");
         }
     }
 
     #[test]
+    #[cfg(feature = "outlines-latin")]
     fn lookup_ascii() {
         assert!(has('A'));
         assert!(has('a'));
@@ -219,18 +689,21 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "outlines-kanji")]
     fn lookup_kanji() {
         assert!(has('株'));
         assert!(has('式'));
     }
 
     #[test]
+    #[cfg(feature = "outlines-kana")]
     fn lookup_hiragana() {
         assert!(has('あ'));
         assert!(has('ん'));
     }
 
     #[test]
+    #[cfg(feature = "outlines-latin")]
     fn rasterize_a_has_inside_pixels() {
         let sdf = rasterize('A', &MetaFontParams::sans_regular()).expect("A in table");
         assert!(
@@ -240,6 +713,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "outlines-kanji")]
     fn rasterize_kabu_has_inside_pixels() {
         let sdf = rasterize('株', &MetaFontParams::sans_regular()).expect("株 in table");
         assert!(
@@ -248,13 +722,71 @@ mod tests {
         );
     }
 
+    /// Per-pixel rasterization straight from the public outline queries.
+    fn reference(cmds: &[PathCmd], fill: FillRule) -> Vec<f32> {
+        let inv = 1.0 / (GLYPH_SDF_SIZE - 1) as f32;
+        let mut data = Vec::new();
+        for py in 0..GLYPH_SDF_SIZE {
+            for px in 0..GLYPH_SDF_SIZE {
+                let x = TILE_EM_LEFT + px as f32 * inv * (TILE_EM_RIGHT - TILE_EM_LEFT);
+                let y = TILE_EM_BOTTOM + py as f32 * inv * (TILE_EM_TOP - TILE_EM_BOTTOM);
+                let dist = fast_sqrt(outline::distance_sq(cmds, x, y));
+                data.push(if outline::is_inside(cmds, x, y, fill) {
+                    -dist
+                } else {
+                    dist
+                });
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn row_sweep_matches_per_pixel_queries() {
+        // Lines, a quad bowl and a cubic, with an overlapping contour so the
+        // fill rules differ; plus a diagonal polyline square.
+        let mut shapes = alloc::vec![
+            alloc::vec![
+                PathCmd::MoveTo(0.1, 0.0),
+                PathCmd::LineTo(0.6, 0.0),
+                PathCmd::QuadTo(0.9, 0.3, 0.6, 0.6),
+                PathCmd::CubicTo(0.4, 0.8, 0.2, 0.5, 0.1, 0.6),
+                PathCmd::MoveTo(0.3, 0.2),
+                PathCmd::LineTo(0.8, 0.2),
+                PathCmd::LineTo(0.8, 0.4),
+                PathCmd::LineTo(0.3, 0.4),
+            ],
+            alloc::vec![
+                PathCmd::MoveTo(0.5, -0.1),
+                PathCmd::LineTo(0.9, 0.3),
+                PathCmd::LineTo(0.5, 0.7),
+                PathCmd::LineTo(0.1, 0.3),
+            ],
+        ];
+        for ch in ['A', 'g', 'あ', '株'] {
+            if let Some((outline, _)) = lookup(ch, false) {
+                shapes.push(outline.decode());
+            }
+        }
+        for cmds in &shapes {
+            for fill in [FillRule::NonZero, FillRule::EvenOdd] {
+                let sdf = rasterize_outline(cmds, 0.5, fill);
+                for (a, b) in sdf.data.iter().zip(reference(cmds, fill)) {
+                    assert_eq!(a.to_bits(), b.to_bits(), "{a} vs {b}");
+                }
+            }
+        }
+    }
+
     #[test]
+    #[cfg(feature = "outlines-latin")]
     fn rasterize_space_has_advance() {
         let sdf = rasterize(' ', &MetaFontParams::sans_regular()).expect("space in table");
         assert!(sdf.advance > 0.0, "space should carry positive advance");
     }
 
     #[test]
+    #[cfg(feature = "outlines-latin")]
     fn bold_fills_more_than_regular() {
         let sdf_regular = rasterize('B', &MetaFontParams::sans_regular()).unwrap();
         let sdf_bold = rasterize('B', &MetaFontParams::sans_bold()).unwrap();
@@ -265,4 +797,182 @@ mod tests {
             "bold 'B' should fill >= regular ('bold'={inside_b}, 'regular'={inside_r})"
         );
     }
+
+    #[test]
+    fn fill_rule_controls_overlap() {
+        // Two overlapping squares with the same orientation.
+        let cmds = [
+            PathCmd::MoveTo(0.1, 0.0),
+            PathCmd::LineTo(0.6, 0.0),
+            PathCmd::LineTo(0.6, 0.5),
+            PathCmd::LineTo(0.1, 0.5),
+            PathCmd::MoveTo(0.4, 0.2),
+            PathCmd::LineTo(0.9, 0.2),
+            PathCmd::LineTo(0.9, 0.7),
+            PathCmd::LineTo(0.4, 0.7),
+        ];
+        let nz = rasterize_outline(&cmds, 1.0, FillRule::NonZero);
+        let eo = rasterize_outline(&cmds, 1.0, FillRule::EvenOdd);
+        // Centre of the overlap: em (0.5, 0.35) → tile (0.5, 0.55).
+        assert!(nz.sample(0.5, 0.55) < 0.0);
+        assert!(eo.sample(0.5, 0.55) > 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn curves_survive_in_table() {
+        let (outline, _) = lookup('O', false).expect("O in table");
+        assert!(outline.commands().any(|c| matches!(c, PathCmd::QuadTo(..))));
+    }
+
+    /// Uncompressed f32 reference outlines (pre-quantization) for 'g' and 'あ'.
+    #[rustfmt::skip]
+    #[cfg(all(feature = "outlines-latin", feature = "outlines-kana"))]
+    const REFERENCE: &[(char, &[PathCmd], f32)] = {
+        use PathCmd::{LineTo as L, MoveTo as M, QuadTo as Q};
+        &[
+            ('g', &[M(0.5811,0.5649), L(0.5811,0.1338), Q(0.5810,0.0093,0.5181,-0.0532), Q(0.4516,-0.1192,0.3252,-0.1191), Q(0.1812,-0.1191,0.0820,-0.0430), L(0.1265,0.0229), Q(0.2051,-0.0430,0.3257,-0.0430), Q(0.4277,-0.0430,0.4688,0.0254), Q(0.4912,0.0625,0.4912,0.1152), L(0.4912,0.1821), Q(0.4653,0.1481,0.4229,0.1274), Q(0.3715,0.1030,0.3193,0.1030), Q(0.2369,0.1031,0.1709,0.1460), Q(0.0655,0.2138,0.0654,0.3408), Q(0.0654,0.4530,0.1562,0.5239), Q(0.2281,0.5791,0.3208,0.5791), Q(0.4229,0.5791,0.4951,0.5132), L(0.5103,0.5649), M(0.4912,0.4141), Q(0.4370,0.5029,0.3271,0.5029), Q(0.2750,0.5029,0.2349,0.4785), Q(0.1607,0.4337,0.1606,0.3413), Q(0.1608,0.2809,0.1987,0.2358), Q(0.2462,0.1793,0.3257,0.1792), Q(0.4086,0.1792,0.4639,0.2373), Q(0.4790,0.2529,0.4912,0.2749)], 0.6699),
+            ('あ', &[M(0.1211,0.6958), L(0.3672,0.6958), Q(0.3716,0.7467,0.3833,0.8257), L(0.4609,0.8159), Q(0.4498,0.7438,0.4443,0.6958), L(0.8472,0.6958), L(0.8472,0.6270), L(0.4370,0.6270), Q(0.4303,0.5610,0.4253,0.4951), Q(0.4946,0.5069,0.5591,0.5068), Q(0.7333,0.5069,0.8267,0.4214), Q(0.9034,0.3515,0.9033,0.2412), Q(0.9033,0.0029,0.5669,-0.0479), L(0.5322,0.0161), Q(0.8224,0.0581,0.8223,0.2437), Q(0.8223,0.3715,0.7070,0.4189), Q(0.6069,0.2422,0.4536,0.1191), Q(0.3681,0.0504,0.3042,0.0229), Q(0.2569,0.0026,0.2100,0.0024), Q(0.1479,0.0025,0.1133,0.0430), Q(0.0790,0.0829,0.0791,0.1567), Q(0.0791,0.2696,0.1704,0.3638), Q(0.2432,0.4385,0.3472,0.4751), Q(0.3520,0.5512,0.3599,0.6270), L(0.1211,0.6270), M(0.3442,0.4028), Q(0.2577,0.3666,0.2051,0.2959), Q(0.1523,0.2241,0.1523,0.1562), Q(0.1524,0.0698,0.2173,0.0698), Q(0.2715,0.0699,0.3589,0.1328), Q(0.3433,0.2299,0.3433,0.3457), Q(0.3433,0.3711,0.3442,0.4028), M(0.4189,0.4268), Q(0.4185,0.3862,0.4185,0.3579), Q(0.4186,0.2621,0.4272,0.1870), Q(0.5659,0.3120,0.6353,0.4390), Q(0.6055,0.4434,0.5654,0.4434), Q(0.4839,0.4433,0.4189,0.4268)], 0.9800),
+        ]
+    };
+
+    #[test]
+    #[cfg(all(feature = "outlines-latin", feature = "outlines-kana"))]
+    fn compressed_matches_reference_rasterization() {
+        let params = MetaFontParams::sans_regular();
+        for &(ch, cmds, advance) in REFERENCE {
+            let (outline, adv) = lookup(ch, false).unwrap();
+            assert!(
+                (adv - advance).abs() < 1e-3,
+                "{ch}: advance {adv} vs {advance}"
+            );
+            let reference = rasterize_outline(cmds, advance, FillRule::NonZero);
+            let decoded = rasterize(ch, &params).unwrap();
+            let max_err = reference
+                .data
+                .iter()
+                .zip(decoded.data.iter())
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0f32, f32::max);
+            // Quantization to 1/2048 em moves edges by at most ~0.00025 em.
+            assert!(max_err < 1e-3, "{ch}: max SDF error {max_err}");
+            assert_eq!(outline.decode().len(), cmds.len());
+        }
+    }
+
+    #[test]
+    fn decoder_stops_on_truncated_record() {
+        // Claims 3 commands (M, L, L) but carries only one point.
+        static TRUNCATED: [u8; 4] = [3, 0b0001_0100, 2, 2];
+        let outline = EncodedOutline { bytes: &TRUNCATED };
+        let cmds = outline.decode();
+        let u = 1.0 / f32::from(OUTLINE_UNITS_PER_EM);
+        assert_eq!(cmds, [PathCmd::MoveTo(u, u)]);
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn master_weights_render_masters_unchanged() {
+        let regular = styled_outline('H', &MetaFontParams::sans_regular()).unwrap();
+        assert_eq!(regular.cmds, lookup('H', false).unwrap().0.decode());
+        assert_eq!(regular.embolden, 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn weight_is_continuous() {
+        let ink = |weight: f32| {
+            let params = MetaFontParams {
+                weight,
+                ..MetaFontParams::sans_regular()
+            };
+            let sdf = rasterize('H', &params).unwrap();
+            sdf.data.iter().filter(|d| **d < 0.0).count()
+        };
+        let steps: alloc::vec::Vec<usize> = [0.1, 0.3, 0.45, 0.55, 0.65, 0.75, 0.9]
+            .iter()
+            .map(|w| ink(*w))
+            .collect();
+        for w in steps.windows(2) {
+            assert!(w[1] >= w[0], "ink must grow with weight: {steps:?}");
+        }
+        assert!(steps[6] > steps[0]);
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn width_scales_advance_and_outline() {
+        let base = styled_outline('H', &MetaFontParams::sans_regular()).unwrap();
+        let wide = styled_outline(
+            'H',
+            &MetaFontParams {
+                width: 1.5,
+                ..MetaFontParams::sans_regular()
+            },
+        )
+        .unwrap();
+        assert!((wide.advance - base.advance * 1.5).abs() < 1e-6);
+        let max_x = |o: &StyledOutline| {
+            o.cmds
+                .iter()
+                .map(|c| c.end_point().0)
+                .fold(0.0f32, f32::max)
+        };
+        assert!((max_x(&wide) - max_x(&base) * 1.5).abs() < 1e-4);
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn slant_shears_top_right() {
+        let upright = styled_outline('I', &MetaFontParams::sans_regular()).unwrap();
+        let italic = styled_outline(
+            'I',
+            &MetaFontParams {
+                slant: 0.2,
+                ..MetaFontParams::sans_regular()
+            },
+        )
+        .unwrap();
+        for (a, b) in upright.cmds.iter().zip(&italic.cmds) {
+            let (ax, ay) = a.end_point();
+            let (bx, by) = b.end_point();
+            assert_eq!(ay, by);
+            // Points above the baseline move right, in proportion to height.
+            if ay > 0.1 {
+                assert!(bx > ax);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "outlines-latin", feature = "outlines-bold"))]
+    fn compatible_masters_are_interpolated() {
+        // 'H' has the same command sequence in both masters.
+        let params = MetaFontParams {
+            weight: 0.6,
+            ..MetaFontParams::sans_regular()
+        };
+        let mid = styled_outline('H', &params).unwrap();
+        assert_eq!(mid.embolden, 0.0);
+        let regular = lookup('H', false).unwrap();
+        let bold = lookup('H', true).unwrap();
+        assert!((mid.advance - (regular.1 + bold.1) * 0.5).abs() < 1e-6);
+        let expected = outline::lerp(&regular.0.decode(), &bold.0.decode(), 0.5).unwrap();
+        assert_eq!(mid.cmds, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "outlines-latin")]
+    fn styled_metrics_bound_outline() {
+        let params = MetaFontParams::sans_regular();
+        let styled = styled_outline('H', &params).unwrap();
+        let m = styled.metrics();
+        assert!(m.has_ink());
+        assert!(m.lsb > 0.0 && m.rsb > 0.0);
+        assert!(m.ink_max.y > 0.5 && m.ink_min.y > -0.05);
+        let sdf = rasterize('H', &params).unwrap();
+        assert!((sdf.lsb - m.lsb).abs() < 1e-6);
+        // Space has no ink.
+        assert!(!styled_outline(' ', &params).unwrap().metrics().has_ink());
+    }
 }
@@ -0,0 +1,203 @@
+//! Halfwidth and Fullwidth Forms — U+FF00 to U+FFEF
+//!
+//! Nothing here is drawn from scratch:
+//!
+//! - fullwidth ASCII (！ to ～) and the fullwidth symbols (￠ ￡ ￢ ￣ ￤ ￥)
+//!   reuse the ASCII and Latin-1 skeletons with their ink centred in a
+//!   1-em cell;
+//! - halfwidth katakana (ｦ to ﾝ) are the [`katakana`] skeletons squeezed to
+//!   half width, with ﾞ and ﾟ as standalone sound marks;
+//! - halfwidth CJK punctuation (｡ ｢ ｣ ､ ･) keeps the full-size
+//!   [`cjk_symbols`] shape and moves it into the half cell;
+//! - halfwidth Hangul jamo are the [`hangul`] compatibility jamo, squeezed.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use super::latin::{self, append, extent, seg};
+use super::{cjk_symbols, hangul, katakana, GlyphGenerator, GlyphSkeleton};
+use crate::stroke::Point2;
+
+/// Advance of the fullwidth forms.
+const FULL_ADVANCE: f32 = 1.0;
+/// Advance of the halfwidth forms.
+const HALF_ADVANCE: f32 = 0.5;
+
+/// Halfwidth katakana U+FF66 to U+FF9D in code point order.
+const HALFWIDTH_KATAKANA: [char; 56] = [
+    'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー', 'ア', 'イ', 'ウ', 'エ', 'オ',
+    'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ', 'チ', 'ツ', 'テ', 'ト', 'ナ',
+    'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ', 'ム', 'メ', 'モ', 'ヤ', 'ユ',
+    'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン',
+];
+
+/// Does the fullwidth / halfwidth generator draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    matches!(ch as u32, 0xFF01..=0xFF5E | 0xFF61..=0xFF9F | 0xFFE0..=0xFFE5)
+        || halfwidth_jamo(ch).is_some()
+}
+
+/// Compatibility jamo drawn for a halfwidth Hangul letter.
+const fn halfwidth_jamo(ch: char) -> Option<char> {
+    let cp = ch as u32;
+    let jamo = match cp {
+        0xFFA1..=0xFFBE => cp - 0xFFA1 + 0x3131,
+        0xFFC2..=0xFFC7 => cp - 0xFFC2 + 0x314F,
+        0xFFCA..=0xFFCF => cp - 0xFFCA + 0x3155,
+        0xFFD2..=0xFFD7 => cp - 0xFFD2 + 0x315B,
+        0xFFDA..=0xFFDC => cp - 0xFFDA + 0x3161,
+        _ => return None,
+    };
+    char::from_u32(jamo)
+}
+
+/// Skeleton of a Halfwidth and Fullwidth Forms character, or `None` for
+/// code points this generator does not draw.
+pub(crate) fn build_skeleton(gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    let cp = ch as u32;
+    match cp {
+        0xFF01..=0xFF5E => Some(recentre(gen.build_skeleton((cp - 0xFEE0) as u8))),
+        0xFFE0..=0xFFE5 => {
+            let base = ['¢', '£', '¬', '¯', '¦', '¥'][(cp - 0xFFE0) as usize];
+            latin::build_skeleton(gen, base).map(recentre)
+        }
+        _ => build_halfwidth(gen, ch),
+    }
+}
+
+/// Halfwidth forms; independent of the font parameters, like the kana.
+fn build_halfwidth(gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    let cp = ch as u32;
+    match cp {
+        // ｡ ､ and ｣ already sit in the left half; ｢ and ･ move over.
+        0xFF61 => cjk_symbols::build_skeleton(gen, '。').map(|s| shifted(&s, 0.0)),
+        0xFF62 => cjk_symbols::build_skeleton(gen, '「').map(|s| shifted(&s, -0.4)),
+        0xFF63 => cjk_symbols::build_skeleton(gen, '」').map(|s| shifted(&s, 0.0)),
+        0xFF64 => cjk_symbols::build_skeleton(gen, '、').map(|s| shifted(&s, 0.0)),
+        0xFF65 => katakana::build_skeleton(gen, '・').map(|s| shifted(&s, -0.25)),
+        0xFF66..=0xFF9D => {
+            katakana::build_skeleton(gen, HALFWIDTH_KATAKANA[(cp - 0xFF66) as usize]).map(squeezed)
+        }
+        0xFF9E | 0xFF9F => Some(sound_mark(cp == 0xFF9F)),
+        _ => hangul::build_skeleton(gen, halfwidth_jamo(ch)?).map(squeezed),
+    }
+}
+
+/// `skel` moved so its ink is centred in a 1-em cell. The ASCII advances
+/// carry uneven side bearings, so centring the advance box would not do.
+fn recentre(skel: GlyphSkeleton) -> GlyphSkeleton {
+    let (lo, hi) = extent(&skel);
+    let mut out = GlyphSkeleton::empty();
+    append(
+        &mut out,
+        &skel,
+        1.0,
+        FULL_ADVANCE * 0.5 - (lo.x + hi.x) * 0.5,
+        0.0,
+    );
+    out.advance = FULL_ADVANCE;
+    out
+}
+
+/// Full-width `skel` moved by `dx` into a half-width cell.
+fn shifted(skel: &GlyphSkeleton, dx: f32) -> GlyphSkeleton {
+    let mut out = GlyphSkeleton::empty();
+    append(&mut out, skel, 1.0, dx, 0.0);
+    out.advance = HALF_ADVANCE;
+    out
+}
+
+/// Full-width `skel` compressed horizontally into a half-width cell.
+fn squeezed(skel: GlyphSkeleton) -> GlyphSkeleton {
+    let mut out = GlyphSkeleton::empty();
+    latin::append_mapped(&mut out, &skel, |p| Point2::new(p.x * 0.5, p.y));
+    out.advance = HALF_ADVANCE;
+    out
+}
+
+/// Standalone ﾞ (two ticks) or ﾟ (small ring) in the upper half cell.
+fn sound_mark(handakuten: bool) -> GlyphSkeleton {
+    let mut s = GlyphSkeleton::empty();
+    s.advance = HALF_ADVANCE;
+    if handakuten {
+        super::helpers::add_ellipse(&mut s, 0.2, 0.8, 0.08, 0.08, 0.0);
+    } else {
+        s.add_stroke(seg(0.1, 0.9, 0.18, 0.74));
+        s.add_stroke(seg(0.24, 0.9, 0.32, 0.74));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::param::MetaFontParams;
+
+    fn gen() -> GlyphGenerator {
+        GlyphGenerator::new(&MetaFontParams::sans_regular())
+    }
+
+    #[test]
+    fn fullwidth_ascii_is_centred_ascii() {
+        let gen = gen();
+        for ch in ['Ａ', 'ｇ', '０', '！', '～'] {
+            let full = build_skeleton(&gen, ch).unwrap();
+            let ascii = gen.build_skeleton((ch as u32 - 0xFEE0) as u8);
+            assert_eq!(full.stroke_count, ascii.stroke_count, "{ch}");
+            assert!((full.advance - 1.0).abs() < 1e-6);
+            let (lo, hi) = extent(&full);
+            let (alo, ahi) = extent(&ascii);
+            assert!((hi.x - lo.x - (ahi.x - alo.x)).abs() < 1e-4, "{ch}");
+            assert!((lo.y - alo.y).abs() < 1e-4, "{ch}: baseline kept");
+        }
+        let (lo, hi) = extent(&build_skeleton(&gen, 'Ｏ').unwrap());
+        assert!(((lo.x + hi.x) * 0.5 - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn halfwidth_katakana_is_squeezed_katakana() {
+        let gen = gen();
+        for (half, full) in [
+            ('ｱ', 'ア'),
+            ('ﾝ', 'ン'),
+            ('ｦ', 'ヲ'),
+            ('ｯ', 'ッ'),
+            ('ｰ', 'ー'),
+        ] {
+            let h = build_skeleton(&gen, half).unwrap();
+            let f = katakana::build_skeleton(&gen, full).unwrap();
+            assert_eq!(h.stroke_count, f.stroke_count, "{half}");
+            assert!((h.advance - 0.5).abs() < 1e-6);
+            assert!(extent(&h).1.x <= 0.5, "{half}");
+        }
+        for mark in ['ﾞ', 'ﾟ'] {
+            assert!(build_skeleton(&gen, mark).unwrap().stroke_count > 0);
+        }
+    }
+
+    #[test]
+    fn halfwidth_punctuation_fits_the_half_cell() {
+        let gen = gen();
+        for ch in ['｡', '｢', '｣', '､', '･'] {
+            let skel = build_skeleton(&gen, ch).unwrap();
+            let (lo, hi) = extent(&skel);
+            assert!(lo.x >= 0.0 && hi.x <= 0.5, "{ch}");
+        }
+    }
+
+    #[test]
+    fn coverage() {
+        let gen = gen();
+        for ch in ['Ａ', '￥', '￣', 'ﾟ', 'ﾡ', 'ￂ', 'ￜ'] {
+            assert!(covers(ch), "{ch}");
+            assert!(build_skeleton(&gen, ch).is_some(), "{ch}");
+        }
+        for ch in [
+            '\u{FF00}', '\u{FF5F}', '\u{FFA0}', '\u{FFC0}', '￦', '\u{FFE8}',
+        ] {
+            assert!(!covers(ch), "{ch}");
+            assert!(build_skeleton(&gen, ch).is_none(), "{ch}");
+        }
+    }
+}
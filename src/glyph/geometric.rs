@@ -0,0 +1,481 @@
+//! Geometric shapes and arrows — ■ □ ▲ △ ● ○ ◆ ◇ ★ ☆, ← ↑ → ↓ ⇒ and
+//! their relatives.
+//!
+//! Each glyph is a polygon, circle or set of line segments evaluated as an
+//! exact signed distance field, so edges stay straight and circles round
+//! at any size. Shapes fill a square one cap height on a side, centred on
+//! half the cap height so bullets and arrows sit in the middle of Latin
+//! text; outlines and arrow shafts follow `MetaFontParams::weight`. Filled
+//! and outlined forms share the same outer edge, so ■ and □ line up.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use alloc::vec::Vec;
+
+use crate::glyph::font_render::fast_sqrt;
+use crate::glyph::outline::point_line_dist_sq;
+use crate::glyph::{GlyphMetrics, GlyphResult, GlyphSdf};
+use crate::param::MetaFontParams;
+use crate::stroke::Point2;
+
+/// Space on either side of the shape square (em).
+const SIDE_BEARING: f32 = 0.08;
+/// Line half-width relative to `MetaFontParams::stroke_half_width`.
+const LINE_SCALE: f32 = 0.8;
+/// Thinnest line half-width (em).
+const MIN_LINE: f32 = 0.01;
+/// Arrowhead arm length relative to the arrow length.
+const HEAD: f32 = 0.32;
+/// Distance of each double-arrow shaft from the axis, relative to the
+/// arrow length.
+const DOUBLE_GAP: f32 = 0.1;
+/// Inner radius of the five-pointed stars relative to the outer radius
+/// (a regular pentagram).
+const STAR_INNER: f32 = 0.382;
+
+/// Unit vectors from the centre to the star's points and notches, starting
+/// at the top and going anticlockwise in 36° steps.
+const STAR: [(f32, f32); 10] = [
+    (0.0, 1.0),
+    (-0.587_785, 0.809_017),
+    (-0.951_057, 0.309_017),
+    (-0.951_057, -0.309_017),
+    (-0.587_785, -0.809_017),
+    (0.0, -1.0),
+    (0.587_785, -0.809_017),
+    (0.951_057, -0.309_017),
+    (0.951_057, 0.309_017),
+    (0.587_785, 0.809_017),
+];
+
+/// Does this module draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x2190..=0x2195
+            | 0x21D0..=0x21D5
+            | 0x25A0
+            | 0x25A1
+            | 0x25AA
+            | 0x25AB
+            | 0x25B2
+            | 0x25B3
+            | 0x25B6
+            | 0x25B7
+            | 0x25BC
+            | 0x25BD
+            | 0x25C0
+            | 0x25C1
+            | 0x25C6
+            | 0x25C7
+            | 0x25CB
+            | 0x25CE
+            | 0x25CF
+            | 0x2605
+            | 0x2606
+    )
+}
+
+/// Generate the SDF for a geometric shape or arrow, or `None` for
+/// characters this module does not draw.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams) -> Option<GlyphSdf> {
+    render(ch, params).map(|result| result.sdf)
+}
+
+/// Metrics of `ch` without rasterizing it.
+#[must_use]
+pub fn metrics(ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+    let frame = Frame::new(params);
+    parts(ch, &frame).map(|parts| frame.metrics(&parts))
+}
+
+/// [`generate`] with provenance and metrics.
+#[must_use]
+pub fn render(ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+    let frame = Frame::new(params);
+    let parts = parts(ch, &frame)?;
+    let metrics = frame.metrics(&parts);
+
+    let mut sdf = GlyphSdf::from_field(
+        Point2::new(0.0, frame.centre.y - frame.advance * 0.5),
+        Point2::new(frame.advance, frame.centre.y + frame.advance * 0.5),
+        |p| {
+            parts
+                .iter()
+                .map(|part| part.distance(p) - frame.line)
+                .fold(f32::MAX, f32::min)
+        },
+    );
+    sdf.advance = frame.advance;
+    sdf.lsb = metrics.lsb;
+    Some(GlyphResult::procedural(sdf, metrics))
+}
+
+/// Size, position and line weight of the shapes for one set of parameters.
+struct Frame {
+    centre: Point2,
+    /// Side of the square the shapes fill
+    side: f32,
+    advance: f32,
+    /// Half-width of outlines and arrow shafts
+    line: f32,
+}
+
+impl Frame {
+    fn new(params: &MetaFontParams) -> Self {
+        let side = params.cap_height;
+        let advance = side + 2.0 * SIDE_BEARING;
+        Self {
+            centre: Point2::new(advance * 0.5, side * 0.5),
+            side,
+            advance,
+            line: (params.stroke_half_width() * LINE_SCALE).max(MIN_LINE),
+        }
+    }
+
+    /// Point at `(x, y)` in shape units: the square spans -0.5 to 0.5.
+    fn at(&self, x: f32, y: f32) -> Point2 {
+        Point2::new(self.centre.x + x * self.side, self.centre.y + y * self.side)
+    }
+
+    fn polygon(&self, points: &[(f32, f32)], filled: bool) -> Part {
+        Part::Polygon {
+            points: points.iter().map(|&(x, y)| self.at(x, y)).collect(),
+            filled,
+        }
+    }
+
+    fn circle(&self, radius: f32, filled: bool) -> Part {
+        Part::Circle {
+            centre: self.centre,
+            radius: radius * self.side,
+            filled,
+        }
+    }
+
+    /// Ink box: every part's geometry grown by the line half-width.
+    fn metrics(&self, parts: &[Part]) -> GlyphMetrics {
+        let mut lo = Point2::new(f32::MAX, f32::MAX);
+        let mut hi = Point2::new(f32::MIN, f32::MIN);
+        let mut add = |p: Point2, r: f32| {
+            lo = Point2::new(lo.x.min(p.x - r), lo.y.min(p.y - r));
+            hi = Point2::new(hi.x.max(p.x + r), hi.y.max(p.y + r));
+        };
+        for part in parts {
+            match part {
+                Part::Polygon { points, .. } => points.iter().for_each(|&p| add(p, self.line)),
+                Part::Circle { centre, radius, .. } => add(*centre, radius + self.line),
+                Part::Segment { a, b } => {
+                    add(*a, self.line);
+                    add(*b, self.line);
+                }
+            }
+        }
+        GlyphMetrics::from_ink(self.advance, lo, hi)
+    }
+}
+
+/// Geometry of a glyph. Every part is drawn with the frame's line
+/// half-width: outlines straddle the edge, filled parts grow by it.
+enum Part {
+    Polygon {
+        points: Vec<Point2>,
+        filled: bool,
+    },
+    Circle {
+        centre: Point2,
+        radius: f32,
+        filled: bool,
+    },
+    Segment {
+        a: Point2,
+        b: Point2,
+    },
+}
+
+impl Part {
+    /// Signed distance to the part's centre line (outlines, segments) or
+    /// area (filled parts), before the line half-width is applied.
+    fn distance(&self, p: Point2) -> f32 {
+        match self {
+            Self::Polygon { points, filled } => {
+                let edge = points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|(&a, &b)| segment_distance(p, a, b))
+                    .fold(f32::MAX, f32::min);
+                if *filled && contains(points, p) {
+                    -edge
+                } else {
+                    edge
+                }
+            }
+            Self::Circle {
+                centre,
+                radius,
+                filled,
+            } => {
+                let (dx, dy) = (p.x - centre.x, p.y - centre.y);
+                let d = fast_sqrt(dx * dx + dy * dy) - radius;
+                if *filled {
+                    d
+                } else {
+                    d.abs()
+                }
+            }
+            Self::Segment { a, b } => segment_distance(p, *a, *b),
+        }
+    }
+}
+
+fn segment_distance(p: Point2, a: Point2, b: Point2) -> f32 {
+    fast_sqrt(point_line_dist_sq(p.x, p.y, (a.x, a.y), (b.x, b.y)))
+}
+
+/// Even-odd point-in-polygon test.
+fn contains(points: &[Point2], p: Point2) -> bool {
+    let mut inside = false;
+    for (a, b) in points.iter().zip(points.iter().cycle().skip(1)) {
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn parts(ch: char, frame: &Frame) -> Option<Vec<Part>> {
+    let mut out = Vec::new();
+    let cp = ch as u32;
+    match cp {
+        // ← ↑ → ↓ ↔ ↕
+        0x2190..=0x2195 => {
+            let (dir, both) = [
+                ((-1.0, 0.0), false),
+                ((0.0, 1.0), false),
+                ((1.0, 0.0), false),
+                ((0.0, -1.0), false),
+                ((1.0, 0.0), true),
+                ((0.0, 1.0), true),
+            ][(cp - 0x2190) as usize];
+            arrow(&mut out, frame, dir, false, both);
+        }
+        // ⇐ ⇑ ⇒ ⇓ ⇔ ⇕
+        0x21D0..=0x21D5 => {
+            let (dir, both) = [
+                ((-1.0, 0.0), false),
+                ((0.0, 1.0), false),
+                ((1.0, 0.0), false),
+                ((0.0, -1.0), false),
+                ((1.0, 0.0), true),
+                ((0.0, 1.0), true),
+            ][(cp - 0x21D0) as usize];
+            arrow(&mut out, frame, dir, true, both);
+        }
+        0x25A0 | 0x25A1 => out.push(square(frame, 0.5, cp == 0x25A0)),
+        0x25AA | 0x25AB => out.push(square(frame, 0.25, cp == 0x25AA)),
+        // ▲ △ ▶ ▷ ▼ ▽ ◀ ◁
+        0x25B2 | 0x25B3 | 0x25B6 | 0x25B7 | 0x25BC | 0x25BD | 0x25C0 | 0x25C1 => {
+            let turns = match cp {
+                0x25B2 | 0x25B3 => 0,
+                0x25C0 | 0x25C1 => 1,
+                0x25BC | 0x25BD => 2,
+                _ => 3,
+            };
+            let points = [(-0.5, -0.5), (0.5, -0.5), (0.0, 0.5)].map(|p| turn(p, turns));
+            out.push(frame.polygon(&points, cp & 1 == 0));
+        }
+        0x25C6 | 0x25C7 => out.push(frame.polygon(
+            &[(0.0, -0.5), (0.5, 0.0), (0.0, 0.5), (-0.5, 0.0)],
+            cp == 0x25C6,
+        )),
+        0x25CB => out.push(frame.circle(0.5, false)),
+        0x25CE => {
+            out.push(frame.circle(0.5, false));
+            out.push(frame.circle(0.25, false));
+        }
+        0x25CF => out.push(frame.circle(0.5, true)),
+        0x2605 | 0x2606 => {
+            // Centre the star's box: its points reach 1 up and 0.809 down.
+            let radius = 0.5 / 0.904_508;
+            let lift = -0.5 + 0.809_017 * radius;
+            let points: Vec<(f32, f32)> = STAR
+                .iter()
+                .enumerate()
+                .map(|(k, &(x, y))| {
+                    let r = if k % 2 == 0 {
+                        radius
+                    } else {
+                        radius * STAR_INNER
+                    };
+                    (x * r, y * r + lift)
+                })
+                .collect();
+            out.push(frame.polygon(&points, cp == 0x2605));
+        }
+        _ => return None,
+    }
+    Some(out)
+}
+
+/// Square of half-side `half` (shape units) around the centre.
+fn square(frame: &Frame, half: f32, filled: bool) -> Part {
+    frame.polygon(
+        &[(-half, -half), (half, -half), (half, half), (-half, half)],
+        filled,
+    )
+}
+
+/// `(x, y)` turned anticlockwise by `turns` quarter turns.
+fn turn((x, y): (f32, f32), turns: u32) -> (f32, f32) {
+    (0..turns).fold((x, y), |(x, y), _| (-y, x))
+}
+
+/// Arrow along `dir` through the frame centre, one shape unit long, with a
+/// chevron head at the far end (and the near end too when `both`). A
+/// `double` arrow has two shafts that end where they meet the head.
+fn arrow(out: &mut Vec<Part>, frame: &Frame, dir: (f32, f32), double: bool, both: bool) {
+    let at = |t: f32, s: f32| frame.at(dir.0 * t - dir.1 * s, dir.1 * t + dir.0 * s);
+    let head = if double { HEAD * 1.25 } else { HEAD };
+    let ends: &[f32] = if both { &[0.5, -0.5] } else { &[0.5] };
+    for &end in ends {
+        let back = end - end.signum() * head;
+        out.push(Part::Segment {
+            a: at(end, 0.0),
+            b: at(back, head),
+        });
+        out.push(Part::Segment {
+            a: at(end, 0.0),
+            b: at(back, -head),
+        });
+    }
+    if double {
+        // The shafts stop on the chevrons, one gap back from each tip.
+        let gap = DOUBLE_GAP;
+        let start = if both { -0.5 + gap } else { -0.5 };
+        for s in [gap, -gap] {
+            out.push(Part::Segment {
+                a: at(start, s),
+                b: at(0.5 - gap, s),
+            });
+        }
+    } else {
+        out.push(Part::Segment {
+            a: at(-0.5, 0.0),
+            b: at(0.5, 0.0),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::GLYPH_SDF_SIZE;
+
+    const IMPLEMENTED: &str = "←↑→↓↔↕⇐⇑⇒⇓⇔⇕■□▪▫▲△▶▷▼▽◀◁◆◇○◎●★☆";
+
+    fn tile(ch: char) -> GlyphSdf {
+        generate(ch, &MetaFontParams::sans_regular()).unwrap()
+    }
+
+    fn inked(sdf: &GlyphSdf) -> usize {
+        sdf.data.iter().filter(|d| **d < 0.0).count()
+    }
+
+    #[test]
+    fn implemented_glyphs_have_ink() {
+        let params = MetaFontParams::sans_regular();
+        for ch in IMPLEMENTED.chars() {
+            assert!(covers(ch), "{ch}");
+            let sdf = tile(ch);
+            assert!(inked(&sdf) > 0, "{ch}");
+            let m = metrics(ch, &params).unwrap();
+            assert!((m.advance - sdf.advance).abs() < 1e-6, "{ch}");
+            assert!(m.lsb > 0.0 && m.rsb > 0.0, "{ch}");
+        }
+        assert!(!covers('A') && !covers('\u{2196}'));
+    }
+
+    #[test]
+    fn filled_and_outlined_forms_share_their_outer_edge() {
+        let params = MetaFontParams::sans_regular();
+        for (filled, outlined) in [('■', '□'), ('▲', '△'), ('●', '○'), ('◆', '◇'), ('★', '☆')]
+        {
+            let (f, o) = (
+                metrics(filled, &params).unwrap(),
+                metrics(outlined, &params).unwrap(),
+            );
+            assert_eq!(f.ink_min, o.ink_min, "{filled}");
+            assert_eq!(f.ink_max, o.ink_max, "{filled}");
+            assert!(inked(&tile(filled)) > inked(&tile(outlined)), "{filled}");
+        }
+        // The centre of a filled shape is ink, of an outlined one paper.
+        assert!(tile('●').is_inside(0.5, 0.5));
+        assert!(!tile('○').is_inside(0.5, 0.5));
+        assert!(tile('◎').data.iter().filter(|d| **d < 0.0).count() > inked(&tile('○')));
+    }
+
+    #[test]
+    fn shapes_are_centred_on_half_the_cap_height() {
+        let params = MetaFontParams::sans_regular();
+        for ch in ['■', '●', '◆', '★', '▲', '▶'] {
+            let m = metrics(ch, &params).unwrap();
+            let cy = (m.ink_min.y + m.ink_max.y) * 0.5;
+            assert!((cy - params.cap_height * 0.5).abs() < 1e-3, "{ch}: {cy}");
+            assert!((m.lsb - m.rsb).abs() < 1e-3, "{ch}");
+        }
+    }
+
+    #[test]
+    fn arrows_point_where_they_say() {
+        // The head end of an arrow carries more ink than its tail.
+        let halves = |ch: char, vertical: bool| {
+            let sdf = tile(ch);
+            let (mut low, mut high) = (0usize, 0usize);
+            for (i, d) in sdf.data.iter().enumerate() {
+                let (x, y) = (i % GLYPH_SDF_SIZE, i / GLYPH_SDF_SIZE);
+                let coord = if vertical { y } else { x };
+                if *d < 0.0 {
+                    if coord < GLYPH_SDF_SIZE / 2 {
+                        low += 1;
+                    } else {
+                        high += 1;
+                    }
+                }
+            }
+            (low, high)
+        };
+        for (ch, vertical, forward) in [
+            ('→', false, true),
+            ('←', false, false),
+            ('↑', true, true),
+            ('↓', true, false),
+            ('⇒', false, true),
+            ('⇐', false, false),
+        ] {
+            let (low, high) = halves(ch, vertical);
+            assert_eq!(high > low, forward, "{ch}: {low} {high}");
+        }
+        let (low, high) = halves('↔', false);
+        assert!(low.abs_diff(high) * 20 < low + high);
+    }
+
+    #[test]
+    fn weight_thickens_lines() {
+        let light = MetaFontParams {
+            weight: 0.2,
+            ..MetaFontParams::sans_regular()
+        };
+        let heavy = MetaFontParams {
+            weight: 0.9,
+            ..light
+        };
+        for ch in ['□', '→', '○'] {
+            let thin = inked(&generate(ch, &light).unwrap());
+            let thick = inked(&generate(ch, &heavy).unwrap());
+            assert!(thick > thin, "{ch}");
+        }
+    }
+}
@@ -0,0 +1,561 @@
+//! Greek glyphs (monotonic Greek, U+0386 to U+03CE)
+//!
+//! Capitals that share their shape with Latin letters (Α Β Ε Ζ Η Ι Κ Μ Ν
+//! Ο Ρ Τ Υ Χ) reuse the ASCII skeletons, as do ο and ν; the rest get their
+//! own skeletons. Tonos and dialytika are attached with the Latin mark
+//! shapes, except that the tonos of a capital sits in front of the letter.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use super::helpers::{add_ellipse, add_half_arc, add_quarter_arc};
+use super::latin::{self, append, curve, dotless, extent, seg};
+use super::{GlyphGenerator, GlyphSkeleton};
+use crate::composite::DiacriticType;
+
+/// Room left in front of a capital for its tonos.
+const TONOS_SPACE: f32 = 0.12;
+
+/// Does the Greek generator draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{037E}' | '\u{0386}'..='\u{038A}' | '\u{038C}' | '\u{038E}'..='\u{03A1}' | '\u{03A3}'..='\u{03CE}'
+    )
+}
+
+/// Skeleton of a Greek character, or `None` when [`covers`] is false.
+pub(crate) fn build_skeleton(gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    if !covers(ch) {
+        return None;
+    }
+    Some(gen.upright(|up| up.build_greek_upright(ch)))
+}
+
+impl GlyphGenerator {
+    fn build_greek_upright(&self, ch: char) -> GlyphSkeleton {
+        use DiacriticType::{Acute, Diaeresis};
+        match ch {
+            // Greek question mark and ano teleia
+            '\u{037E}' => self.ascii(';'),
+            '\u{0387}' => latin::build_skeleton(self, '·').unwrap_or_else(|| self.ascii('.')),
+            'Ά' => self.capital_tonos('Α'),
+            'Έ' => self.capital_tonos('Ε'),
+            'Ή' => self.capital_tonos('Η'),
+            'Ί' => self.capital_tonos('Ι'),
+            'Ό' => self.capital_tonos('Ο'),
+            'Ύ' => self.capital_tonos('Υ'),
+            'Ώ' => self.capital_tonos('Ω'),
+            'Ϊ' => self.with_mark(self.greek_upper('Ι'), Diaeresis, true),
+            'Ϋ' => self.with_mark(self.greek_upper('Υ'), Diaeresis, true),
+            'ά' => self.with_mark(self.greek_lower('α'), Acute, false),
+            'έ' => self.with_mark(self.greek_lower('ε'), Acute, false),
+            'ή' => self.with_mark(self.greek_lower('η'), Acute, false),
+            'ί' => self.with_mark(self.greek_lower('ι'), Acute, false),
+            'ό' => self.with_mark(self.greek_lower('ο'), Acute, false),
+            'ύ' => self.with_mark(self.greek_lower('υ'), Acute, false),
+            'ώ' => self.with_mark(self.greek_lower('ω'), Acute, false),
+            'ϊ' => self.with_mark(self.greek_lower('ι'), Diaeresis, false),
+            'ϋ' => self.with_mark(self.greek_lower('υ'), Diaeresis, false),
+            // Dialytika with tonos: the accent sits between the dots.
+            'ΐ' | 'ΰ' => {
+                let base = self.greek_lower(if ch == 'ΐ' { 'ι' } else { 'υ' });
+                let (lo, hi) = extent(&base);
+                let mut skel = self.with_mark(base, Diaeresis, false);
+                self.add_mark(&mut skel, Acute, lo, hi, false);
+                skel
+            }
+            'Α'..='Ω' => self.greek_upper(ch),
+            _ => self.greek_lower(ch),
+        }
+    }
+
+    /// Capital with the tonos in front of it (Ά Έ Ή ...).
+    fn capital_tonos(&self, base: char) -> GlyphSkeleton {
+        let letter = self.greek_upper(base);
+        let cap = self.cap_height;
+        let mut skel = GlyphSkeleton::empty();
+        skel.add_stroke(seg(0.02, cap * 0.78, 0.08, cap));
+        append(&mut skel, &letter, 1.0, TONOS_SPACE, 0.0);
+        skel.advance = letter.advance + TONOS_SPACE;
+        skel
+    }
+
+    /// Capital letters Α to Ω.
+    pub(super) fn greek_upper(&self, ch: char) -> GlyphSkeleton {
+        let cap = self.cap_height;
+        let mut skel = GlyphSkeleton::empty();
+        match ch {
+            'Α' => return self.ascii('A'),
+            'Β' => return self.ascii('B'),
+            'Ε' => return self.ascii('E'),
+            'Ζ' => return self.ascii('Z'),
+            'Η' => return self.ascii('H'),
+            'Ι' => return self.ascii('I'),
+            'Κ' => return self.ascii('K'),
+            'Μ' => return self.ascii('M'),
+            'Ν' => return self.ascii('N'),
+            'Ο' => return self.ascii('O'),
+            'Ρ' => return self.ascii('P'),
+            'Τ' => return self.ascii('T'),
+            'Υ' => return self.ascii('Y'),
+            'Χ' => return self.ascii('X'),
+            'Γ' => {
+                let w = cap * 0.48;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(0.05, cap, w, cap));
+            }
+            'Δ' => {
+                let w = cap * 0.7;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.04, 0.0, w * 0.5, cap));
+                skel.add_stroke(seg(w * 0.5, cap, w - 0.04, 0.0));
+                skel.add_stroke(seg(0.04, 0.0, w - 0.04, 0.0));
+            }
+            'Θ' => {
+                let mut o = self.ascii('O');
+                let (lo, hi) = extent(&o);
+                let y = (lo.y + hi.y) * 0.5;
+                o.add_stroke(seg(lo.x + 0.1, y, hi.x - 0.1, y));
+                return o;
+            }
+            'Λ' => {
+                let w = cap * 0.65;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, w * 0.5, cap));
+                skel.add_stroke(seg(w * 0.5, cap, w - 0.05, 0.0));
+            }
+            'Ξ' => {
+                let w = cap * 0.55;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, cap, w, cap));
+                skel.add_stroke(seg(0.12, cap * 0.5, w - 0.07, cap * 0.5));
+                skel.add_stroke(seg(0.05, 0.0, w, 0.0));
+            }
+            'Π' => {
+                let w = cap * 0.6;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, cap));
+                skel.add_stroke(seg(w - 0.05, 0.0, w - 0.05, cap));
+                skel.add_stroke(seg(0.05, cap, w - 0.05, cap));
+            }
+            'Σ' => {
+                let w = cap * 0.55;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(w, cap, 0.05, cap));
+                skel.add_stroke(seg(0.05, cap, w * 0.55, cap * 0.5));
+                skel.add_stroke(seg(w * 0.55, cap * 0.5, 0.05, 0.0));
+                skel.add_stroke(seg(0.05, 0.0, w, 0.0));
+            }
+            'Φ' => {
+                let w = cap * 0.75;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(w * 0.5, 0.0, w * 0.5, cap));
+                add_ellipse(
+                    &mut skel,
+                    w * 0.5,
+                    cap * 0.5,
+                    w * 0.5 - 0.05,
+                    cap * 0.3,
+                    0.0,
+                );
+            }
+            'Ψ' => {
+                let w = cap * 0.7;
+                skel.advance = w + 0.1;
+                skel.add_stroke(seg(w * 0.5, 0.0, w * 0.5, cap));
+                skel.add_stroke(curve([
+                    (0.05, cap),
+                    (0.05, cap * 0.3),
+                    (w - 0.05, cap * 0.3),
+                    (w - 0.05, cap),
+                ]));
+            }
+            'Ω' => {
+                let w = cap * 0.75;
+                let (cx, cy) = (w * 0.5, cap * 0.55);
+                let (rx, ry) = (w * 0.5 - 0.05, cap * 0.45);
+                skel.advance = w + 0.1;
+                add_quarter_arc(&mut skel, cx, cy, rx, ry, 3, 0.0);
+                add_quarter_arc(&mut skel, cx, cy, rx, ry, 0, 0.0);
+                skel.add_stroke(curve([
+                    (cx - rx, cy),
+                    (cx - rx, cy - ry * 0.6),
+                    (w * 0.32, 0.12),
+                    (w * 0.32, 0.0),
+                ]));
+                skel.add_stroke(curve([
+                    (cx + rx, cy),
+                    (cx + rx, cy - ry * 0.6),
+                    (w * 0.68, 0.12),
+                    (w * 0.68, 0.0),
+                ]));
+                skel.add_stroke(seg(0.04, 0.0, w * 0.32, 0.0));
+                skel.add_stroke(seg(w * 0.68, 0.0, w - 0.04, 0.0));
+            }
+            _ => return self.build_placeholder(b'?'),
+        }
+        skel
+    }
+
+    /// Small letters α to ω (and final sigma).
+    pub(super) fn greek_lower(&self, ch: char) -> GlyphSkeleton {
+        let xh = self.x_height;
+        let asc = self.cap_height * 1.05;
+        let desc = self.cap_height * 0.25;
+        let mut skel = GlyphSkeleton::empty();
+        match ch {
+            'ο' => return self.ascii('o'),
+            'ν' => return self.ascii('v'),
+            'ι' => return dotless(self.ascii('i')),
+            'μ' => return latin::build_skeleton(self, 'µ').unwrap_or_else(|| self.ascii('u')),
+            'κ' => {
+                let k = self.ascii('K');
+                let s = xh / self.cap_height;
+                skel.advance = k.advance * s;
+                append(&mut skel, &k, s, 0.0, 0.0);
+            }
+            'α' => {
+                let w = xh * 0.85;
+                let cx = w * 0.45;
+                skel.advance = w + 0.06;
+                add_half_arc(&mut skel, cx, xh * 0.5, w * 0.4, xh * 0.5, 1, 0.0);
+                skel.add_stroke(curve([
+                    (cx, xh),
+                    (w * 0.75, xh),
+                    (w * 0.8, xh * 0.3),
+                    (w, 0.0),
+                ]));
+                skel.add_stroke(curve([
+                    (cx, 0.0),
+                    (w * 0.75, 0.0),
+                    (w * 0.8, xh * 0.7),
+                    (w, xh),
+                ]));
+            }
+            'β' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                skel.add_stroke(seg(0.05, -desc, 0.05, asc * 0.8));
+                skel.add_stroke(curve([
+                    (0.05, asc * 0.8),
+                    (0.05, asc),
+                    (w - 0.1, asc),
+                    (w - 0.1, asc * 0.78),
+                ]));
+                skel.add_stroke(curve([
+                    (w - 0.1, asc * 0.78),
+                    (w - 0.1, asc * 0.58),
+                    (w * 0.5, asc * 0.55),
+                    (w * 0.35, asc * 0.55),
+                ]));
+                skel.add_stroke(curve([
+                    (w * 0.35, asc * 0.55),
+                    (w + 0.02, asc * 0.52),
+                    (w + 0.02, 0.0),
+                    (0.05, xh * 0.1),
+                ]));
+            }
+            'γ' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                skel.add_stroke(seg(0.05, xh, w * 0.5, 0.0));
+                skel.add_stroke(seg(w - 0.05, xh, w * 0.5, 0.0));
+                skel.add_stroke(seg(w * 0.5, 0.0, w * 0.5, -desc));
+            }
+            'δ' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                add_ellipse(
+                    &mut skel,
+                    w * 0.5,
+                    xh * 0.45,
+                    w * 0.5 - 0.05,
+                    xh * 0.45,
+                    0.0,
+                );
+                skel.add_stroke(curve([
+                    (w * 0.5, xh * 0.9),
+                    (0.05, asc * 0.85),
+                    (w * 0.3, asc),
+                    (w * 0.85, asc * 0.95),
+                ]));
+            }
+            'ε' => {
+                let w = xh * 0.7;
+                skel.advance = w + 0.06;
+                skel.add_stroke(curve([
+                    (w - 0.05, xh * 0.85),
+                    (w * 0.6, xh * 1.02),
+                    (0.08, xh * 0.95),
+                    (0.08, xh * 0.75),
+                ]));
+                skel.add_stroke(curve([
+                    (0.08, xh * 0.75),
+                    (0.08, xh * 0.55),
+                    (w * 0.3, xh * 0.5),
+                    (w * 0.55, xh * 0.5),
+                ]));
+                skel.add_stroke(curve([
+                    (w * 0.55, xh * 0.5),
+                    (0.03, xh * 0.48),
+                    (0.03, 0.0),
+                    (w - 0.05, xh * 0.12),
+                ]));
+            }
+            'ζ' | 'ξ' => {
+                let w = xh * 0.75;
+                skel.advance = w + 0.06;
+                skel.add_stroke(seg(0.1, asc, w, asc));
+                if ch == 'ζ' {
+                    skel.add_stroke(curve([
+                        (w, asc),
+                        (0.05, xh * 0.7),
+                        (0.05, 0.05),
+                        (w * 0.6, 0.0),
+                    ]));
+                } else {
+                    skel.add_stroke(curve([
+                        (w * 0.75, asc),
+                        (0.1, asc * 0.95),
+                        (0.1, xh * 0.75),
+                        (w * 0.6, xh * 0.75),
+                    ]));
+                    skel.add_stroke(curve([
+                        (w * 0.6, xh * 0.75),
+                        (0.03, xh * 0.72),
+                        (0.03, 0.0),
+                        (w * 0.6, 0.0),
+                    ]));
+                }
+                skel.add_stroke(curve([
+                    (w * 0.6, 0.0),
+                    (w, 0.0),
+                    (w, -desc * 0.6),
+                    (w * 0.6, -desc),
+                ]));
+            }
+            'η' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                skel.add_stroke(seg(0.05, 0.0, 0.05, xh));
+                skel.add_stroke(curve([
+                    (0.05, xh * 0.7),
+                    (0.1, xh),
+                    (w - 0.05, xh * 1.05),
+                    (w - 0.05, xh * 0.6),
+                ]));
+                skel.add_stroke(seg(w - 0.05, xh * 0.6, w - 0.05, -desc));
+            }
+            'θ' => {
+                let w = xh * 0.75;
+                skel.advance = w + 0.08;
+                add_ellipse(
+                    &mut skel,
+                    w * 0.5,
+                    asc * 0.5,
+                    w * 0.5 - 0.03,
+                    asc * 0.5,
+                    0.0,
+                );
+                skel.add_stroke(seg(0.05, asc * 0.5, w - 0.05, asc * 0.5));
+            }
+            'λ' => {
+                let w = xh * 0.85;
+                skel.advance = w + 0.06;
+                let (x0, x1) = (0.08, w - 0.03);
+                skel.add_stroke(seg(x0, asc, x1, 0.0));
+                // The short leg starts on the long one, a little above
+                // the x-height midpoint.
+                let t = 1.0 - xh * 0.65 / asc;
+                skel.add_stroke(seg(x0 + (x1 - x0) * t, xh * 0.65, 0.05, 0.0));
+            }
+            'π' => {
+                let w = xh * 0.85;
+                skel.advance = w + 0.06;
+                skel.add_stroke(seg(0.02, xh, w, xh));
+                skel.add_stroke(seg(w * 0.25, xh, w * 0.22, 0.0));
+                skel.add_stroke(seg(w * 0.75, xh, w * 0.78, 0.0));
+            }
+            'ρ' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                add_ellipse(&mut skel, w * 0.5, xh * 0.5, w * 0.5 - 0.05, xh * 0.5, 0.0);
+                skel.add_stroke(seg(0.05, xh * 0.5, 0.05, -desc));
+            }
+            'σ' => {
+                let w = xh * 0.85;
+                let cx = w * 0.45;
+                skel.advance = w + 0.06;
+                add_ellipse(&mut skel, cx, xh * 0.45, w * 0.4, xh * 0.45, 0.0);
+                skel.add_stroke(seg(cx, xh * 0.9, w, xh * 0.9));
+            }
+            'ς' => {
+                let w = xh * 0.7;
+                skel.advance = w + 0.06;
+                skel.add_stroke(curve([
+                    (w - 0.03, xh * 0.9),
+                    (w * 0.5, xh * 1.05),
+                    (0.05, xh),
+                    (0.05, xh * 0.5),
+                ]));
+                skel.add_stroke(curve([
+                    (0.05, xh * 0.5),
+                    (0.05, 0.05),
+                    (w * 0.7, 0.1),
+                    (w * 0.7, -desc * 0.3),
+                ]));
+                skel.add_stroke(curve([
+                    (w * 0.7, -desc * 0.3),
+                    (w * 0.7, -desc * 0.7),
+                    (w * 0.5, -desc),
+                    (w * 0.3, -desc),
+                ]));
+            }
+            'τ' => {
+                let w = xh * 0.75;
+                skel.advance = w + 0.06;
+                skel.add_stroke(seg(0.03, xh, w, xh));
+                skel.add_stroke(curve([
+                    (w * 0.45, xh),
+                    (w * 0.45, 0.1),
+                    (w * 0.5, 0.0),
+                    (w * 0.8, 0.02),
+                ]));
+            }
+            'υ' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                skel.add_stroke(curve([
+                    (0.05, xh),
+                    (0.05, -0.02),
+                    (w - 0.05, -0.02),
+                    (w - 0.05, xh),
+                ]));
+            }
+            'φ' => {
+                let w = xh * 0.95;
+                skel.advance = w + 0.08;
+                add_ellipse(&mut skel, w * 0.5, xh * 0.5, w * 0.5 - 0.05, xh * 0.5, 0.0);
+                skel.add_stroke(seg(w * 0.5, asc * 0.85, w * 0.5, -desc));
+            }
+            'χ' => {
+                let w = xh * 0.8;
+                skel.advance = w + 0.08;
+                skel.add_stroke(seg(0.05, xh, w - 0.05, -desc));
+                skel.add_stroke(seg(w - 0.05, xh, 0.05, -desc));
+            }
+            'ψ' => {
+                let w = xh * 0.9;
+                skel.advance = w + 0.08;
+                skel.add_stroke(curve([
+                    (0.05, xh),
+                    (0.05, 0.05),
+                    (w - 0.05, 0.05),
+                    (w - 0.05, xh),
+                ]));
+                skel.add_stroke(seg(w * 0.5, asc, w * 0.5, -desc));
+            }
+            'ω' => {
+                let w = xh * 1.1;
+                skel.advance = w + 0.08;
+                skel.add_stroke(curve([
+                    (w * 0.2, xh),
+                    (0.0, xh * 0.6),
+                    (0.05, 0.0),
+                    (w * 0.28, 0.0),
+                ]));
+                skel.add_stroke(curve([
+                    (w * 0.28, 0.0),
+                    (w * 0.45, 0.0),
+                    (w * 0.5, xh * 0.2),
+                    (w * 0.5, xh * 0.5),
+                ]));
+                skel.add_stroke(curve([
+                    (w * 0.5, xh * 0.5),
+                    (w * 0.5, xh * 0.2),
+                    (w * 0.55, 0.0),
+                    (w * 0.72, 0.0),
+                ]));
+                skel.add_stroke(curve([
+                    (w * 0.72, 0.0),
+                    (w * 0.95, 0.0),
+                    (w, xh * 0.6),
+                    (w * 0.8, xh),
+                ]));
+            }
+            _ => return self.build_placeholder(b'?'),
+        }
+        skel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::param::MetaFontParams;
+
+    fn gen() -> GlyphGenerator {
+        GlyphGenerator::new(&MetaFontParams::sans_regular())
+    }
+
+    #[test]
+    fn covers_monotonic_greek() {
+        let gen = gen();
+        for cp in 0x0370..=0x03FF_u32 {
+            let ch = char::from_u32(cp).unwrap();
+            let Some(skel) = build_skeleton(&gen, ch) else {
+                assert!(!covers(ch));
+                continue;
+            };
+            assert_eq!(skel.placeholders, 0, "{ch} is a placeholder");
+            assert_eq!(skel.dropped, 0, "{ch} dropped strokes");
+            assert!(skel.stroke_count > 0, "{ch} has no strokes");
+        }
+        for ch in "ΑΩαωςάΐΰ\u{37E}\u{387}".chars() {
+            assert!(covers(ch), "{ch}");
+        }
+        assert!(!covers('\u{03A2}') && !covers('ϐ') && !covers('A'));
+    }
+
+    #[test]
+    fn homoglyphs_reuse_latin() {
+        let gen = gen();
+        for (greek, latin) in [('Α', 'A'), ('Η', 'H'), ('Ο', 'O'), ('ο', 'o'), ('ν', 'v')] {
+            let g = build_skeleton(&gen, greek).unwrap();
+            let l = gen.build_skeleton(latin as u8);
+            assert_eq!(g.stroke_count, l.stroke_count, "{greek}");
+            assert_eq!(g.strokes[0].p0, l.strokes[0].p0, "{greek}");
+        }
+    }
+
+    #[test]
+    fn descenders_and_ascenders() {
+        let gen = gen();
+        for ch in ['β', 'γ', 'η', 'ρ', 'φ', 'χ', 'ψ', 'ς'] {
+            let (lo, _) = extent(&build_skeleton(&gen, ch).unwrap());
+            assert!(lo.y < -0.05, "{ch} should descend");
+        }
+        for ch in ['β', 'δ', 'θ', 'λ'] {
+            let (_, hi) = extent(&build_skeleton(&gen, ch).unwrap());
+            assert!(hi.y > gen.x_height + 0.1, "{ch} should ascend");
+        }
+    }
+
+    #[test]
+    fn capital_tonos_sits_in_front() {
+        let gen = gen();
+        let alpha = build_skeleton(&gen, 'Α').unwrap();
+        let tonos = build_skeleton(&gen, 'Ά').unwrap();
+        assert_eq!(tonos.stroke_count, alpha.stroke_count + 1);
+        assert!((tonos.advance - alpha.advance - TONOS_SPACE).abs() < 1e-6);
+        let (lo, hi) = extent(&tonos);
+        assert!(lo.x < 0.05 && hi.y <= gen.cap_height + 1e-5);
+        // Lowercase accents go on top.
+        let (_, a_hi) = extent(&build_skeleton(&gen, 'α').unwrap());
+        let (_, acute_hi) = extent(&build_skeleton(&gen, 'ά').unwrap());
+        assert!(acute_hi.y > a_hi.y + 0.05);
+    }
+}
@@ -0,0 +1,549 @@
+//! Hangul glyph rendering — U+AC00 to U+D7A3 (syllables) and U+3131 to
+//! U+3163 (compatibility jamo)
+//!
+//! Syllables are decomposed arithmetically into initial, medial and
+//! optional final jamo (`S = 0xAC00 + (L * 21 + V) * 28 + T`) and composed
+//! the same way kanji are built from IDS components: each jamo skeleton is
+//! defined in a unit box and mapped into the region the layout assigns it.
+//! The layout depends on the vowel shape (vertical ㅏ, horizontal ㅗ,
+//! compound ㅘ) and on whether a final consonant is present, giving six
+//! variants.
+//!
+//! Consonant corners (ㄱ ㄴ ㅁ ...) are drawn as two straight strokes. The
+//! few hundred syllables that would exceed the skeleton's stroke budget
+//! (ㅃ + ㅏ + ㄼ and the like) are rebuilt in a compact form: every corner
+//! becomes a single cubic, the open boxes of ㄷ ㄹ ㅂ ㅌ run through both
+//! corners in one cubic and the bar of ㅈ ㅊ flows into its left leg. The
+//! densest syllable (ㅃ + ㅙ + ㄿ) then needs 15 strokes, so every
+//! syllable fits.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use crate::glyph::cjk_strokes::StrokePlacement;
+use crate::glyph::{GlyphGenerator, GlyphSdf, GlyphSkeleton};
+use crate::param::MetaFontParams;
+use crate::stroke::{Point2, Stroke};
+
+/// Standard advance for a full-width Hangul glyph (em units).
+const HANGUL_ADVANCE: f32 = 1.0;
+
+/// First precomposed syllable (가).
+const SYLLABLE_BASE: u32 = 0xAC00;
+/// Number of precomposed syllables (19 × 21 × 28).
+const SYLLABLE_COUNT: u32 = 11_172;
+
+/// Initial consonants in jamo order.
+const INITIALS: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ',
+    'ㅌ', 'ㅍ', 'ㅎ',
+];
+/// Medial vowels in jamo order.
+const MEDIALS: [char; 21] = [
+    'ㅏ', 'ㅐ', 'ㅑ', 'ㅒ', 'ㅓ', 'ㅔ', 'ㅕ', 'ㅖ', 'ㅗ', 'ㅘ', 'ㅙ', 'ㅚ', 'ㅛ', 'ㅜ', 'ㅝ', 'ㅞ',
+    'ㅟ', 'ㅠ', 'ㅡ', 'ㅢ', 'ㅣ',
+];
+/// Final consonants in jamo order (index 0 = no final).
+const FINALS: [char; 27] = [
+    'ㄱ', 'ㄲ', 'ㄳ', 'ㄴ', 'ㄵ', 'ㄶ', 'ㄷ', 'ㄹ', 'ㄺ', 'ㄻ', 'ㄼ', 'ㄽ', 'ㄾ', 'ㄿ', 'ㅀ', 'ㅁ',
+    'ㅂ', 'ㅄ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+/// Arrangement of the jamo inside a syllable block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyllableLayout {
+    /// Vowel to the right of the initial (가, 너, 비)
+    Vertical,
+    /// Vowel below the initial (고, 무, 그)
+    Horizontal,
+    /// Horizontal part below, vertical part to the right (과, 뭐, 의)
+    Compound,
+}
+
+impl SyllableLayout {
+    /// Layout used by the medial `vowel` (a compatibility jamo).
+    #[must_use]
+    pub const fn for_vowel(vowel: char) -> Self {
+        match vowel {
+            'ㅗ' | 'ㅛ' | 'ㅜ' | 'ㅠ' | 'ㅡ' => Self::Horizontal,
+            'ㅘ' | 'ㅙ' | 'ㅚ' | 'ㅝ' | 'ㅞ' | 'ㅟ' | 'ㅢ' => Self::Compound,
+            _ => Self::Vertical,
+        }
+    }
+}
+
+/// Generate the SDF for a Hangul syllable or compatibility jamo. Characters
+/// outside those blocks return an empty SDF with the full-width advance.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
+    skeleton(ch).map_or_else(
+        || {
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = HANGUL_ADVANCE;
+            sdf
+        },
+        |skel| GlyphGenerator::new(params).generate_from_skeleton(&skel),
+    )
+}
+
+/// Does the Hangul composer draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    let cp = ch as u32;
+    (cp >= SYLLABLE_BASE && cp < SYLLABLE_BASE + SYLLABLE_COUNT) || is_compat_jamo(ch)
+}
+
+/// Compatibility jamo drawn on their own (ㄱ to ㅣ).
+const fn is_compat_jamo(ch: char) -> bool {
+    matches!(ch, '\u{3131}'..='\u{3163}')
+}
+
+/// Split a precomposed syllable into its initial, medial and final jamo
+/// (as compatibility jamo).
+#[must_use]
+pub fn decompose(ch: char) -> Option<(char, char, Option<char>)> {
+    let s = (ch as u32).checked_sub(SYLLABLE_BASE)?;
+    if s >= SYLLABLE_COUNT {
+        return None;
+    }
+    let (l, v, t) = (s / (21 * 28), s / 28 % 21, s % 28);
+    let final_jamo = t.checked_sub(1).map(|t| FINALS[t as usize]);
+    Some((INITIALS[l as usize], MEDIALS[v as usize], final_jamo))
+}
+
+/// Skeleton of `ch` in the builder shape shared by every skeleton module.
+/// Jamo placement does not depend on the pen, so `gen` is unused.
+pub(crate) fn build_skeleton(_gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    skeleton(ch)
+}
+
+/// Compose the skeleton of a syllable or standalone jamo.
+///
+/// Syllables that would overflow the stroke budget are rebuilt in the
+/// compact form (see the module docs).
+fn skeleton(ch: char) -> Option<GlyphSkeleton> {
+    if is_compat_jamo(ch) {
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = HANGUL_ADVANCE;
+        add_jamo(&mut skel, ch, StrokePlacement::new(0.1, 0.1, 0.8, 0.8));
+        return Some(skel);
+    }
+    let skel = compose(ch, false)?;
+    if skel.dropped > 0 {
+        return compose(ch, true);
+    }
+    Some(skel)
+}
+
+/// Lay out the jamo of syllable `ch`; `compact` merges corner strokes.
+fn compose(ch: char, compact: bool) -> Option<GlyphSkeleton> {
+    let (initial, medial, final_jamo) = decompose(ch)?;
+    let mut skel = GlyphSkeleton::empty();
+    skel.advance = HANGUL_ADVANCE;
+    let (b_initial, b_horizontal, b_vertical, b_final) =
+        regions(SyllableLayout::for_vowel(medial), final_jamo.is_some());
+    add_consonant(&mut skel, initial, b_initial, compact);
+    let (horizontal, vertical) = split_vowel(medial);
+    if let Some(h) = horizontal {
+        add_vowel(&mut skel, h, b_horizontal);
+    }
+    if let Some(v) = vertical {
+        add_vowel(&mut skel, v, b_vertical);
+    }
+    if let Some(f) = final_jamo {
+        add_consonant(&mut skel, f, b_final, compact);
+    }
+    Some(skel)
+}
+
+/// Regions for the initial, the horizontal and vertical vowel parts and
+/// the final.
+fn regions(
+    layout: SyllableLayout,
+    has_final: bool,
+) -> (
+    StrokePlacement,
+    StrokePlacement,
+    StrokePlacement,
+    StrokePlacement,
+) {
+    let p = StrokePlacement::new;
+    let final_box = p(0.1, 0.0, 0.8, 0.38);
+    match (layout, has_final) {
+        (SyllableLayout::Vertical, false) => (
+            p(0.02, 0.1, 0.58, 0.8),
+            final_box,
+            p(0.55, 0.0, 0.43, 1.0),
+            final_box,
+        ),
+        (SyllableLayout::Vertical, true) => (
+            p(0.02, 0.45, 0.58, 0.52),
+            final_box,
+            p(0.55, 0.38, 0.43, 0.62),
+            final_box,
+        ),
+        (SyllableLayout::Horizontal, false) => (
+            p(0.15, 0.45, 0.7, 0.52),
+            p(0.0, 0.0, 1.0, 0.5),
+            final_box,
+            final_box,
+        ),
+        (SyllableLayout::Horizontal, true) => (
+            p(0.15, 0.64, 0.7, 0.35),
+            p(0.0, 0.36, 1.0, 0.3),
+            final_box,
+            final_box,
+        ),
+        (SyllableLayout::Compound, false) => (
+            p(0.02, 0.5, 0.6, 0.47),
+            p(0.0, 0.05, 0.75, 0.5),
+            p(0.62, 0.0, 0.36, 1.0),
+            final_box,
+        ),
+        (SyllableLayout::Compound, true) => (
+            p(0.02, 0.64, 0.6, 0.35),
+            p(0.0, 0.36, 0.75, 0.32),
+            p(0.62, 0.36, 0.36, 0.63),
+            final_box,
+        ),
+    }
+}
+
+/// Horizontal and vertical parts of a vowel (ㅘ = ㅗ + ㅏ).
+const fn split_vowel(v: char) -> (Option<char>, Option<char>) {
+    match v {
+        'ㅘ' => (Some('ㅗ'), Some('ㅏ')),
+        'ㅙ' => (Some('ㅗ'), Some('ㅐ')),
+        'ㅚ' => (Some('ㅗ'), Some('ㅣ')),
+        'ㅝ' => (Some('ㅜ'), Some('ㅓ')),
+        'ㅞ' => (Some('ㅜ'), Some('ㅔ')),
+        'ㅟ' => (Some('ㅜ'), Some('ㅣ')),
+        'ㅢ' => (Some('ㅡ'), Some('ㅣ')),
+        'ㅗ' | 'ㅛ' | 'ㅜ' | 'ㅠ' | 'ㅡ' => (Some(v), None),
+        _ => (None, Some(v)),
+    }
+}
+
+/// A standalone compatibility jamo in `b`.
+fn add_jamo(skel: &mut GlyphSkeleton, ch: char, b: StrokePlacement) {
+    match split_vowel(ch) {
+        (Some(h), Some(v)) => {
+            add_vowel(
+                skel,
+                h,
+                StrokePlacement::new(b.x, b.y, b.w * 0.7, b.h * 0.6),
+            );
+            add_vowel(
+                skel,
+                v,
+                StrokePlacement::new(b.x + b.w * 0.6, b.y, b.w * 0.4, b.h),
+            );
+        }
+        _ if ('ㅏ'..='ㅣ').contains(&ch) => add_vowel(skel, ch, b),
+        _ => add_consonant(skel, ch, b, false),
+    }
+}
+
+/// Clusters written as two consonants side by side.
+const fn split_cluster(c: char) -> Option<(char, char)> {
+    Some(match c {
+        'ㄲ' => ('ㄱ', 'ㄱ'),
+        'ㄳ' => ('ㄱ', 'ㅅ'),
+        'ㄵ' => ('ㄴ', 'ㅈ'),
+        'ㄶ' => ('ㄴ', 'ㅎ'),
+        'ㄸ' => ('ㄷ', 'ㄷ'),
+        'ㄺ' => ('ㄹ', 'ㄱ'),
+        'ㄻ' => ('ㄹ', 'ㅁ'),
+        'ㄼ' => ('ㄹ', 'ㅂ'),
+        'ㄽ' => ('ㄹ', 'ㅅ'),
+        'ㄾ' => ('ㄹ', 'ㅌ'),
+        'ㄿ' => ('ㄹ', 'ㅍ'),
+        'ㅀ' => ('ㄹ', 'ㅎ'),
+        'ㅃ' => ('ㅂ', 'ㅂ'),
+        'ㅄ' => ('ㅂ', 'ㅅ'),
+        'ㅆ' => ('ㅅ', 'ㅅ'),
+        'ㅉ' => ('ㅈ', 'ㅈ'),
+        _ => return None,
+    })
+}
+
+/// Add a consonant (or consonant cluster) fitted to `b`. With `compact`
+/// each corner is one cubic with a doubled control point instead of two
+/// lines, and three-leg runs are one cubic through both corners.
+fn add_consonant(skel: &mut GlyphSkeleton, c: char, b: StrokePlacement, compact: bool) {
+    if let Some((first, second)) = split_cluster(c) {
+        let half = b.w * 0.5;
+        add_consonant(
+            skel,
+            first,
+            StrokePlacement::new(b.x, b.y, half, b.h),
+            compact,
+        );
+        add_consonant(
+            skel,
+            second,
+            StrokePlacement::new(b.x + half, b.y, half, b.h),
+            compact,
+        );
+        return;
+    }
+    let m = |u: f32, v: f32| Point2::new(b.x + u * b.w, b.y + v * b.h);
+    let line = |a: (f32, f32), c: (f32, f32)| Stroke::line(m(a.0, a.1), m(c.0, c.1));
+    // Two straight legs meeting at `c`.
+    let corner = |skel: &mut GlyphSkeleton, a: (f32, f32), c: (f32, f32), d: (f32, f32)| {
+        if compact {
+            skel.add_stroke(Stroke::new(
+                m(a.0, a.1),
+                m(c.0, c.1),
+                m(c.0, c.1),
+                m(d.0, d.1),
+            ));
+        } else {
+            skel.add_stroke(line(a, c));
+            skel.add_stroke(line(c, d));
+        }
+    };
+    // Three legs through corners `c` and `d`, for the compact form.
+    let bend = |a: (f32, f32), c: (f32, f32), d: (f32, f32), e: (f32, f32)| {
+        Stroke::new(m(a.0, a.1), m(c.0, c.1), m(d.0, d.1), m(e.0, e.1))
+    };
+    match c {
+        'ㄷ' if compact => {
+            skel.add_stroke(bend((0.85, 0.85), (0.15, 0.85), (0.15, 0.15), (0.88, 0.15)))
+        }
+        'ㄹ' if compact => {
+            corner(skel, (0.15, 0.88), (0.85, 0.88), (0.85, 0.52));
+            skel.add_stroke(bend((0.85, 0.52), (0.15, 0.52), (0.15, 0.12), (0.88, 0.12)));
+        }
+        'ㅂ' if compact => {
+            skel.add_stroke(bend((0.18, 0.9), (0.18, 0.12), (0.82, 0.12), (0.82, 0.9)));
+            skel.add_stroke(line((0.18, 0.5), (0.82, 0.5)));
+        }
+        'ㅌ' if compact => {
+            skel.add_stroke(bend((0.85, 0.85), (0.15, 0.85), (0.15, 0.15), (0.88, 0.15)));
+            skel.add_stroke(line((0.15, 0.5), (0.8, 0.5)));
+        }
+        'ㄱ' => corner(skel, (0.12, 0.85), (0.85, 0.85), (0.78, 0.1)),
+        'ㄴ' => corner(skel, (0.15, 0.88), (0.15, 0.15), (0.9, 0.15)),
+        'ㄷ' => {
+            skel.add_stroke(line((0.15, 0.85), (0.85, 0.85)));
+            corner(skel, (0.15, 0.85), (0.15, 0.15), (0.88, 0.15));
+        }
+        'ㄹ' => {
+            corner(skel, (0.15, 0.88), (0.85, 0.88), (0.85, 0.52));
+            skel.add_stroke(line((0.85, 0.52), (0.15, 0.52)));
+            corner(skel, (0.15, 0.52), (0.15, 0.12), (0.88, 0.12));
+        }
+        'ㅁ' => {
+            corner(skel, (0.15, 0.85), (0.85, 0.85), (0.85, 0.15));
+            corner(skel, (0.15, 0.85), (0.15, 0.15), (0.85, 0.15));
+        }
+        'ㅂ' => {
+            corner(skel, (0.18, 0.9), (0.18, 0.12), (0.82, 0.12));
+            skel.add_stroke(line((0.82, 0.9), (0.82, 0.12)));
+            skel.add_stroke(line((0.18, 0.5), (0.82, 0.5)));
+        }
+        'ㅅ' => add_siot(skel, &m, 0.88),
+        'ㅇ' => add_ieung(skel, &m, 0.5, 0.38),
+        'ㅈ' => add_jieut(skel, &m, 0.85, compact),
+        'ㅊ' => {
+            skel.add_stroke(line((0.5, 0.98), (0.5, 0.86)));
+            add_jieut(skel, &m, 0.78, compact);
+        }
+        'ㅋ' => {
+            corner(skel, (0.12, 0.85), (0.85, 0.85), (0.78, 0.1));
+            skel.add_stroke(line((0.15, 0.5), (0.82, 0.5)));
+        }
+        'ㅌ' => {
+            skel.add_stroke(line((0.15, 0.85), (0.85, 0.85)));
+            skel.add_stroke(line((0.15, 0.5), (0.8, 0.5)));
+            corner(skel, (0.15, 0.85), (0.15, 0.15), (0.88, 0.15));
+        }
+        'ㅍ' => {
+            skel.add_stroke(line((0.1, 0.85), (0.9, 0.85)));
+            skel.add_stroke(line((0.32, 0.85), (0.35, 0.15)));
+            skel.add_stroke(line((0.68, 0.85), (0.65, 0.15)));
+            skel.add_stroke(line((0.05, 0.15), (0.95, 0.15)));
+        }
+        'ㅎ' => {
+            skel.add_stroke(line((0.5, 0.98), (0.5, 0.86)));
+            skel.add_stroke(line((0.12, 0.78), (0.88, 0.78)));
+            add_ieung(skel, &m, 0.35, 0.27);
+        }
+        _ => {}
+    }
+}
+
+/// ㅅ hanging from height `top` of the box.
+fn add_siot(skel: &mut GlyphSkeleton, m: &impl Fn(f32, f32) -> Point2, top: f32) {
+    for leg in siot_legs(m, top) {
+        skel.add_stroke(leg);
+    }
+}
+
+/// Left and right legs of a ㅅ hanging from height `top`.
+fn siot_legs(m: &impl Fn(f32, f32) -> Point2, top: f32) -> [Stroke; 2] {
+    [
+        Stroke::new(m(0.5, top), m(0.45, top * 0.55), m(0.3, 0.25), m(0.1, 0.1)),
+        Stroke::new(
+            m(0.48, top * 0.65),
+            m(0.6, top * 0.4),
+            m(0.75, 0.2),
+            m(0.9, 0.1),
+        ),
+    ]
+}
+
+/// ㅈ with its bar at height `top`. With `compact` the bar runs straight
+/// into the left leg, as in handwritten forms.
+fn add_jieut(skel: &mut GlyphSkeleton, m: &impl Fn(f32, f32) -> Point2, top: f32, compact: bool) {
+    if !compact {
+        skel.add_stroke(Stroke::line(m(0.15, top), m(0.85, top)));
+        add_siot(skel, m, top);
+        return;
+    }
+    let [mut left, right] = siot_legs(m, top);
+    left.p0 = m(0.15, top);
+    left.p1 = m(0.55, top);
+    skel.add_stroke(left);
+    skel.add_stroke(right);
+}
+
+/// ㅇ centred at `(0.5, cy)` with radius `r` (box units), as two
+/// half-circle cubics.
+fn add_ieung(skel: &mut GlyphSkeleton, m: &impl Fn(f32, f32) -> Point2, cy: f32, r: f32) {
+    let k = r * 4.0 / 3.0;
+    let (top, bottom) = (m(0.5, cy + r), m(0.5, cy - r));
+    skel.add_stroke(Stroke::new(
+        top,
+        m(0.5 - k, cy + r),
+        m(0.5 - k, cy - r),
+        bottom,
+    ));
+    skel.add_stroke(Stroke::new(
+        bottom,
+        m(0.5 + k, cy - r),
+        m(0.5 + k, cy + r),
+        top,
+    ));
+}
+
+/// Add a simple (non-compound) vowel fitted to `b`.
+fn add_vowel(skel: &mut GlyphSkeleton, v: char, b: StrokePlacement) {
+    let m = |u: f32, v: f32| Point2::new(b.x + u * b.w, b.y + v * b.h);
+    let line = |a: (f32, f32), c: (f32, f32)| Stroke::line(m(a.0, a.1), m(c.0, c.1));
+    // Vertical vowels: main stems and the branches off them.
+    let (stems, branches): (&[f32], &[(f32, f32, f32)]) = match v {
+        'ㅏ' => (&[0.35], &[(0.35, 0.8, 0.55)]),
+        'ㅑ' => (&[0.35], &[(0.35, 0.8, 0.65), (0.35, 0.8, 0.4)]),
+        'ㅓ' => (&[0.65], &[(0.2, 0.65, 0.55)]),
+        'ㅕ' => (&[0.65], &[(0.2, 0.65, 0.65), (0.2, 0.65, 0.4)]),
+        'ㅐ' => (&[0.35, 0.8], &[(0.35, 0.8, 0.55)]),
+        'ㅒ' => (&[0.35, 0.8], &[(0.35, 0.8, 0.65), (0.35, 0.8, 0.4)]),
+        'ㅔ' => (&[0.45, 0.85], &[(0.1, 0.45, 0.55)]),
+        'ㅖ' => (&[0.45, 0.85], &[(0.1, 0.45, 0.65), (0.1, 0.45, 0.4)]),
+        'ㅣ' => (&[0.5], &[]),
+        // Horizontal vowels: bar height and the stems off it.
+        'ㅗ' | 'ㅛ' | 'ㅜ' | 'ㅠ' | 'ㅡ' => {
+            let (bar, ticks): (f32, &[f32]) = match v {
+                'ㅗ' => (0.3, &[0.5]),
+                'ㅛ' => (0.3, &[0.35, 0.65]),
+                'ㅜ' => (0.7, &[0.5]),
+                'ㅠ' => (0.7, &[0.35, 0.65]),
+                _ => (0.5, &[]),
+            };
+            skel.add_stroke(line((0.05, bar), (0.95, bar)));
+            let end = if bar < 0.5 { 0.8 } else { 0.15 };
+            for &u in ticks {
+                skel.add_stroke(line((u, bar), (u, end)));
+            }
+            return;
+        }
+        _ => return,
+    };
+    for &u in stems {
+        skel.add_stroke(line((u, 0.95), (u, 0.05)));
+    }
+    for &(u0, u1, y) in branches {
+        skel.add_stroke(line((u0, y), (u1, y)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompose_follows_unicode_arithmetic() {
+        assert_eq!(decompose('가'), Some(('ㄱ', 'ㅏ', None)));
+        assert_eq!(decompose('한'), Some(('ㅎ', 'ㅏ', Some('ㄴ'))));
+        assert_eq!(decompose('글'), Some(('ㄱ', 'ㅡ', Some('ㄹ'))));
+        assert_eq!(decompose('뷁'), Some(('ㅂ', 'ㅞ', Some('ㄺ'))));
+        assert_eq!(decompose('힣'), Some(('ㅎ', 'ㅣ', Some('ㅎ'))));
+        assert_eq!(decompose('ㄱ'), None);
+        assert_eq!(decompose('\u{D7A4}'), None);
+    }
+
+    #[test]
+    fn layout_follows_vowel_shape() {
+        assert_eq!(SyllableLayout::for_vowel('ㅏ'), SyllableLayout::Vertical);
+        assert_eq!(SyllableLayout::for_vowel('ㅜ'), SyllableLayout::Horizontal);
+        assert_eq!(SyllableLayout::for_vowel('ㅢ'), SyllableLayout::Compound);
+        // The final always sits below everything else.
+        for layout in [
+            SyllableLayout::Vertical,
+            SyllableLayout::Horizontal,
+            SyllableLayout::Compound,
+        ] {
+            let (initial, _, _, fin) = regions(layout, true);
+            assert!(fin.y + fin.h <= initial.y + 1e-6);
+        }
+    }
+
+    #[test]
+    fn all_syllables_compose() {
+        for cp in SYLLABLE_BASE..SYLLABLE_BASE + SYLLABLE_COUNT {
+            let ch = char::from_u32(cp).unwrap();
+            let skel = skeleton(ch).unwrap();
+            assert_eq!(skel.placeholders, 0, "{ch}");
+            assert!(skel.stroke_count >= 2, "{ch}");
+            assert_eq!(skel.dropped, 0, "{ch}");
+        }
+        // ㅃ + ㅙ + ㄿ is the densest combination.
+        assert_eq!(skeleton('뾆').unwrap().stroke_count, 15);
+        for ch in "한국어뷁닭없읽값했".chars() {
+            assert_eq!(skeleton(ch).unwrap().dropped, 0, "{ch}");
+        }
+    }
+
+    #[test]
+    fn dense_syllables_merge_corners() {
+        // 빫 = ㅃ + ㅏ + ㄼ only fits with merged corners.
+        assert!(compose('빫', false).unwrap().dropped > 0);
+        let skel = skeleton('빫').unwrap();
+        assert_eq!(skel.dropped, 0);
+        assert_eq!(skel.stroke_count, compose('빫', true).unwrap().stroke_count);
+        // Light syllables keep the straight corners.
+        assert_eq!(skeleton('각').unwrap().stroke_count, 6);
+    }
+
+    #[test]
+    fn jamo_render_standalone() {
+        let params = MetaFontParams::sans_regular();
+        for ch in ['ㄱ', 'ㅎ', 'ㄺ', 'ㅏ', 'ㅘ', 'ㅣ'] {
+            let sdf = generate(ch, &params);
+            assert!(sdf.data.iter().any(|d| *d < 0.0), "{ch}");
+        }
+        assert!(covers('ㅣ') && !covers('\u{3164}'));
+        assert!(skeleton('ㄱ').unwrap().stroke_count < skeleton('ㄲ').unwrap().stroke_count);
+    }
+
+    #[test]
+    fn final_adds_strokes_below() {
+        let ga = skeleton('가').unwrap();
+        let gak = skeleton('각').unwrap();
+        assert_eq!(gak.stroke_count, ga.stroke_count + 2);
+        let low = gak.strokes[gak.stroke_count - 1].p3.y;
+        assert!(low < 0.3);
+    }
+}
@@ -0,0 +1,558 @@
+//! Grid fitting — small-size hinting for parametric glyphs
+//!
+//! At 9–14 px a stem that is 0.98 px wide and starts 0.4 px into a pixel
+//! column renders as two grey columns. [`GridFit`] adjusts the skeleton for
+//! one pixel size before rasterization:
+//!
+//! 1. **Stem widths** — the pen is rescaled (base width and, when it can be
+//!    solved, contrast) so vertical and horizontal stems are whole pixels
+//!    wide, never thinner than one pixel.
+//! 2. **Stem positions** — straight vertical and horizontal strokes are
+//!    moved so their edges land on pixel boundaries.
+//! 3. **Blue zones** — the baseline, x-height and cap-height are snapped the
+//!    same way, and overshoots smaller than half a pixel are flattened onto
+//!    them so round and flat letters share a height.
+//! 4. **Everything else** follows by piecewise-linear interpolation between
+//!    the snapped positions, so joints stay connected and curves keep their
+//!    shape. The advance is rounded to whole pixels.
+//!
+//! Optional stem darkening widens stems by up to half a pixel before the
+//! width is rounded, fading out as the size grows, to counter the thin,
+//! washed-out look of light weights on screens.
+//!
+//! The fitted glyph is only sharp when it is drawn at the same size with
+//! the pen origin and baseline on pixel boundaries.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use crate::glyph::float::{ceil, round};
+use crate::glyph::{
+    GlyphGenerator, GlyphResult, GlyphSdf, GlyphSkeleton, MAX_GLYPH_STROKES, MIN_SIDE_BEARING,
+};
+use crate::param::MetaFontParams;
+use crate::stroke::{PenModel, Point2, Stroke};
+
+/// Extra stem width (pixels) added by stem darkening at small sizes.
+const DARKEN_PX: f32 = 0.5;
+/// Size (ppem) at and below which darkening is at full strength.
+const DARKEN_FULL_PPEM: f32 = 9.0;
+/// Size (ppem) at and above which darkening is off.
+const DARKEN_NONE_PPEM: f32 = 24.0;
+/// Control points may stray this far (em) off the stem axis.
+const STEM_TOLERANCE: f32 = 1e-3;
+/// Shortest stroke (em) treated as a stem.
+const MIN_STEM_LENGTH: f32 = 0.05;
+/// Largest overshoot (em) flattened onto a blue zone; also capped at half
+/// a pixel.
+const BLUE_FUZZ: f32 = 0.02;
+/// Highest contrast the width solver may give the pen.
+const MAX_CONTRAST: f32 = 0.95;
+
+/// Grid-fitting settings for one pixel size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridFit {
+    /// Pixels per em the glyph will be drawn at
+    pub ppem: f32,
+    /// Widen stems at small sizes before rounding their width
+    pub stem_darkening: bool,
+}
+
+impl GridFit {
+    /// Fit to `ppem` pixels per em, without stem darkening.
+    #[must_use]
+    pub const fn new(ppem: f32) -> Self {
+        Self {
+            ppem,
+            stem_darkening: false,
+        }
+    }
+
+    /// Enable or disable stem darkening.
+    #[must_use]
+    pub const fn with_stem_darkening(mut self, on: bool) -> Self {
+        self.stem_darkening = on;
+        self
+    }
+
+    /// Stem width (pixels) added before rounding: half a pixel up to
+    /// 9 ppem, fading linearly to zero at 24 ppem.
+    #[must_use]
+    pub fn darkening(&self) -> f32 {
+        if !self.stem_darkening {
+            return 0.0;
+        }
+        let fade = (DARKEN_NONE_PPEM - self.ppem) / (DARKEN_NONE_PPEM - DARKEN_FULL_PPEM);
+        DARKEN_PX * fade.clamp(0.0, 1.0)
+    }
+
+    /// Size of one pixel in em.
+    fn pixel(&self) -> f32 {
+        1.0 / self.ppem
+    }
+
+    fn is_valid(&self) -> bool {
+        self.ppem.is_finite() && self.ppem > 0.0
+    }
+
+    /// Move a stem centred at `centre` with half-width `hw` (em) so its
+    /// lower edge sits on a pixel boundary. With a whole-pixel width the
+    /// upper edge does too.
+    fn snap(&self, centre: f32, hw: f32) -> f32 {
+        (round((centre - hw) * self.ppem) + hw * self.ppem) * self.pixel()
+    }
+}
+
+/// Grid-fitted SDF for any character the parametric skeleton builders
+/// cover (alphabetic scripts, small capitals and figure variants, kana,
+/// kanji, Hangul, CJK symbols, halfwidth and fullwidth forms). `None` for
+/// procedural glyphs such as box drawing, which have no stems to fit.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams, fit: &GridFit) -> Option<GlyphSdf> {
+    render(ch, params, fit).map(|result| result.sdf)
+}
+
+/// [`generate`] with provenance and metrics.
+#[must_use]
+pub fn render(ch: char, params: &MetaFontParams, fit: &GridFit) -> Option<GlyphResult> {
+    let (gen, skeleton) = GlyphGenerator::new(params).any_skeleton(ch)?;
+    Some(gen.render_fitted(&skeleton, fit))
+}
+
+impl GlyphGenerator {
+    /// This generator with its pen resized so vertical and horizontal
+    /// stems are whole pixels wide at `fit.ppem`.
+    #[must_use]
+    pub fn grid_fitted(&self, fit: &GridFit) -> Self {
+        if !fit.is_valid() {
+            return *self;
+        }
+        let mut fitted = *self;
+        fitted.pen = fit_pen(&self.pen, fit);
+        fitted
+    }
+
+    /// `skeleton` with stems, blue zones and advance snapped to the pixel
+    /// grid at `fit.ppem`. Call this on the unfitted generator and
+    /// rasterize the result with [`grid_fitted`](Self::grid_fitted).
+    #[must_use]
+    pub fn fit_skeleton(&self, skeleton: &GlyphSkeleton, fit: &GridFit) -> GlyphSkeleton {
+        if !fit.is_valid() {
+            return *skeleton;
+        }
+        let fitted_gen = self.grid_fitted(fit);
+        let hw_v = fitted_gen.pen.half_width(Point2::new(0.0, 1.0));
+        let hw_h = fitted_gen.pen.half_width(Point2::new(1.0, 0.0));
+        let strokes = &skeleton.strokes[..skeleton.stroke_count];
+
+        let mut xs = Axis::new();
+        for x in strokes.iter().filter_map(|s| stem(s, |p| p.x, |p| p.y)) {
+            xs.add(x, fit.snap(x, hw_v));
+        }
+
+        let mut ys = Axis::new();
+        let fuzz = BLUE_FUZZ.min(0.5 * fit.pixel());
+        for zone in [0.0, self.x_height, self.cap_height] {
+            let near = strokes.iter().any(|s| {
+                [s.p0, s.p1, s.p2, s.p3]
+                    .iter()
+                    .any(|p| (p.y - zone).abs() <= fuzz)
+            });
+            if near {
+                ys.add_zone(zone, fit.snap(zone, hw_h), fuzz);
+            }
+        }
+        for y in strokes.iter().filter_map(|s| stem(s, |p| p.y, |p| p.x)) {
+            if !ys.in_zone(y) {
+                ys.add(y, fit.snap(y, hw_h));
+            }
+        }
+
+        xs.finish();
+        ys.finish();
+        let mut fitted = *skeleton;
+        for stroke in &mut fitted.strokes[..fitted.stroke_count] {
+            for p in [
+                &mut stroke.p0,
+                &mut stroke.p1,
+                &mut stroke.p2,
+                &mut stroke.p3,
+            ] {
+                *p = Point2::new(xs.map(p.x), ys.map(p.y));
+            }
+        }
+
+        // Whole-pixel advance, widened rather than letting the ink plus
+        // side bearings overflow it.
+        let metrics = fitted_gen.metrics(&fitted);
+        let needed = if metrics.has_ink() {
+            metrics.ink_width() + 2.0 * MIN_SIDE_BEARING
+        } else {
+            0.0
+        };
+        let mut advance = round(skeleton.advance * fit.ppem).max(1.0);
+        if advance * fit.pixel() < needed {
+            advance = ceil(needed * fit.ppem);
+        }
+        fitted.advance = advance * fit.pixel();
+        fitted
+    }
+
+    /// Grid-fit `skeleton` for `fit.ppem` and rasterize it with the fitted
+    /// pen.
+    #[must_use]
+    pub fn render_fitted(&self, skeleton: &GlyphSkeleton, fit: &GridFit) -> GlyphResult {
+        self.grid_fitted(fit)
+            .render(&self.fit_skeleton(skeleton, fit))
+    }
+}
+
+/// Pen whose vertical and horizontal stem widths are whole pixels.
+///
+/// Both widths are rounded on their own and the pen's base width and
+/// contrast solved to hit them; when the solution is out of range only
+/// the base width is scaled, which fixes the vertical stems.
+fn fit_pen(pen: &PenModel, fit: &GridFit) -> PenModel {
+    let vertical = Point2::new(0.0, 1.0);
+    let horizontal = Point2::new(1.0, 0.0);
+    let hw_v = pen.half_width(vertical);
+    let hw_h = pen.half_width(horizontal);
+    if hw_v <= 0.0 || hw_h <= 0.0 {
+        return *pen;
+    }
+    let target = |hw: f32| {
+        let px = round(2.0 * hw * fit.ppem + fit.darkening()).max(1.0);
+        0.5 * px * fit.pixel()
+    };
+    let (want_v, want_h) = (target(hw_v), target(hw_h));
+
+    // half_width = base · (1 − contrast · s), with s depending only on the
+    // direction.
+    let unit = PenModel {
+        base_width: 1.0,
+        contrast: 1.0,
+        ..*pen
+    };
+    let s_v = 1.0 - unit.half_width(vertical);
+    let s_h = 1.0 - unit.half_width(horizontal);
+    let ratio = want_h / want_v;
+    let denom = s_h - ratio * s_v;
+    if denom.abs() > 1e-6 {
+        let contrast = (1.0 - ratio) / denom;
+        if (0.0..=MAX_CONTRAST).contains(&contrast) {
+            return PenModel {
+                base_width: want_v / (1.0 - contrast * s_v),
+                contrast,
+                ..*pen
+            };
+        }
+    }
+    PenModel {
+        base_width: pen.base_width * want_v / hw_v,
+        ..*pen
+    }
+}
+
+/// Position of `stroke` across its axis if it is a straight stem running
+/// along the other one: every control point has the same `across`
+/// coordinate and the stroke spans at least [`MIN_STEM_LENGTH`].
+fn stem(stroke: &Stroke, across: fn(Point2) -> f32, along: fn(Point2) -> f32) -> Option<f32> {
+    let at = across(stroke.p0);
+    let straight = [stroke.p1, stroke.p2, stroke.p3]
+        .iter()
+        .all(|p| (across(*p) - at).abs() <= STEM_TOLERANCE);
+    let long = (along(stroke.p3) - along(stroke.p0)).abs() >= MIN_STEM_LENGTH;
+    (straight && long).then_some(at)
+}
+
+/// Snapped positions along one axis and the piecewise-linear map through
+/// them.
+struct Axis {
+    /// (original, fitted) pairs, sorted by original after `finish`
+    anchors: [(f32, f32); MAX_GLYPH_STROKES + 3],
+    len: usize,
+    /// Blue zones: (original, fitted, fuzz)
+    zones: [(f32, f32, f32); 3],
+    zone_count: usize,
+}
+
+impl Axis {
+    const fn new() -> Self {
+        Self {
+            anchors: [(0.0, 0.0); MAX_GLYPH_STROKES + 3],
+            len: 0,
+            zones: [(0.0, 0.0, 0.0); 3],
+            zone_count: 0,
+        }
+    }
+
+    fn add(&mut self, original: f32, fitted: f32) {
+        let known = self.anchors[..self.len]
+            .iter()
+            .any(|(o, _)| (o - original).abs() <= STEM_TOLERANCE);
+        if !known && self.len < self.anchors.len() {
+            self.anchors[self.len] = (original, fitted);
+            self.len += 1;
+        }
+    }
+
+    fn add_zone(&mut self, original: f32, fitted: f32, fuzz: f32) {
+        if self.zone_count < self.zones.len() {
+            self.zones[self.zone_count] = (original, fitted, fuzz);
+            self.zone_count += 1;
+            self.add(original, fitted);
+        }
+    }
+
+    fn in_zone(&self, v: f32) -> bool {
+        self.zones[..self.zone_count]
+            .iter()
+            .any(|(z, _, fuzz)| (v - z).abs() <= *fuzz)
+    }
+
+    /// Sort the anchors and keep the fitted positions in the same order as
+    /// the originals.
+    fn finish(&mut self) {
+        let anchors = &mut self.anchors[..self.len];
+        anchors.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        for i in 1..anchors.len() {
+            anchors[i].1 = anchors[i].1.max(anchors[i - 1].1);
+        }
+    }
+
+    fn map(&self, v: f32) -> f32 {
+        // Overshoots within a zone are flattened onto it.
+        if let Some((_, fitted, _)) = self.zones[..self.zone_count]
+            .iter()
+            .find(|(z, _, fuzz)| (v - z).abs() <= *fuzz)
+        {
+            return *fitted;
+        }
+        let anchors = &self.anchors[..self.len];
+        let (Some(first), Some(last)) = (anchors.first(), anchors.last()) else {
+            return v;
+        };
+        if v <= first.0 {
+            return v + first.1 - first.0;
+        }
+        if v >= last.0 {
+            return v + last.1 - last.0;
+        }
+        let i = anchors.partition_point(|(o, _)| *o <= v);
+        let (a, b) = (anchors[i - 1], anchors[i]);
+        let t = (v - a.0) / (b.0 - a.0);
+        a.1 + (b.1 - a.1) * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// Pixel coverage of `sdf` drawn at `ppem` with the pen origin on a
+    /// pixel corner, top row first, box-filtered from `SUB`² samples.
+    fn pixels(sdf: &GlyphSdf, ppem: f32) -> Vec<Vec<f32>> {
+        const SUB: usize = 4;
+        let size = crate::glyph::GLYPH_SDF_SIZE;
+        let (w, h) = (
+            sdf.bbox_max.x - sdf.bbox_min.x,
+            sdf.bbox_max.y - sdf.bbox_min.y,
+        );
+        let distance = |x: f32, y: f32| {
+            let u = ((x - sdf.bbox_min.x) / w * (size - 1) as f32).clamp(0.0, (size - 1) as f32);
+            let v = ((y - sdf.bbox_min.y) / h * (size - 1) as f32).clamp(0.0, (size - 1) as f32);
+            let (x0, y0) = ((u as usize).min(size - 2), (v as usize).min(size - 2));
+            let (fx, fy) = (u - x0 as f32, v - y0 as f32);
+            let at = |px: usize, py: usize| sdf.data[py * size + px];
+            let bottom = at(x0, y0) + (at(x0 + 1, y0) - at(x0, y0)) * fx;
+            let top = at(x0, y0 + 1) + (at(x0 + 1, y0 + 1) - at(x0, y0 + 1)) * fx;
+            bottom + (top - bottom) * fy
+        };
+        let columns = ceil(sdf.advance * ppem) as i32;
+        let (lo, hi) = (
+            round(sdf.bbox_min.y * ppem) as i32,
+            round(sdf.bbox_max.y * ppem) as i32,
+        );
+        (lo..hi)
+            .rev()
+            .map(|row| {
+                (0..columns)
+                    .map(|col| {
+                        let inside = (0..SUB * SUB)
+                            .filter(|k| {
+                                let x = col as f32 + ((k % SUB) as f32 + 0.5) / SUB as f32;
+                                let y = row as f32 + ((k / SUB) as f32 + 0.5) / SUB as f32;
+                                distance(x / ppem, y / ppem) < 0.0
+                            })
+                            .count();
+                        inside as f32 / (SUB * SUB) as f32
+                    })
+                    .collect()
+            })
+            .filter(|row: &Vec<f32>| row.iter().any(|c| *c > 0.0))
+            .collect()
+    }
+
+    /// Fraction of inked pixels that are grey rather than solid.
+    fn blur(image: &[Vec<f32>]) -> f32 {
+        let inked = image.iter().flatten().filter(|c| **c > 0.1).count();
+        let grey = image
+            .iter()
+            .flatten()
+            .filter(|c| **c > 0.1 && **c < 0.9)
+            .count();
+        grey as f32 / inked.max(1) as f32
+    }
+
+    fn ascii(image: &[Vec<f32>]) -> Vec<String> {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+        image
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|c| RAMP[(c * 9.0 + 0.5) as usize] as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn before_after(
+        ch: char,
+        params: &MetaFontParams,
+        fit: &GridFit,
+    ) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+        let gen = GlyphGenerator::new(params);
+        let skeleton = gen.build_char_skeleton(ch).unwrap();
+        let before = pixels(&gen.render(&skeleton).sdf, fit.ppem);
+        let after = pixels(&generate(ch, params, fit).unwrap(), fit.ppem);
+        (before, after)
+    }
+
+    #[test]
+    fn before_and_after_images() {
+        // 'H' at 12 px: the stems straddle pixel columns and the crossbar
+        // two rows before fitting; afterwards every stem is one solid pixel.
+        const BEFORE: [&str; 11] = [
+            "    .  ", "%   %  ", "#   %  ", "#   %  ", "%   %  ", "%###@  ", "#:::@  ", "#   %  ",
+            "%   %  ", "#   %  ", "-   =  ",
+        ];
+        const AFTER: [&str; 9] = [
+            "%   % ", "@   @ ", "@   @ ", "@   @ ", "@@@@@ ", "@   @ ", "@   @ ", "@   @ ",
+            "%   % ",
+        ];
+        let params = MetaFontParams::sans_regular();
+        let (before, after) = before_after('H', &params, &GridFit::new(12.0));
+        assert_eq!(ascii(&before), BEFORE);
+        assert_eq!(ascii(&after), AFTER);
+    }
+
+    #[test]
+    fn fitting_sharpens_small_sizes() {
+        // Only the round stem ends stay grey once fitted.
+        let params = MetaFontParams::sans_regular();
+        let (mut before_total, mut cases) = (0.0, 0);
+        for ch in ['H', 'E', 'T', 'l', 'n', 'm'] {
+            for ppem in [9.0, 10.0, 11.0, 12.0, 13.0, 14.0] {
+                let (before, after) = before_after(ch, &params, &GridFit::new(ppem));
+                let (b, a) = (blur(&before), blur(&after));
+                assert!(a <= 0.25, "{ch} at {ppem}px: {b} -> {a}");
+                before_total += b;
+                cases += 1;
+            }
+        }
+        assert!(before_total / cases as f32 > 0.75, "{before_total}");
+    }
+
+    #[test]
+    fn stems_are_whole_pixels_on_pixel_edges() {
+        for weight in [0.2, 0.4, 0.7, 0.9] {
+            let params = MetaFontParams {
+                weight,
+                ..MetaFontParams::sans_regular()
+            };
+            let gen = GlyphGenerator::new(&params);
+            for ppem in [9.0, 12.0, 16.0, 24.0] {
+                let fit = GridFit::new(ppem);
+                let pen = gen.grid_fitted(&fit).pen;
+                let hw = pen.half_width(Point2::new(0.0, 1.0)) * ppem;
+                assert!(hw >= 0.5 - 1e-4, "{weight} {ppem}: {hw}");
+                assert!((2.0 * hw - round(2.0 * hw)).abs() < 1e-3);
+                let skeleton = gen.fit_skeleton(&gen.build_char_skeleton('H').unwrap(), &fit);
+                for stroke in &skeleton.strokes[..skeleton.stroke_count] {
+                    if let Some(x) = stem(stroke, |p| p.x, |p| p.y) {
+                        let edge = x * ppem - hw;
+                        assert!((edge - round(edge)).abs() < 1e-3, "{weight} {ppem}: {edge}");
+                    }
+                }
+                let advance = skeleton.advance * ppem;
+                assert!((advance - round(advance)).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn blue_zones_align_round_and_flat_letters() {
+        let params = MetaFontParams {
+            weight: 0.2,
+            ..MetaFontParams::sans_regular()
+        };
+        let fit = GridFit::new(12.0);
+        let top = |ch| render(ch, &params, &fit).unwrap().metrics.ink_max.y;
+        let bottom = |ch| render(ch, &params, &fit).unwrap().metrics.ink_min.y;
+        // The overshoot of 'o' is flattened onto the x-height and baseline.
+        assert!((top('o') - top('x')).abs() < 1e-4);
+        assert!((bottom('o') - bottom('x')).abs() < 1e-4);
+        assert!((top('O') - top('H')).abs() < 1e-4);
+        let x_height = top('x') * fit.ppem;
+        assert!((x_height - round(x_height)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stem_darkening_fades_with_size() {
+        let fit = GridFit::new(9.0).with_stem_darkening(true);
+        assert!((fit.darkening() - DARKEN_PX).abs() < 1e-6);
+        assert_eq!(GridFit::new(9.0).darkening(), 0.0);
+        assert_eq!(
+            GridFit::new(30.0).with_stem_darkening(true).darkening(),
+            0.0
+        );
+        let mid = GridFit::new(16.0).with_stem_darkening(true).darkening();
+        assert!(mid > 0.0 && mid < DARKEN_PX);
+
+        // A 1.4 px stem stays at 1 px plain and rounds up to 2 px darkened.
+        let params = MetaFontParams {
+            weight: 0.7,
+            ..MetaFontParams::sans_regular()
+        };
+        let gen = GlyphGenerator::new(&params);
+        let width = |fit: &GridFit| {
+            gen.grid_fitted(fit).pen.half_width(Point2::new(0.0, 1.0)) * 2.0 * fit.ppem
+        };
+        let plain = GridFit::new(12.0);
+        assert!((width(&plain) - 1.0).abs() < 1e-3);
+        assert!((width(&plain.with_stem_darkening(true)) - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn covers_cjk_and_ignores_invalid_sizes() {
+        let params = MetaFontParams::sans_regular();
+        let fit = GridFit::new(12.0);
+        for ch in ['あ', 'ア', '明', '한', '、', 'Ａ', 'ｱ', 'ᴀ', '⁴'] {
+            let sdf = generate(ch, &params, &fit).unwrap();
+            assert!(sdf.data.iter().any(|d| *d < 0.0), "{ch}");
+            assert!((sdf.advance * 12.0 - round(sdf.advance * 12.0)).abs() < 1e-3);
+        }
+        assert!(generate('\u{E000}', &params, &fit).is_none());
+        assert!(generate('─', &params, &fit).is_none());
+
+        let gen = GlyphGenerator::new(&params);
+        let skeleton = gen.build_char_skeleton('H').unwrap();
+        for ppem in [0.0, -3.0, f32::NAN] {
+            let fitted = gen.fit_skeleton(&skeleton, &GridFit::new(ppem));
+            assert_eq!(fitted.strokes[0].p0, skeleton.strokes[0].p0);
+            assert_eq!(fitted.advance, skeleton.advance);
+        }
+    }
+}
@@ -21,7 +21,7 @@ const KANA_ADVANCE: f32 = 1.0;
 /// Falls back to an empty placeholder for any character not yet covered.
 #[must_use]
 pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
-    build_skeleton(ch).map_or_else(
+    skeleton(ch).map_or_else(
         || {
             let mut sdf = GlyphSdf::empty();
             sdf.advance = KANA_ADVANCE;
@@ -34,10 +34,22 @@ pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
     )
 }
 
+/// Does a skeleton exist for this hiragana character?
+#[must_use]
+pub fn covers(ch: char) -> bool {
+    skeleton(ch).is_some()
+}
+
+/// Skeleton of `ch` in the builder shape shared by every skeleton module.
+/// Kana do not depend on the pen, so `gen` is unused.
+pub(crate) fn build_skeleton(_gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    skeleton(ch)
+}
+
 /// Build the stroke skeleton for a hiragana character. Returns `None` for
 /// characters that aren't implemented yet (so the caller can render a
 /// placeholder rather than panic).
-fn build_skeleton(ch: char) -> Option<GlyphSkeleton> {
+fn skeleton(ch: char) -> Option<GlyphSkeleton> {
     match ch {
         'あ' => Some(build_a()),
         'い' => Some(build_i()),
@@ -173,19 +185,19 @@ fn append_handakuten(skel: &mut GlyphSkeleton) {
 }
 
 fn with_dakuten(base: char) -> Option<GlyphSkeleton> {
-    let mut skel = build_skeleton(base)?;
+    let mut skel = skeleton(base)?;
     append_dakuten(&mut skel);
     Some(skel)
 }
 
 fn with_handakuten(base: char) -> Option<GlyphSkeleton> {
-    let mut skel = build_skeleton(base)?;
+    let mut skel = skeleton(base)?;
     append_handakuten(&mut skel);
     Some(skel)
 }
 
 fn with_small(base: char) -> Option<GlyphSkeleton> {
-    let mut skel = build_skeleton(base)?;
+    let mut skel = skeleton(base)?;
     // Scale to ~65% and push toward the upper-right (the typical position
     // for small kana when used in 拗音 like きゃ, きゅ, きょ).
     transform_skeleton(&mut skel, 0.65, 0.3, 0.2);
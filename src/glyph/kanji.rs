@@ -16,7 +16,7 @@ use alloc::vec::Vec;
 use crate::cjk::ids::{parse, Ids};
 use crate::cjk::ids_db::lookup as kanji_lookup;
 use crate::cjk::layout::CompositionLayout;
-use crate::glyph::{GlyphGenerator, GlyphSdf, GlyphSkeleton, MAX_GLYPH_STROKES};
+use crate::glyph::{GlyphGenerator, GlyphSdf, GlyphSkeleton};
 use crate::param::MetaFontParams;
 use crate::stroke::{Point2, Stroke};
 
@@ -45,38 +45,45 @@ impl Bbox {
 /// Kanji の SDF を生成する。未登録の漢字は空 SDF を返す。
 #[must_use]
 pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
-    let Some(def) = kanji_lookup(ch) else {
-        let mut sdf = GlyphSdf::empty();
-        sdf.advance = KANJI_ADVANCE;
-        return sdf;
-    };
-    let Ok(tree) = parse(def.ids) else {
-        let mut sdf = GlyphSdf::empty();
-        sdf.advance = KANJI_ADVANCE;
-        return sdf;
-    };
+    skeleton(ch).map_or_else(
+        || {
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = KANJI_ADVANCE;
+            sdf
+        },
+        |skel| GlyphGenerator::new(params).generate_from_skeleton(&skel),
+    )
+}
 
-    let mut skel = GlyphSkeleton::empty();
-    skel.advance = KANJI_ADVANCE;
-    let bbox = Bbox::unit();
-    let mut strokes_added = 0usize;
-    add_ids_strokes(&tree, bbox, &mut skel, &mut strokes_added);
+/// IDS 合成でスケルトンを生成できる漢字か。
+#[must_use]
+pub fn covers(ch: char) -> bool {
+    skeleton(ch).is_some()
+}
 
-    if strokes_added == 0 {
-        let mut sdf = GlyphSdf::empty();
-        sdf.advance = KANJI_ADVANCE;
-        return sdf;
-    }
+/// `gen` を取る共通のビルダー形。漢字の骨格はペンに依存しないので
+/// `gen` は使わない。
+pub(crate) fn build_skeleton(_gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    skeleton(ch)
+}
+
+/// IDS ツリーからスケルトンを組み立てる。未登録・パース失敗・ストローク
+/// 0 本の場合は `None`。
+fn skeleton(ch: char) -> Option<GlyphSkeleton> {
+    let def = kanji_lookup(ch)?;
+    let tree = parse(def.ids).ok()?;
 
-    let gen = GlyphGenerator::new(params);
-    gen.generate_from_skeleton(&skel)
+    let mut skel = GlyphSkeleton::empty();
+    skel.advance = KANJI_ADVANCE;
+    add_ids_strokes(&tree, Bbox::unit(), &mut skel);
+    (skel.stroke_count > 0).then_some(skel)
 }
 
 /// IDS ツリーを再帰的に展開して、各部品のストロークを `skel` に追加する。
-fn add_ids_strokes(tree: &Ids, bbox: Bbox, skel: &mut GlyphSkeleton, count: &mut usize) {
+fn add_ids_strokes(tree: &Ids, bbox: Bbox, skel: &mut GlyphSkeleton) {
     match tree {
         Ids::Leaf(ch) => {
-            add_component_strokes(*ch, bbox, skel, count);
+            add_component_strokes(*ch, bbox, skel);
         }
         Ids::Binary {
             layout,
@@ -84,8 +91,8 @@ fn add_ids_strokes(tree: &Ids, bbox: Bbox, skel: &mut GlyphSkeleton, count: &mut
             second,
         } => {
             let (b1, b2) = split_binary(bbox, *layout);
-            add_ids_strokes(first, b1, skel, count);
-            add_ids_strokes(second, b2, skel, count);
+            add_ids_strokes(first, b1, skel);
+            add_ids_strokes(second, b2, skel);
         }
         Ids::Ternary {
             layout,
@@ -94,9 +101,9 @@ fn add_ids_strokes(tree: &Ids, bbox: Bbox, skel: &mut GlyphSkeleton, count: &mut
             third,
         } => {
             let (b1, b2, b3) = split_ternary(bbox, *layout);
-            add_ids_strokes(first, b1, skel, count);
-            add_ids_strokes(second, b2, skel, count);
-            add_ids_strokes(third, b3, skel, count);
+            add_ids_strokes(first, b1, skel);
+            add_ids_strokes(second, b2, skel);
+            add_ids_strokes(third, b3, skel);
         }
     }
 }
@@ -282,25 +289,25 @@ fn inset(b: Bbox, margin: f32) -> Bbox {
 
 /// 単一の部品文字を、指定 bbox に収まるようにストロークを `skel` に追加する。
 /// S5 時点では、最頻出の部首 (一, 二, 木, 日, 月, 口, 田, 力, 女, 子, 玉, 囗, 冂) について
-/// 簡易スケルトンを持つ。それ以外の部首は placeholder の正方形枠を描画する。
-fn add_component_strokes(ch: char, b: Bbox, skel: &mut GlyphSkeleton, count: &mut usize) {
-    let strokes = component_strokes(ch, b);
+/// 簡易スケルトンを持つ。それ以外の部首は placeholder の正方形枠を描画し、
+/// `skel.placeholders` に数える。上限を超えたストロークは `skel.dropped` に数える。
+fn add_component_strokes(ch: char, b: Bbox, skel: &mut GlyphSkeleton) {
+    let strokes = component_strokes(ch, b).unwrap_or_else(|| {
+        skel.placeholders += 1;
+        placeholder_strokes(b)
+    });
     for stroke in strokes {
-        if *count >= MAX_GLYPH_STROKES {
-            return;
-        }
         skel.add_stroke(stroke);
-        *count += 1;
     }
 }
 
 /// 部品 char の Bezier ストローク列を返す。
 ///
 /// `m(u, v)` は正規化座標 `[0, 1]^2` を bbox 内ワールド座標に写像するヘルパ。
-/// S6.6 までに 40+ の主要部品を定義し、未定義は矩形 placeholder で代替する。
-fn component_strokes(ch: char, b: Bbox) -> Vec<Stroke> {
+/// S6.6 までに 40+ の主要部品を定義。未定義の部品は `None`。
+fn component_strokes(ch: char, b: Bbox) -> Option<Vec<Stroke>> {
     let m = |u: f32, v: f32| Point2::new(b.x + u * b.w, b.y + v * b.h);
-    match ch {
+    let strokes = match ch {
         // ---- Stroke-count 1-2 atomic shapes ---------------------------------
         '一' => alloc::vec![Stroke::line(m(0.1, 0.5), m(0.9, 0.5))],
         '二' => alloc::vec![
@@ -2248,13 +2255,20 @@ fn component_strokes(ch: char, b: Bbox) -> Vec<Stroke> {
             Stroke::line(m(0.1, 0.1), m(0.9, 0.1)),
         ],
         // ---- placeholder fallback ------------------------------------------
-        _ => alloc::vec![
-            Stroke::line(m(0.2, 0.85), m(0.8, 0.85)),
-            Stroke::line(m(0.8, 0.85), m(0.8, 0.15)),
-            Stroke::line(m(0.2, 0.15), m(0.8, 0.15)),
-            Stroke::line(m(0.2, 0.85), m(0.2, 0.15)),
-        ],
-    }
+        _ => return None,
+    };
+    Some(strokes)
+}
+
+/// 未定義部品の代替となる矩形枠。
+fn placeholder_strokes(b: Bbox) -> Vec<Stroke> {
+    let m = |u: f32, v: f32| Point2::new(b.x + u * b.w, b.y + v * b.h);
+    alloc::vec![
+        Stroke::line(m(0.2, 0.85), m(0.8, 0.85)),
+        Stroke::line(m(0.8, 0.85), m(0.8, 0.15)),
+        Stroke::line(m(0.2, 0.15), m(0.8, 0.15)),
+        Stroke::line(m(0.2, 0.85), m(0.2, 0.15)),
+    ]
 }
 
 #[cfg(test)]
@@ -2298,6 +2312,21 @@ mod tests {
         assert!(inside);
     }
 
+    #[test]
+    fn undefined_component_counts_as_placeholder() {
+        // 任 = ⿰亻壬 — 壬 has no component skeleton yet.
+        let skel = skeleton('任').unwrap();
+        assert!(skel.placeholders > 0);
+        assert_eq!(skeleton('明').unwrap().placeholders, 0);
+    }
+
+    #[test]
+    fn stroke_overflow_is_counted() {
+        let skel = skeleton('検').unwrap();
+        assert_eq!(skel.stroke_count, crate::glyph::MAX_GLYPH_STROKES);
+        assert!(skel.dropped > 0);
+    }
+
     #[test]
     fn bbox_split_left_right_halves() {
         let (l, r) = split_binary(Bbox::unit(), CompositionLayout::LeftRight);
@@ -1,6 +1,6 @@
 //! Katakana glyph rendering — U+30A0 to U+30FF
 //!
-//! S4 implementation. 46 清音 + 濁音 + 半濁音 + 小書き + 長音記号。
+//! S4 implementation. 46 清音 + 濁音 + 半濁音 + 小書き + 長音記号 + 中黒。
 //! See `docs/CJK_KANA_SPEC.md`.
 //!
 //! License: MIT
@@ -16,7 +16,7 @@ const KANA_ADVANCE: f32 = 1.0;
 /// Generate the SDF for a katakana character.
 #[must_use]
 pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
-    build_skeleton(ch).map_or_else(
+    skeleton(ch).map_or_else(
         || {
             let mut sdf = GlyphSdf::empty();
             sdf.advance = KANA_ADVANCE;
@@ -29,7 +29,19 @@ pub fn generate(ch: char, params: &MetaFontParams) -> GlyphSdf {
     )
 }
 
-fn build_skeleton(ch: char) -> Option<GlyphSkeleton> {
+/// Does a skeleton exist for this katakana character?
+#[must_use]
+pub fn covers(ch: char) -> bool {
+    skeleton(ch).is_some()
+}
+
+/// Skeleton of `ch` in the builder shape shared by every skeleton module.
+/// Katakana do not depend on the pen, so `gen` is unused.
+pub(crate) fn build_skeleton(_gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    skeleton(ch)
+}
+
+fn skeleton(ch: char) -> Option<GlyphSkeleton> {
     match ch {
         'ア' => Some(build_a()),
         'イ' => Some(build_i()),
@@ -78,6 +90,7 @@ fn build_skeleton(ch: char) -> Option<GlyphSkeleton> {
         'ヲ' => Some(build_wo()),
         'ン' => Some(build_n()),
         'ー' => Some(build_choon()),
+        '・' => Some(build_nakaguro()),
         // Dakuten
         'ガ' => with_dakuten('カ'),
         'ギ' => with_dakuten('キ'),
@@ -162,19 +175,19 @@ fn append_handakuten(skel: &mut GlyphSkeleton) {
 }
 
 fn with_dakuten(base: char) -> Option<GlyphSkeleton> {
-    let mut skel = build_skeleton(base)?;
+    let mut skel = skeleton(base)?;
     append_dakuten(&mut skel);
     Some(skel)
 }
 
 fn with_handakuten(base: char) -> Option<GlyphSkeleton> {
-    let mut skel = build_skeleton(base)?;
+    let mut skel = skeleton(base)?;
     append_handakuten(&mut skel);
     Some(skel)
 }
 
 fn with_small(base: char) -> Option<GlyphSkeleton> {
-    let mut skel = build_skeleton(base)?;
+    let mut skel = skeleton(base)?;
     transform_skeleton(&mut skel, 0.65, 0.3, 0.2);
     skel.advance = KANA_ADVANCE * 0.65;
     Some(skel)
@@ -1100,6 +1113,14 @@ fn build_choon() -> GlyphSkeleton {
     s
 }
 
+fn build_nakaguro() -> GlyphSkeleton {
+    // ・ — centred dot
+    let mut s = GlyphSkeleton::empty();
+    s.advance = KANA_ADVANCE;
+    s.add_stroke(Stroke::line(Point2::new(0.47, 0.5), Point2::new(0.53, 0.5)));
+    s
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1109,7 +1130,7 @@ mod tests {
         'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ',
         'タ', 'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ',
         'マ', 'ミ', 'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ヲ',
-        'ン', 'ー', // Dakuten
+        'ン', 'ー', '・', // Dakuten
         'ガ', 'ギ', 'グ', 'ゲ', 'ゴ', 'ザ', 'ジ', 'ズ', 'ゼ', 'ゾ', 'ダ', 'ヂ', 'ヅ', 'デ', 'ド',
         'バ', 'ビ', 'ブ', 'ベ', 'ボ', 'ヴ', // Handakuten
         'パ', 'ピ', 'プ', 'ペ', 'ポ', // Small kana
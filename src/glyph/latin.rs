@@ -0,0 +1,912 @@
+//! Latin-1 Supplement and Latin Extended-A glyphs (U+00A0 to U+017F)
+//!
+//! Accented letters are composed from an ASCII base skeleton and a
+//! diacritic mark, using the canonical decompositions in
+//! [`crate::composite`]. Letters without a decomposition (ß, ø, æ, đ, ł,
+//! þ, ...) and the Latin-1 symbols get their own skeletons, mostly built
+//! from ASCII parts.
+//!
+//! Everything is composed upright and slanted once at the end, so marks
+//! stay centred over their base under italic styles.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use super::helpers::{add_ellipse, add_half_arc};
+use super::variants::SUPERIOR_SCALE;
+use super::{GlyphGenerator, GlyphSkeleton};
+use crate::composite::{self, DiacriticPosition, DiacriticType};
+use crate::glyph::dispatcher::LATIN_RANGE;
+use crate::stroke::{Point2, Stroke};
+
+/// Gap between a base letter's skeleton and an attached mark, on top of
+/// the pen width.
+const MARK_GAP: f32 = 0.05;
+/// Length of the short stroke used as a dot (matches `i`).
+const DOT_LEN: f32 = 0.02;
+/// Advance of the no-break space.
+const NBSP_ADVANCE: f32 = 0.25;
+
+/// Does the Latin generator draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    let cp = ch as u32;
+    cp >= LATIN_RANGE.0 && cp <= LATIN_RANGE.1
+}
+
+/// Skeleton of a Latin-1 Supplement / Latin Extended-A character, or
+/// `None` outside U+00A0 to U+017F.
+pub(crate) fn build_skeleton(gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    if !covers(ch) {
+        return None;
+    }
+    Some(gen.upright(|up| match composite::decompose_char(ch) {
+        Some(c) => up.compose_accented(c.base_char, c.diacritics[0]),
+        None => up.build_latin_special(ch),
+    }))
+}
+
+impl GlyphGenerator {
+    /// Build with `slant` forced to zero, then slant the finished skeleton
+    /// as a whole. Composed glyphs use this so parts stay aligned.
+    pub(super) fn upright(&self, build: impl FnOnce(&Self) -> GlyphSkeleton) -> GlyphSkeleton {
+        let up = Self {
+            slant: 0.0,
+            ..*self
+        };
+        let mut skel = build(&up);
+        for stroke in &mut skel.strokes[..skel.stroke_count] {
+            *stroke = stroke.apply_slant(self.slant);
+        }
+        skel
+    }
+
+    /// ASCII skeleton (upright when called inside [`Self::upright`]).
+    pub(super) fn ascii(&self, ch: char) -> GlyphSkeleton {
+        self.build_skeleton(ch as u8)
+    }
+
+    /// `skel` with `mark` centred above or below it.
+    pub(super) fn with_mark(
+        &self,
+        mut skel: GlyphSkeleton,
+        mark: DiacriticType,
+        capital: bool,
+    ) -> GlyphSkeleton {
+        let (lo, hi) = extent(&skel);
+        self.add_mark(&mut skel, mark, lo, hi, capital);
+        skel
+    }
+
+    fn mark_gap(&self) -> f32 {
+        MARK_GAP + 2.0 * self.pen.base_width
+    }
+
+    fn compose_accented(&self, base: char, mark: DiacriticType) -> GlyphSkeleton {
+        let above = mark.default_position() == DiacriticPosition::Above;
+        let mut skel = match base {
+            // Marks above replace the dot of i and j.
+            'i' | 'j' if above => dotless(self.ascii(base)),
+            _ => self.ascii(base),
+        };
+        let (lo, hi) = extent(&skel);
+        let capital = base.is_ascii_uppercase();
+        let tall = matches!(base, 'd' | 'l' | 't' | 'L');
+        match (base, mark) {
+            // ď ľ ť Ľ: the caron becomes a vertical comma beside the stem.
+            (_, DiacriticType::Caron) if tall => {
+                let x = if base == 'L' {
+                    lo.x + 0.14
+                } else {
+                    hi.x + 0.06
+                };
+                let top = if base == 't' { self.cap_height } else { hi.y };
+                skel.add_stroke(Stroke::line(
+                    Point2::new(x + 0.02, top),
+                    Point2::new(x - 0.01, top - 0.12),
+                ));
+                skel.advance += if base == 'L' { 0.0 } else { 0.08 };
+            }
+            // ģ: the cedilla is drawn as a turned comma above.
+            ('g', DiacriticType::Cedilla) => {
+                self.add_mark(&mut skel, DiacriticType::Acute, lo, hi, false);
+            }
+            _ => self.add_mark(&mut skel, mark, lo, hi, capital),
+        }
+        skel
+    }
+
+    /// Attach `mark` to a base whose skeleton spans `lo..hi`.
+    pub(super) fn add_mark(
+        &self,
+        skel: &mut GlyphSkeleton,
+        mark: DiacriticType,
+        lo: Point2,
+        hi: Point2,
+        capital: bool,
+    ) {
+        let w = self.x_height * 0.34;
+        let h = self.x_height * if capital { 0.18 } else { 0.24 };
+        let cx = (lo.x + hi.x) * 0.5;
+        let gap = self.mark_gap();
+        let (x0, y0) = match mark.default_position() {
+            DiacriticPosition::Above => (cx - w * 0.5, hi.y + gap),
+            DiacriticPosition::Below if mark == DiacriticType::Ogonek => (hi.x - w * 0.7, -h),
+            DiacriticPosition::Below => (cx - w * 0.5, -h),
+        };
+        let at = |u: f32, v: f32| Point2::new(x0 + u * w, y0 + v * h);
+        let line = |a: (f32, f32), b: (f32, f32)| Stroke::line(at(a.0, a.1), at(b.0, b.1));
+        let dot = |u: f32| {
+            let p = at(u, 0.4);
+            Stroke::line(p, Point2::new(p.x, p.y + DOT_LEN))
+        };
+        match mark {
+            DiacriticType::Acute => skel.add_stroke(line((0.3, 0.0), (0.75, 1.0))),
+            DiacriticType::Grave => skel.add_stroke(line((0.25, 1.0), (0.7, 0.0))),
+            DiacriticType::Circumflex => {
+                skel.add_stroke(line((0.0, 0.0), (0.5, 1.0)));
+                skel.add_stroke(line((0.5, 1.0), (1.0, 0.0)));
+            }
+            DiacriticType::Caron => {
+                skel.add_stroke(line((0.0, 1.0), (0.5, 0.0)));
+                skel.add_stroke(line((0.5, 0.0), (1.0, 1.0)));
+            }
+            DiacriticType::Tilde => skel.add_stroke(Stroke::new(
+                at(0.0, 0.2),
+                at(0.35, 1.1),
+                at(0.65, -0.1),
+                at(1.0, 0.8),
+            )),
+            DiacriticType::Macron => skel.add_stroke(line((0.0, 0.5), (1.0, 0.5))),
+            DiacriticType::Breve => skel.add_stroke(Stroke::new(
+                at(0.0, 1.0),
+                at(0.05, 0.0),
+                at(0.95, 0.0),
+                at(1.0, 1.0),
+            )),
+            DiacriticType::DotAbove => skel.add_stroke(dot(0.5)),
+            DiacriticType::Diaeresis => {
+                skel.add_stroke(dot(0.15));
+                skel.add_stroke(dot(0.85));
+            }
+            DiacriticType::Ring => {
+                let r = h * 0.5;
+                add_ellipse(skel, cx, y0 + r, r, r, 0.0);
+            }
+            DiacriticType::DoubleAcute => {
+                skel.add_stroke(line((0.0, 0.0), (0.35, 1.0)));
+                skel.add_stroke(line((0.55, 0.0), (0.9, 1.0)));
+            }
+            DiacriticType::Cedilla => {
+                skel.add_stroke(line((0.5, 1.0), (0.5, 0.6)));
+                skel.add_stroke(Stroke::new(
+                    at(0.5, 0.6),
+                    at(1.0, 0.6),
+                    at(1.0, 0.0),
+                    at(0.15, 0.05),
+                ));
+            }
+            DiacriticType::Ogonek => skel.add_stroke(Stroke::new(
+                at(0.7, 1.0),
+                at(0.1, 0.7),
+                at(0.2, 0.0),
+                at(0.9, 0.1),
+            )),
+        }
+    }
+
+    /// Characters without a canonical decomposition.
+    fn build_latin_special(&self, ch: char) -> GlyphSkeleton {
+        let cap = self.cap_height;
+        let xh = self.x_height;
+        let asc = cap * 1.05;
+        let desc = cap * 0.25;
+        match ch {
+            // No-break space (blank, unlike the ASCII space placeholder),
+            // soft hyphen
+            '\u{A0}' => {
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = NBSP_ADVANCE;
+                skel
+            }
+            '\u{AD}' => self.ascii('-'),
+            '¡' => self.inverted('!'),
+            '¿' => self.inverted('?'),
+            '¢' => {
+                let mut skel = self.ascii('c');
+                let (lo, hi) = extent(&skel);
+                let cx = (lo.x + hi.x) * 0.5;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(cx, -0.06),
+                    Point2::new(cx, xh + 0.06),
+                ));
+                skel
+            }
+            '£' => {
+                let w = cap * 0.55;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.1;
+                skel.add_stroke(Stroke::new(
+                    Point2::new(w * 0.9, cap * 0.85),
+                    Point2::new(w * 0.85, cap),
+                    Point2::new(w * 0.3, cap),
+                    Point2::new(w * 0.3, cap * 0.75),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(w * 0.3, cap * 0.75),
+                    Point2::new(w * 0.3, cap * 0.15),
+                ));
+                skel.add_stroke(Stroke::new(
+                    Point2::new(w * 0.3, cap * 0.15),
+                    Point2::new(w * 0.3, 0.05),
+                    Point2::new(0.1, 0.0),
+                    Point2::new(0.04, 0.0),
+                ));
+                skel.add_stroke(Stroke::line(Point2::new(0.04, 0.0), Point2::new(w, 0.0)));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.06, cap * 0.45),
+                    Point2::new(w * 0.65, cap * 0.45),
+                ));
+                skel
+            }
+            '¤' => {
+                let r = xh * 0.25;
+                let c = Point2::new(r + 0.1, xh * 0.5);
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 2.0 * r + 0.2;
+                add_ellipse(&mut skel, c.x, c.y, r, r, 0.0);
+                let (d, t) = (r * 0.7, r * 1.2);
+                for (sx, sy) in [(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)] {
+                    skel.add_stroke(Stroke::line(
+                        Point2::new(c.x + sx * d, c.y + sy * d),
+                        Point2::new(c.x + sx * t, c.y + sy * t),
+                    ));
+                }
+                skel
+            }
+            '¥' => {
+                let mut skel = self.ascii('Y');
+                let (lo, hi) = extent(&skel);
+                for y in [cap * 0.3, cap * 0.15] {
+                    skel.add_stroke(Stroke::line(
+                        Point2::new(lo.x + 0.06, y),
+                        Point2::new(hi.x - 0.06, y),
+                    ));
+                }
+                skel
+            }
+            '¦' => {
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 0.25;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.12, -desc * 0.5),
+                    Point2::new(0.12, cap * 0.4),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.12, cap * 0.6),
+                    Point2::new(0.12, cap),
+                ));
+                skel
+            }
+            '§' => {
+                let s = self.ascii('s');
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = s.advance;
+                append(&mut skel, &s, 0.8, 0.02, cap - xh * 0.8);
+                append(&mut skel, &s, 0.8, 0.02, -desc * 0.3);
+                skel
+            }
+            '©' | '®' => {
+                let r = cap * 0.5;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 2.0 * r + 0.12;
+                add_ellipse(&mut skel, r + 0.06, r, r, r, 0.0);
+                let inner = self.ascii(if ch == '©' { 'C' } else { 'R' });
+                let (lo, hi) = extent(&inner);
+                let s = 0.5;
+                let dx = r + 0.06 - (lo.x + hi.x) * 0.5 * s;
+                append(&mut skel, &inner, s, dx, r - cap * 0.5 * s);
+                skel
+            }
+            'ª' | 'º' => {
+                let base = self.ascii(if ch == 'ª' { 'a' } else { 'o' });
+                let s = SUPERIOR_SCALE;
+                let y = cap - xh * s;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = base.advance * s + 0.06;
+                append(&mut skel, &base, s, 0.03, y);
+                let (lo, hi) = extent(&skel);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x, y - 0.08),
+                    Point2::new(hi.x, y - 0.08),
+                ));
+                skel
+            }
+            '«' | '»' => {
+                let chevron = self.ascii(if ch == '«' { '<' } else { '>' });
+                let (lo, hi) = extent(&chevron);
+                let s = 0.6;
+                let dy = xh * 0.5 - (lo.y + hi.y) * 0.5 * s;
+                let step = (hi.x - lo.x) * s * 0.8;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = chevron.advance * s + step;
+                append(&mut skel, &chevron, s, 0.0, dy);
+                append(&mut skel, &chevron, s, step, dy);
+                skel
+            }
+            '¬' => {
+                let w = xh * 0.9;
+                let y = xh * 0.55;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.1;
+                skel.add_stroke(Stroke::line(Point2::new(0.05, y), Point2::new(w, y)));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(w, y),
+                    Point2::new(w, y - xh * 0.3),
+                ));
+                skel
+            }
+            '°' => {
+                let r = xh * 0.14;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 2.0 * r + 0.12;
+                add_ellipse(&mut skel, r + 0.06, cap - r, r, r, 0.0);
+                skel
+            }
+            '±' => {
+                let plus = self.ascii('+');
+                let (lo, hi) = extent(&plus);
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = plus.advance;
+                append(&mut skel, &plus, 1.0, 0.0, 0.12 - lo.y);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x, 0.02),
+                    Point2::new(hi.x, 0.02),
+                ));
+                skel
+            }
+            '²' | '³' | '¹' => {
+                let digit = match ch {
+                    '¹' => '1',
+                    '²' => '2',
+                    _ => '3',
+                };
+                self.superior(digit)
+            }
+            '¼' | '½' | '¾' => {
+                let (num, den) = match ch {
+                    '¼' => ('1', '4'),
+                    '½' => ('1', '2'),
+                    _ => ('3', '4'),
+                };
+                let (n, d) = (self.ascii(num), self.ascii(den));
+                let s = 0.5;
+                let mut skel = GlyphSkeleton::empty();
+                append(&mut skel, &n, s, 0.02, cap * (1.0 - s));
+                let split = n.advance * s + 0.04;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(split - 0.06, 0.0),
+                    Point2::new(split + 0.1, cap),
+                ));
+                append(&mut skel, &d, s, split + 0.06, 0.0);
+                skel.advance = split + 0.1 + d.advance * s;
+                skel
+            }
+            '´' => self.spacing_mark(DiacriticType::Acute),
+            '¨' => self.spacing_mark(DiacriticType::Diaeresis),
+            '¯' => self.spacing_mark(DiacriticType::Macron),
+            '¸' => self.spacing_mark(DiacriticType::Cedilla),
+            'µ' => {
+                let mut skel = self.ascii('u');
+                let (lo, _) = extent(&skel);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x, 0.0),
+                    Point2::new(lo.x, -desc),
+                ));
+                skel
+            }
+            '¶' => {
+                let w = cap * 0.5;
+                let (x1, x2) = (w * 0.55, w);
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.1;
+                skel.add_stroke(Stroke::new(
+                    Point2::new(x1, cap),
+                    Point2::new(0.0, cap),
+                    Point2::new(0.0, cap * 0.45),
+                    Point2::new(x1, cap * 0.45),
+                ));
+                skel.add_stroke(Stroke::line(Point2::new(x1, cap), Point2::new(x2, cap)));
+                skel.add_stroke(Stroke::line(Point2::new(x1, cap), Point2::new(x1, -desc)));
+                skel.add_stroke(Stroke::line(Point2::new(x2, cap), Point2::new(x2, -desc)));
+                skel
+            }
+            '·' => {
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 0.25;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.12, xh * 0.5),
+                    Point2::new(0.12, xh * 0.5 + DOT_LEN),
+                ));
+                skel
+            }
+            '×' => {
+                let r = xh * 0.25;
+                let c = Point2::new(r + 0.08, xh * 0.5);
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 2.0 * r + 0.16;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(c.x - r, c.y - r),
+                    Point2::new(c.x + r, c.y + r),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(c.x - r, c.y + r),
+                    Point2::new(c.x + r, c.y - r),
+                ));
+                skel
+            }
+            '÷' => {
+                let w = xh * 0.8;
+                let y = xh * 0.5;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.1;
+                skel.add_stroke(Stroke::line(Point2::new(0.05, y), Point2::new(w, y)));
+                for dy in [-0.16, 0.16 - DOT_LEN] {
+                    let p = Point2::new((w + 0.05) * 0.5, y + dy);
+                    skel.add_stroke(Stroke::line(p, Point2::new(p.x, p.y + DOT_LEN)));
+                }
+                skel
+            }
+            'Æ' => {
+                let m = cap * 0.5;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = m + cap * 0.45 + 0.1;
+                skel.add_stroke(Stroke::line(Point2::new(0.04, 0.0), Point2::new(m, cap)));
+                skel.add_stroke(Stroke::line(Point2::new(m, 0.0), Point2::new(m, cap)));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(m, cap),
+                    Point2::new(m + cap * 0.42, cap),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(m, cap * 0.5),
+                    Point2::new(m + cap * 0.36, cap * 0.5),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(m, 0.0),
+                    Point2::new(m + cap * 0.42, 0.0),
+                ));
+                let t = 0.35;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.04 + (m - 0.04) * t, cap * t),
+                    Point2::new(m, cap * t),
+                ));
+                skel
+            }
+            'Œ' => {
+                let m = cap * 0.55;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = m + cap * 0.45 + 0.1;
+                add_half_arc(&mut skel, m, cap * 0.5, m - 0.05, cap * 0.5, 1, 0.0);
+                skel.add_stroke(Stroke::line(Point2::new(m, 0.0), Point2::new(m, cap)));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(m, cap),
+                    Point2::new(m + cap * 0.42, cap),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(m, cap * 0.5),
+                    Point2::new(m + cap * 0.36, cap * 0.5),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(m, 0.0),
+                    Point2::new(m + cap * 0.42, 0.0),
+                ));
+                skel
+            }
+            'æ' => self.ligature('a', 'e'),
+            'œ' => self.ligature('o', 'e'),
+            'Ø' | 'ø' => {
+                let mut skel = self.ascii(if ch == 'Ø' { 'O' } else { 'o' });
+                let (lo, hi) = extent(&skel);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x - 0.02, lo.y - 0.04),
+                    Point2::new(hi.x + 0.02, hi.y + 0.04),
+                ));
+                skel
+            }
+            'Ð' | 'Đ' => {
+                let mut skel = self.ascii('D');
+                let (lo, _) = extent(&skel);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x - 0.06, cap * 0.5),
+                    Point2::new(lo.x + 0.16, cap * 0.5),
+                ));
+                skel
+            }
+            'đ' => {
+                let mut skel = self.ascii('d');
+                let (_, hi) = extent(&skel);
+                let y = asc * 0.82;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(hi.x - 0.14, y),
+                    Point2::new(hi.x + 0.04, y),
+                ));
+                skel
+            }
+            'Ħ' => {
+                let mut skel = self.ascii('H');
+                let (lo, hi) = extent(&skel);
+                let y = cap * 0.78;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x - 0.04, y),
+                    Point2::new(hi.x + 0.04, y),
+                ));
+                skel
+            }
+            'ħ' => {
+                let mut skel = self.ascii('h');
+                let (lo, _) = extent(&skel);
+                let y = asc * 0.82;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x - 0.04, y),
+                    Point2::new(lo.x + 0.14, y),
+                ));
+                skel
+            }
+            'Ŧ' | 'ŧ' => {
+                let mut skel = self.ascii(if ch == 'Ŧ' { 'T' } else { 't' });
+                let (lo, hi) = extent(&skel);
+                let cx = (lo.x + hi.x) * 0.5;
+                let y = if ch == 'Ŧ' { cap * 0.5 } else { xh * 0.45 };
+                skel.add_stroke(Stroke::line(
+                    Point2::new(cx - 0.1, y),
+                    Point2::new(cx + 0.1, y),
+                ));
+                skel
+            }
+            'Ł' => {
+                let mut skel = self.ascii('L');
+                let (lo, _) = extent(&skel);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x - 0.06, cap * 0.3),
+                    Point2::new(lo.x + 0.14, cap * 0.55),
+                ));
+                skel
+            }
+            'ł' => {
+                let mut skel = self.ascii('l');
+                let (lo, _) = extent(&skel);
+                skel.add_stroke(Stroke::line(
+                    Point2::new(lo.x - 0.1, asc * 0.38),
+                    Point2::new(lo.x + 0.1, asc * 0.6),
+                ));
+                skel
+            }
+            'Ŀ' | 'ŀ' => {
+                let mut skel = self.ascii(if ch == 'Ŀ' { 'L' } else { 'l' });
+                let (lo, _) = extent(&skel);
+                let (x, y) = if ch == 'Ŀ' {
+                    (lo.x + 0.2, cap * 0.5)
+                } else {
+                    (lo.x + 0.14, xh * 0.5)
+                };
+                skel.add_stroke(Stroke::line(Point2::new(x, y), Point2::new(x, y + DOT_LEN)));
+                skel.advance += 0.06;
+                skel
+            }
+            'Þ' => {
+                let w = cap * 0.6;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.1;
+                skel.add_stroke(Stroke::line(Point2::new(0.05, 0.0), Point2::new(0.05, cap)));
+                add_half_arc(&mut skel, 0.05, cap * 0.5, w - 0.05, cap * 0.25, 0, 0.0);
+                skel
+            }
+            'þ' => {
+                let w = xh * 0.8;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.08;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.05, -desc),
+                    Point2::new(0.05, asc),
+                ));
+                add_half_arc(&mut skel, 0.05, xh * 0.5, w - 0.05, xh * 0.5, 0, 0.0);
+                skel
+            }
+            'ð' => {
+                let w = xh * 0.85;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.08;
+                add_ellipse(
+                    &mut skel,
+                    w * 0.5,
+                    xh * 0.45,
+                    w * 0.5 - 0.03,
+                    xh * 0.45,
+                    0.0,
+                );
+                skel.add_stroke(Stroke::new(
+                    Point2::new(w - 0.03, xh * 0.5),
+                    Point2::new(w - 0.03, asc * 0.75),
+                    Point2::new(w * 0.6, asc * 0.9),
+                    Point2::new(w * 0.3, asc),
+                ));
+                skel.add_stroke(Stroke::line(
+                    Point2::new(w * 0.3, asc * 0.7),
+                    Point2::new(w * 0.85, asc * 0.92),
+                ));
+                skel
+            }
+            'ß' => {
+                let w = xh * 0.85;
+                let (x0, x1, xm) = (0.05, w - 0.05, w * 0.45);
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = w + 0.08;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(x0, 0.0),
+                    Point2::new(x0, asc * 0.8),
+                ));
+                skel.add_stroke(Stroke::new(
+                    Point2::new(x0, asc * 0.8),
+                    Point2::new(x0, asc),
+                    Point2::new(x1, asc),
+                    Point2::new(x1, asc * 0.78),
+                ));
+                skel.add_stroke(Stroke::new(
+                    Point2::new(x1, asc * 0.78),
+                    Point2::new(x1, asc * 0.62),
+                    Point2::new(xm, asc * 0.6),
+                    Point2::new(xm, asc * 0.55),
+                ));
+                skel.add_stroke(Stroke::new(
+                    Point2::new(xm, asc * 0.55),
+                    Point2::new(x1 + 0.05, asc * 0.52),
+                    Point2::new(x1 + 0.05, 0.0),
+                    Point2::new(xm - 0.05, 0.0),
+                ));
+                skel
+            }
+            'ı' => dotless(self.ascii('i')),
+            'Ĳ' => self.ligature('I', 'J'),
+            'ĳ' => self.ligature('i', 'j'),
+            'ĸ' => {
+                let k = self.ascii('K');
+                let s = xh / cap;
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = k.advance * s;
+                append(&mut skel, &k, s, 0.0, 0.0);
+                skel
+            }
+            'ŉ' => {
+                let quote = self.ascii('\'');
+                let n = self.ascii('n');
+                let mut skel = GlyphSkeleton::empty();
+                append(&mut skel, &quote, 1.0, 0.0, 0.0);
+                append(&mut skel, &n, 1.0, quote.advance * 0.7, 0.0);
+                skel.advance = quote.advance * 0.7 + n.advance;
+                skel
+            }
+            'Ŋ' | 'ŋ' => {
+                let mut skel = self.ascii(if ch == 'Ŋ' { 'N' } else { 'n' });
+                let (_, hi) = extent(&skel);
+                skel.add_stroke(Stroke::new(
+                    Point2::new(hi.x, 0.0),
+                    Point2::new(hi.x, -desc),
+                    Point2::new(hi.x - 0.12, -desc),
+                    Point2::new(hi.x - 0.18, -desc * 0.8),
+                ));
+                skel
+            }
+            'ſ' => {
+                let mut skel = GlyphSkeleton::empty();
+                skel.advance = 0.3;
+                skel.add_stroke(Stroke::line(
+                    Point2::new(0.1, 0.0),
+                    Point2::new(0.1, asc * 0.8),
+                ));
+                skel.add_stroke(Stroke::new(
+                    Point2::new(0.1, asc * 0.8),
+                    Point2::new(0.1, asc),
+                    Point2::new(0.26, asc),
+                    Point2::new(0.3, asc * 0.92),
+                ));
+                skel
+            }
+            // Every other code point in the range decomposes.
+            _ => self.build_placeholder(b'?'),
+        }
+    }
+
+    /// `ch` rotated by 180° and hung from the x-height (¡, ¿).
+    fn inverted(&self, ch: char) -> GlyphSkeleton {
+        let src = self.ascii(ch);
+        let (lo, hi) = extent(&src);
+        let dy = self.x_height - (lo.y + hi.y);
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = src.advance;
+        append_mapped(&mut skel, &src, |p| {
+            Point2::new(lo.x + hi.x - p.x, lo.y + hi.y - p.y + dy)
+        });
+        skel
+    }
+
+    /// Two letters sharing a side (æ, œ, ĳ, Ĳ).
+    fn ligature(&self, first: char, second: char) -> GlyphSkeleton {
+        let a = self.ascii(first);
+        let b = self.ascii(second);
+        let (_, a_hi) = extent(&a);
+        let (b_lo, _) = extent(&b);
+        // Round letters share a stroke; ĳ and Ĳ keep a gap.
+        let overlap = if matches!(first, 'a' | 'o') {
+            0.04
+        } else {
+            -0.06
+        };
+        let dx = a_hi.x - b_lo.x - overlap;
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = dx + b.advance;
+        append(&mut skel, &a, 1.0, 0.0, 0.0);
+        append(&mut skel, &b, 1.0, dx, 0.0);
+        skel
+    }
+
+    /// A mark on an empty base (´ ¨ ¯ ¸).
+    fn spacing_mark(&self, mark: DiacriticType) -> GlyphSkeleton {
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = 0.34;
+        let lo = Point2::new(0.04, 0.0);
+        let hi = Point2::new(0.3, self.x_height);
+        self.add_mark(&mut skel, mark, lo, hi, false);
+        skel
+    }
+}
+
+/// Straight stroke between two points.
+pub(super) fn seg(x0: f32, y0: f32, x1: f32, y1: f32) -> Stroke {
+    Stroke::line(Point2::new(x0, y0), Point2::new(x1, y1))
+}
+
+/// Cubic stroke through four `(x, y)` control points.
+pub(super) fn curve(p: [(f32, f32); 4]) -> Stroke {
+    let pt = |i: usize| Point2::new(p[i].0, p[i].1);
+    Stroke::new(pt(0), pt(1), pt(2), pt(3))
+}
+
+/// `skel` without its last stroke — the dot of `i` and `j`.
+pub(super) fn dotless(mut skel: GlyphSkeleton) -> GlyphSkeleton {
+    skel.stroke_count = skel.stroke_count.saturating_sub(1);
+    skel
+}
+
+/// Bounds of the stroke centre lines (pen width not included).
+pub(super) fn extent(skel: &GlyphSkeleton) -> (Point2, Point2) {
+    const STEPS: usize = 8;
+    let mut lo = Point2::new(f32::MAX, f32::MAX);
+    let mut hi = Point2::new(f32::MIN, f32::MIN);
+    for stroke in &skel.strokes[..skel.stroke_count] {
+        for i in 0..=STEPS {
+            let p = stroke.position(i as f32 / STEPS as f32);
+            lo = Point2::new(lo.x.min(p.x), lo.y.min(p.y));
+            hi = Point2::new(hi.x.max(p.x), hi.y.max(p.y));
+        }
+    }
+    if skel.stroke_count == 0 {
+        return (Point2::ZERO, Point2::ZERO);
+    }
+    (lo, hi)
+}
+
+/// Append `src` scaled about the origin, then moved by `(dx, dy)`.
+pub(super) fn append(dst: &mut GlyphSkeleton, src: &GlyphSkeleton, scale: f32, dx: f32, dy: f32) {
+    append_mapped(dst, src, |p| {
+        Point2::new(p.x * scale + dx, p.y * scale + dy)
+    });
+}
+
+/// Append `src` with every control point moved by `f`.
+pub(super) fn append_mapped(
+    dst: &mut GlyphSkeleton,
+    src: &GlyphSkeleton,
+    f: impl Fn(Point2) -> Point2,
+) {
+    for s in &src.strokes[..src.stroke_count] {
+        dst.add_stroke(Stroke::new(f(s.p0), f(s.p1), f(s.p2), f(s.p3)));
+    }
+    dst.dropped += src.dropped;
+    dst.placeholders += src.placeholders;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::param::MetaFontParams;
+
+    fn gen() -> GlyphGenerator {
+        GlyphGenerator::new(&MetaFontParams::sans_regular())
+    }
+
+    #[test]
+    fn covers_whole_range() {
+        let gen = gen();
+        for cp in LATIN_RANGE.0..=LATIN_RANGE.1 {
+            let ch = char::from_u32(cp).unwrap();
+            let skel = build_skeleton(&gen, ch).unwrap();
+            assert_eq!(skel.placeholders, 0, "{ch} is a placeholder");
+            assert_eq!(skel.dropped, 0, "{ch} dropped strokes");
+            if ch != '\u{A0}' {
+                assert!(skel.stroke_count > 0, "{ch} has no strokes");
+            }
+        }
+        assert!(build_skeleton(&gen, 'A').is_none());
+        assert!(build_skeleton(&gen, 'Ա').is_none());
+    }
+
+    #[test]
+    fn marks_sit_above_and_below() {
+        let gen = gen();
+        let (_, e_hi) = extent(&gen.ascii('e'));
+        let (_, acute_hi) = extent(&build_skeleton(&gen, 'é').unwrap());
+        assert!(acute_hi.y > e_hi.y + MARK_GAP);
+        let (c_lo, _) = extent(&gen.ascii('c'));
+        let (cedilla_lo, _) = extent(&build_skeleton(&gen, 'ç').unwrap());
+        assert!(cedilla_lo.y < c_lo.y - 0.05);
+    }
+
+    #[test]
+    fn i_loses_its_dot_under_marks() {
+        let gen = gen();
+        let i = gen.ascii('i');
+        assert_eq!(
+            build_skeleton(&gen, 'í').unwrap().stroke_count,
+            i.stroke_count
+        );
+        assert_eq!(
+            build_skeleton(&gen, 'ı').unwrap().stroke_count,
+            i.stroke_count - 1
+        );
+        // The ogonek keeps the dot.
+        assert_eq!(
+            build_skeleton(&gen, 'į').unwrap().stroke_count,
+            i.stroke_count + 1
+        );
+    }
+
+    #[test]
+    fn slant_keeps_marks_over_base() {
+        let italic = GlyphGenerator::new(&MetaFontParams::serif_italic());
+        let upright = GlyphGenerator::new(&MetaFontParams {
+            slant: 0.0,
+            ..MetaFontParams::serif_italic()
+        });
+        let (lo, hi) = extent(&build_skeleton(&italic, 'Ê').unwrap());
+        let (ulo, uhi) = extent(&build_skeleton(&upright, 'Ê').unwrap());
+        assert!((lo.y - ulo.y).abs() < 1e-5 && (hi.y - uhi.y).abs() < 1e-5);
+        assert!(hi.x > uhi.x);
+    }
+
+    #[test]
+    fn special_letters_differ_from_base() {
+        let gen = gen();
+        for (special, base) in [('ø', 'o'), ('ł', 'l'), ('đ', 'd'), ('Ø', 'O'), ('ŋ', 'n')] {
+            let s = build_skeleton(&gen, special).unwrap();
+            assert_eq!(
+                s.stroke_count,
+                gen.ascii(base).stroke_count + 1,
+                "{special}"
+            );
+        }
+        for ch in ['ß', 'þ', 'æ', 'Æ', 'œ', 'ð'] {
+            let m = gen.metrics(&build_skeleton(&gen, ch).unwrap());
+            assert!(m.has_ink() && m.advance > 0.3, "{ch}");
+        }
+    }
+
+    #[test]
+    fn generate_accepts_latin1_bytes() {
+        let gen = gen();
+        let sdf = gen.generate(0xE9);
+        assert!(sdf.data.iter().any(|d| *d < 0.0));
+        assert_eq!(gen.generate_char('é').unwrap().data, sdf.data);
+        assert!(gen.generate_char('Ł').is_some());
+        assert!(gen.generate_char('\u{0500}').is_none());
+    }
+}
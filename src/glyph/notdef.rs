@@ -0,0 +1,205 @@
+//! `.notdef` glyph — what unsupported code points render as.
+//!
+//! A blank tile hides missing-glyph bugs, so the default is the "tofu"
+//! hex box: a rectangle with the code point's hexadecimal value drawn in
+//! two rows using the parametric digit and letter skeletons (4 digits for
+//! the BMP, 6 above it). The box and digits are drawn upright in the same
+//! em frame as the outline glyphs (`[0, 1] × [-0.2, 0.8]`).
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::glyph::font_render::fast_sqrt;
+use crate::glyph::font_render::{TILE_EM_BOTTOM, TILE_EM_LEFT, TILE_EM_RIGHT, TILE_EM_TOP};
+use crate::glyph::outline::point_line_dist_sq;
+use crate::glyph::{GlyphGenerator, GlyphSdf};
+use crate::param::MetaFontParams;
+use crate::stroke::Point2;
+
+/// How characters that no provider covers are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NotdefStyle {
+    /// Empty tile with a 0.5 em advance (the historic behaviour).
+    Blank,
+    /// Empty rectangle.
+    Box,
+    /// Rectangle containing the hexadecimal code point.
+    #[default]
+    HexBox,
+}
+
+/// Gap between the box and the side bearings (em).
+const SIDE_BEARING: f32 = 0.06;
+/// Padding between the box and the hex digits (em).
+const BOX_PADDING: f32 = 0.07;
+/// Vertical gap between the two digit rows (em).
+const ROW_GAP: f32 = 0.06;
+/// Horizontal gap between digit cells (em).
+const COLUMN_GAP: f32 = 0.04;
+/// Digit cell width relative to the (unscaled) cap height.
+const CELL_ASPECT: f32 = 0.76;
+/// Samples per skeleton stroke when flattening.
+const STROKE_STEPS: usize = 16;
+/// Advance of the plain box, matching the old blank tile.
+const BOX_ADVANCE: f32 = 0.5;
+
+/// Generate the `.notdef` tile for `ch` in `style`.
+#[must_use]
+pub fn generate(ch: char, params: &MetaFontParams, style: NotdefStyle) -> GlyphSdf {
+    let height = params.cap_height;
+    let frame_hw = (params.stroke_half_width() * 0.5).max(0.01);
+    match style {
+        NotdefStyle::Blank => {
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = BOX_ADVANCE;
+            sdf
+        }
+        NotdefStyle::Box => {
+            let x1 = BOX_ADVANCE - SIDE_BEARING;
+            rasterize(&[(frame(x1, height), frame_hw)], BOX_ADVANCE)
+        }
+        NotdefStyle::HexBox => hex_box(ch, params, frame_hw),
+    }
+}
+
+fn hex_box(ch: char, params: &MetaFontParams, frame_hw: f32) -> GlyphSdf {
+    let cp = ch as u32;
+    let digits = if cp > 0xFFFF { 6 } else { 4 };
+    let cols = digits / 2;
+
+    let height = params.cap_height;
+    let row_h = (height - 2.0 * BOX_PADDING - ROW_GAP) * 0.5;
+    let scale = row_h / height;
+    let cell_w = CELL_ASPECT * height * scale;
+    let inner_w = cols as f32 * cell_w + (cols - 1) as f32 * COLUMN_GAP;
+    let x1 = SIDE_BEARING + inner_w + 2.0 * BOX_PADDING;
+    let advance = x1 + SIDE_BEARING;
+
+    // Digits use the caller's skeletons, upright and at a weight that stays
+    // legible at the reduced size.
+    let upright = MetaFontParams {
+        slant: 0.0,
+        ..*params
+    };
+    let gen = GlyphGenerator::new(&upright);
+    let digit_hw = (params.stroke_half_width() * scale * 1.2).max(0.012);
+
+    let mut elements = alloc::vec![(frame(x1, height), frame_hw)];
+    for i in 0..digits {
+        let nibble = (cp >> (4 * (digits - 1 - i))) & 0xF;
+        let glyph = b"0123456789ABCDEF"[nibble as usize];
+        let skel = gen.build_skeleton(glyph);
+
+        let (row, col) = (i / cols, i % cols);
+        let cell_x = SIDE_BEARING + BOX_PADDING + col as f32 * (cell_w + COLUMN_GAP);
+        let dx = cell_x + (cell_w - skel.advance * scale) * 0.5;
+        let dy = height - BOX_PADDING - row_h - row as f32 * (row_h + ROW_GAP);
+        for stroke in &skel.strokes[..skel.stroke_count] {
+            let stroke = stroke.scale(scale).translate(dx, dy);
+            let points = (0..=STROKE_STEPS)
+                .map(|s| stroke.position(s as f32 / STROKE_STEPS as f32))
+                .collect();
+            elements.push((points, digit_hw));
+        }
+    }
+    rasterize(&elements, advance)
+}
+
+/// Closed rectangle from the left side bearing to `x1`, baseline to `height`.
+fn frame(x1: f32, height: f32) -> Vec<Point2> {
+    let x0 = SIDE_BEARING;
+    alloc::vec![
+        Point2::new(x0, 0.0),
+        Point2::new(x1, 0.0),
+        Point2::new(x1, height),
+        Point2::new(x0, height),
+        Point2::new(x0, 0.0),
+    ]
+}
+
+/// SDF of the union of polylines stroked with the given half-widths.
+fn rasterize(elements: &[(Vec<Point2>, f32)], advance: f32) -> GlyphSdf {
+    let mut sdf = GlyphSdf::from_field(
+        Point2::new(TILE_EM_LEFT, TILE_EM_BOTTOM),
+        Point2::new(TILE_EM_RIGHT, TILE_EM_TOP),
+        |p| {
+            elements
+                .iter()
+                .map(|(points, hw)| {
+                    let d2 = points
+                        .windows(2)
+                        .map(|w| point_line_dist_sq(p.x, p.y, (w[0].x, w[0].y), (w[1].x, w[1].y)))
+                        .fold(f32::MAX, f32::min);
+                    fast_sqrt(d2) - hw
+                })
+                .fold(f32::MAX, f32::min)
+        },
+    );
+    sdf.advance = advance;
+    sdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inside_count(sdf: &GlyphSdf) -> usize {
+        sdf.data.iter().filter(|d| **d < 0.0).count()
+    }
+
+    /// Sample at em coordinates.
+    fn at(sdf: &GlyphSdf, x: f32, y: f32) -> f32 {
+        let u = (x - TILE_EM_LEFT) / (TILE_EM_RIGHT - TILE_EM_LEFT);
+        let v = (y - TILE_EM_BOTTOM) / (TILE_EM_TOP - TILE_EM_BOTTOM);
+        sdf.sample(u, v)
+    }
+
+    #[test]
+    fn blank_is_empty_half_em() {
+        let sdf = generate('가', &MetaFontParams::sans_regular(), NotdefStyle::Blank);
+        assert_eq!(inside_count(&sdf), 0);
+        assert!((sdf.advance - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn box_draws_frame_only() {
+        let params = MetaFontParams::sans_regular();
+        let sdf = generate('가', &params, NotdefStyle::Box);
+        assert!(inside_count(&sdf) > 0);
+        // Left edge is inked, centre is hollow.
+        assert!(at(&sdf, SIDE_BEARING, params.cap_height * 0.5) < 0.0);
+        assert!(at(&sdf, 0.25, params.cap_height * 0.5) > 0.0);
+    }
+
+    #[test]
+    fn hex_box_draws_digits_inside_frame() {
+        let params = MetaFontParams::sans_regular();
+        let boxed = generate('가', &params, NotdefStyle::Box);
+        let hex = generate('가', &params, NotdefStyle::HexBox);
+        assert!(inside_count(&hex) > inside_count(&boxed) + 50);
+        for d in hex.data {
+            assert!(d.is_finite());
+        }
+    }
+
+    #[test]
+    fn hex_box_depends_on_code_point() {
+        let params = MetaFontParams::sans_regular();
+        let a = generate('\u{AC00}', &params, NotdefStyle::HexBox);
+        let b = generate('\u{AC01}', &params, NotdefStyle::HexBox);
+        assert_ne!(a.data, b.data);
+        assert!((a.advance - b.advance).abs() < 1e-6);
+    }
+
+    #[test]
+    fn supplementary_planes_get_six_digits() {
+        let params = MetaFontParams::sans_regular();
+        let bmp = generate('\u{E000}', &params, NotdefStyle::HexBox);
+        let smp = generate('\u{1F600}', &params, NotdefStyle::HexBox);
+        assert!(smp.advance > bmp.advance);
+        assert!(smp.advance <= TILE_EM_RIGHT);
+    }
+}
@@ -0,0 +1,630 @@
+//! Curve-preserving glyph outlines.
+//!
+//! Outlines are stored as a flat stream of [`PathCmd`]s in em units
+//! (TTF y-up). Every [`PathCmd::MoveTo`] starts a new contour; contours are
+//! implicitly closed back to their start point. Quadratic (TrueType) and
+//! cubic (CFF) segments are kept as-is, so the embedded tables stay small
+//! and edges remain smooth at any tile size.
+//!
+//! Geometry helpers:
+//!   - [`distance_sq`] — squared distance to the nearest point on the
+//!     outline, curves included (Newton-refined nearest point, not a chord
+//!     approximation)
+//!   - [`winding_number`] / [`is_inside`] — inside test under either
+//!     [`FillRule`], with curves intersected analytically per monotone span
+//!
+//!   - [`lerp`] / [`PathCmd::map_points`] — master interpolation and affine
+//!     transforms (width, slant)
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use super::font_render::fast_sqrt;
+
+/// One outline drawing command in em units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCmd {
+    /// Start a new contour at `(x, y)`.
+    MoveTo(f32, f32),
+    /// Straight line to `(x, y)`.
+    LineTo(f32, f32),
+    /// Quadratic Bezier: control `(x1, y1)`, end `(x, y)`.
+    QuadTo(f32, f32, f32, f32),
+    /// Cubic Bezier: controls `(x1, y1)`, `(x2, y2)`, end `(x, y)`.
+    CubicTo(f32, f32, f32, f32, f32, f32),
+}
+
+impl PathCmd {
+    /// Point the pen ends at after this command.
+    #[must_use]
+    pub const fn end_point(&self) -> (f32, f32) {
+        match *self {
+            Self::MoveTo(x, y)
+            | Self::LineTo(x, y)
+            | Self::QuadTo(_, _, x, y)
+            | Self::CubicTo(_, _, _, _, x, y) => (x, y),
+        }
+    }
+
+    /// Apply `f` to every point (end and control points) of the command.
+    /// Affine `f` maps Bezier segments exactly.
+    #[must_use]
+    pub fn map_points(self, mut f: impl FnMut(f32, f32) -> (f32, f32)) -> Self {
+        match self {
+            Self::MoveTo(x, y) => {
+                let (x, y) = f(x, y);
+                Self::MoveTo(x, y)
+            }
+            Self::LineTo(x, y) => {
+                let (x, y) = f(x, y);
+                Self::LineTo(x, y)
+            }
+            Self::QuadTo(x1, y1, x, y) => {
+                let (x1, y1) = f(x1, y1);
+                let (x, y) = f(x, y);
+                Self::QuadTo(x1, y1, x, y)
+            }
+            Self::CubicTo(x1, y1, x2, y2, x, y) => {
+                let (x1, y1) = f(x1, y1);
+                let (x2, y2) = f(x2, y2);
+                let (x, y) = f(x, y);
+                Self::CubicTo(x1, y1, x2, y2, x, y)
+            }
+        }
+    }
+}
+
+/// Point-wise interpolation `a + (b − a)·t` between two outlines.
+///
+/// Returns `None` unless the outlines are compatible — the same command
+/// sequence, as with two masters of a variable font.
+#[must_use]
+pub fn lerp(a: &[PathCmd], b: &[PathCmd], t: f32) -> Option<Vec<PathCmd>> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let mix = |p: f32, q: f32| p + (q - p) * t;
+    a.iter()
+        .zip(b)
+        .map(|(&p, &q)| match (p, q) {
+            (PathCmd::MoveTo(x0, y0), PathCmd::MoveTo(x1, y1)) => {
+                Some(PathCmd::MoveTo(mix(x0, x1), mix(y0, y1)))
+            }
+            (PathCmd::LineTo(x0, y0), PathCmd::LineTo(x1, y1)) => {
+                Some(PathCmd::LineTo(mix(x0, x1), mix(y0, y1)))
+            }
+            (PathCmd::QuadTo(a0, b0, x0, y0), PathCmd::QuadTo(a1, b1, x1, y1)) => Some(
+                PathCmd::QuadTo(mix(a0, a1), mix(b0, b1), mix(x0, x1), mix(y0, y1)),
+            ),
+            (
+                PathCmd::CubicTo(a0, b0, c0, d0, x0, y0),
+                PathCmd::CubicTo(a1, b1, c1, d1, x1, y1),
+            ) => Some(PathCmd::CubicTo(
+                mix(a0, a1),
+                mix(b0, b1),
+                mix(c0, c1),
+                mix(d0, d1),
+                mix(x0, x1),
+                mix(y0, y1),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Rule deciding which regions of an outline are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Filled where the winding number is non-zero. This is what TrueType
+    /// and CFF fonts are designed for, and renders overlapping contours
+    /// (common in variable and merged fonts) correctly.
+    #[default]
+    NonZero,
+    /// Filled where a ray crosses the outline an odd number of times.
+    EvenOdd,
+}
+
+/// A single outline segment with explicit start point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// Line `p0 → p1`.
+    Line((f32, f32), (f32, f32)),
+    /// Quadratic Bezier `p0, c, p1`.
+    Quad((f32, f32), (f32, f32), (f32, f32)),
+    /// Cubic Bezier `p0, c1, c2, p1`.
+    Cubic((f32, f32), (f32, f32), (f32, f32), (f32, f32)),
+}
+
+/// Number of coarse samples used to seed the nearest-point search.
+const QUAD_SAMPLES: usize = 8;
+const CUBIC_SAMPLES: usize = 16;
+
+/// Newton iterations applied after coarse sampling.
+const NEWTON_ITERS: usize = 5;
+
+/// Bisection iterations when intersecting a monotone curve span.
+const BISECT_ITERS: usize = 24;
+
+/// Iterate over the segments of `cmds`, including each contour's implicit
+/// closing line.
+pub fn segments(cmds: &[PathCmd]) -> impl Iterator<Item = Segment> + '_ {
+    Segments {
+        cmds,
+        idx: 0,
+        start: (0.0, 0.0),
+        cur: (0.0, 0.0),
+        open: false,
+    }
+}
+
+struct Segments<'a> {
+    cmds: &'a [PathCmd],
+    idx: usize,
+    start: (f32, f32),
+    cur: (f32, f32),
+    open: bool,
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        loop {
+            let Some(&cmd) = self.cmds.get(self.idx) else {
+                return self.close();
+            };
+            if let PathCmd::MoveTo(..) = cmd {
+                if let Some(seg) = self.close() {
+                    return Some(seg);
+                }
+            }
+            self.idx += 1;
+            let p0 = self.cur;
+            match cmd {
+                PathCmd::MoveTo(x, y) => {
+                    self.start = (x, y);
+                    self.cur = (x, y);
+                    self.open = true;
+                }
+                PathCmd::LineTo(x, y) => {
+                    self.cur = (x, y);
+                    return Some(Segment::Line(p0, (x, y)));
+                }
+                PathCmd::QuadTo(x1, y1, x, y) => {
+                    self.cur = (x, y);
+                    return Some(Segment::Quad(p0, (x1, y1), (x, y)));
+                }
+                PathCmd::CubicTo(x1, y1, x2, y2, x, y) => {
+                    self.cur = (x, y);
+                    return Some(Segment::Cubic(p0, (x1, y1), (x2, y2), (x, y)));
+                }
+            }
+        }
+    }
+}
+
+impl Segments<'_> {
+    /// Emit the closing line of the open contour, if it has non-zero length.
+    fn close(&mut self) -> Option<Segment> {
+        if !self.open {
+            return None;
+        }
+        self.open = false;
+        (self.cur != self.start).then_some(Segment::Line(self.cur, self.start))
+    }
+}
+
+impl Segment {
+    /// Point at parameter `t ∈ [0, 1]`.
+    #[must_use]
+    pub fn eval(&self, t: f32) -> (f32, f32) {
+        let u = 1.0 - t;
+        match *self {
+            Self::Line(p0, p1) => (p0.0 + (p1.0 - p0.0) * t, p0.1 + (p1.1 - p0.1) * t),
+            Self::Quad(p0, c, p1) => {
+                let (a, b, d) = (u * u, 2.0 * u * t, t * t);
+                (a * p0.0 + b * c.0 + d * p1.0, a * p0.1 + b * c.1 + d * p1.1)
+            }
+            Self::Cubic(p0, c1, c2, p1) => {
+                let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                (
+                    a * p0.0 + b * c1.0 + c * c2.0 + d * p1.0,
+                    a * p0.1 + b * c1.1 + c * c2.1 + d * p1.1,
+                )
+            }
+        }
+    }
+
+    /// First derivative at `t`.
+    fn deriv(&self, t: f32) -> (f32, f32) {
+        let u = 1.0 - t;
+        match *self {
+            Self::Line(p0, p1) => (p1.0 - p0.0, p1.1 - p0.1),
+            Self::Quad(p0, c, p1) => (
+                2.0 * (u * (c.0 - p0.0) + t * (p1.0 - c.0)),
+                2.0 * (u * (c.1 - p0.1) + t * (p1.1 - c.1)),
+            ),
+            Self::Cubic(p0, c1, c2, p1) => (
+                3.0 * (u * u * (c1.0 - p0.0) + 2.0 * u * t * (c2.0 - c1.0) + t * t * (p1.0 - c2.0)),
+                3.0 * (u * u * (c1.1 - p0.1) + 2.0 * u * t * (c2.1 - c1.1) + t * t * (p1.1 - c2.1)),
+            ),
+        }
+    }
+
+    /// Second derivative at `t`.
+    fn deriv2(&self, t: f32) -> (f32, f32) {
+        match *self {
+            Self::Line(..) => (0.0, 0.0),
+            Self::Quad(p0, c, p1) => (
+                2.0 * (p1.0 - 2.0 * c.0 + p0.0),
+                2.0 * (p1.1 - 2.0 * c.1 + p0.1),
+            ),
+            Self::Cubic(p0, c1, c2, p1) => {
+                let u = 1.0 - t;
+                (
+                    6.0 * (u * (c2.0 - 2.0 * c1.0 + p0.0) + t * (p1.0 - 2.0 * c2.0 + c1.0)),
+                    6.0 * (u * (c2.1 - 2.0 * c1.1 + p0.1) + t * (p1.1 - 2.0 * c2.1 + c1.1)),
+                )
+            }
+        }
+    }
+
+    /// Axis-aligned bounds of the control polygon (contains the curve).
+    #[must_use]
+    pub fn control_bounds(&self) -> ((f32, f32), (f32, f32)) {
+        let pts: [(f32, f32); 4] = match *self {
+            Self::Line(p0, p1) => [p0, p1, p1, p1],
+            Self::Quad(p0, c, p1) => [p0, c, p1, p1],
+            Self::Cubic(p0, c1, c2, p1) => [p0, c1, c2, p1],
+        };
+        let mut lo = pts[0];
+        let mut hi = pts[0];
+        for p in &pts[1..] {
+            lo = (lo.0.min(p.0), lo.1.min(p.1));
+            hi = (hi.0.max(p.0), hi.1.max(p.1));
+        }
+        (lo, hi)
+    }
+
+    /// Squared distance from `(px, py)` to the nearest point on the segment.
+    #[must_use]
+    pub fn distance_sq(&self, px: f32, py: f32) -> f32 {
+        let samples = match *self {
+            Self::Line(p0, p1) => return point_line_dist_sq(px, py, p0, p1),
+            Self::Quad(..) => QUAD_SAMPLES,
+            Self::Cubic(..) => CUBIC_SAMPLES,
+        };
+        let dist_at = |t: f32| {
+            let (x, y) = self.eval(t);
+            (x - px) * (x - px) + (y - py) * (y - py)
+        };
+
+        // Coarse pass: the global minimum lies in the basin of the best sample.
+        let inv = 1.0 / samples as f32;
+        let mut best_t = 0.0;
+        let mut best = dist_at(0.0);
+        for i in 1..=samples {
+            let t = i as f32 * inv;
+            let d = dist_at(t);
+            if d < best {
+                best = d;
+                best_t = t;
+            }
+        }
+
+        // Newton on f(t) = (B(t) - p) · B'(t), the derivative of the squared
+        // distance (up to a factor of 2).
+        let mut t = best_t;
+        for _ in 0..NEWTON_ITERS {
+            let (x, y) = self.eval(t);
+            let (dx, dy) = self.deriv(t);
+            let (ddx, ddy) = self.deriv2(t);
+            let (ex, ey) = (x - px, y - py);
+            let f = ex * dx + ey * dy;
+            let df = dx * dx + dy * dy + ex * ddx + ey * ddy;
+            if df.abs() < 1e-12 {
+                break;
+            }
+            t = (t - f / df).clamp(0.0, 1.0);
+        }
+        best.min(dist_at(t))
+    }
+
+    /// Accumulate the signed crossings of the ray `y = py, x > px` into
+    /// `winding` (+1 upward, −1 downward).
+    fn accumulate_winding(&self, px: f32, py: f32, winding: &mut i32) {
+        let (_, (x_hi, _)) = self.control_bounds();
+        if x_hi <= px {
+            return;
+        }
+        self.scanline_crossings(py, |x, dir| {
+            if x > px {
+                *winding += dir;
+            }
+        });
+    }
+
+    /// Report every crossing of the line `y = py` as `(x, dir)` (+1 upward,
+    /// −1 downward). `x` is capped at the control-bounds maximum so that
+    /// counting crossings with `x > px` matches
+    /// [`winding_number`] exactly.
+    pub(crate) fn scanline_crossings(&self, py: f32, mut f: impl FnMut(f32, i32)) {
+        let ((_, y_lo), (x_hi, y_hi)) = self.control_bounds();
+        if py < y_lo || py >= y_hi {
+            return;
+        }
+        // Split at the y-extrema so every span is monotone in y, then
+        // bisect each span for its (at most one) crossing.
+        let mut cuts = [0.0f32, 1.0, 1.0, 1.0];
+        let mut n = 1;
+        for t in self.y_extrema().into_iter().flatten() {
+            cuts[n] = t;
+            n += 1;
+        }
+        cuts[n] = 1.0;
+        cuts[1..=n].sort_unstable_by(f32::total_cmp);
+        for w in cuts[..=n].windows(2) {
+            let (t0, t1) = (w[0], w[1]);
+            if t1 <= t0 {
+                continue;
+            }
+            let y0 = self.eval(t0).1;
+            let y1 = self.eval(t1).1;
+            // Half-open rule: a span owns its lower endpoint only, so shared
+            // vertices are counted exactly once.
+            let dir = if y0 <= py && py < y1 {
+                1
+            } else if y1 <= py && py < y0 {
+                -1
+            } else {
+                continue;
+            };
+            let (mut lo, mut hi) = if y0 < y1 { (t0, t1) } else { (t1, t0) };
+            for _ in 0..BISECT_ITERS {
+                let mid = 0.5 * (lo + hi);
+                if self.eval(mid).1 < py {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            f(self.eval(0.5 * (lo + hi)).0.min(x_hi), dir);
+        }
+    }
+
+    /// Parameters in `(0, 1)` where `dy/dt = 0`.
+    fn y_extrema(&self) -> [Option<f32>; 2] {
+        let inside = |t: f32| (t > 0.0 && t < 1.0).then_some(t);
+        match *self {
+            Self::Line(..) => [None, None],
+            Self::Quad(p0, c, p1) => {
+                let denom = p0.1 - 2.0 * c.1 + p1.1;
+                if denom.abs() < 1e-12 {
+                    [None, None]
+                } else {
+                    [inside((p0.1 - c.1) / denom), None]
+                }
+            }
+            Self::Cubic(p0, c1, c2, p1) => {
+                // dy/dt / 3 = a t² + b t + c
+                let a = -p0.1 + 3.0 * c1.1 - 3.0 * c2.1 + p1.1;
+                let b = 2.0 * (p0.1 - 2.0 * c1.1 + c2.1);
+                let c = c1.1 - p0.1;
+                if a.abs() < 1e-12 {
+                    if b.abs() < 1e-12 {
+                        return [None, None];
+                    }
+                    return [inside(-c / b), None];
+                }
+                let disc = b * b - 4.0 * a * c;
+                if disc < 0.0 {
+                    return [None, None];
+                }
+                // Roots q / a and c / q: no cancellation when `a` is only a
+                // rounding residue (symmetric arches), where (-b ± s) / 2a
+                // would lose the root entirely.
+                let s = fast_sqrt(disc);
+                let q = -0.5 * if b < 0.0 { b - s } else { b + s };
+                [inside(q / a), inside(c / q)]
+            }
+        }
+    }
+}
+
+/// Squared distance from `(px, py)` to the nearest point of the outline.
+/// Returns `f32::MAX` for an empty outline.
+#[must_use]
+pub fn distance_sq(cmds: &[PathCmd], px: f32, py: f32) -> f32 {
+    let mut best = f32::MAX;
+    for seg in segments(cmds) {
+        // The curve lies inside its control polygon's bounds, so segments
+        // whose bounds are already farther than `best` can be skipped.
+        let ((x0, y0), (x1, y1)) = seg.control_bounds();
+        let dx = (x0 - px).max(px - x1).max(0.0);
+        let dy = (y0 - py).max(py - y1).max(0.0);
+        if dx * dx + dy * dy >= best {
+            continue;
+        }
+        let d = seg.distance_sq(px, py);
+        if d < best {
+            best = d;
+        }
+    }
+    best
+}
+
+/// Samples per curve when measuring [`bounds`].
+const BOUNDS_SAMPLES: usize = 16;
+
+/// Tight axis-aligned bounds `(min, max)` of the outline, or `None` for an
+/// empty outline. Curves are sampled, so extrema between samples may be
+/// missed by a negligible fraction of an em.
+#[must_use]
+pub fn bounds(cmds: &[PathCmd]) -> Option<((f32, f32), (f32, f32))> {
+    let mut lo = (f32::MAX, f32::MAX);
+    let mut hi = (f32::MIN, f32::MIN);
+    for seg in segments(cmds) {
+        let steps = if matches!(seg, Segment::Line(..)) {
+            1
+        } else {
+            BOUNDS_SAMPLES
+        };
+        for i in 0..=steps {
+            let (x, y) = seg.eval(i as f32 / steps as f32);
+            lo = (lo.0.min(x), lo.1.min(y));
+            hi = (hi.0.max(x), hi.1.max(y));
+        }
+    }
+    (lo.0 <= hi.0).then_some((lo, hi))
+}
+
+/// Winding number of the outline around `(px, py)`.
+#[must_use]
+pub fn winding_number(cmds: &[PathCmd], px: f32, py: f32) -> i32 {
+    let mut winding = 0;
+    for seg in segments(cmds) {
+        seg.accumulate_winding(px, py, &mut winding);
+    }
+    winding
+}
+
+/// Inside test under the given fill rule.
+#[must_use]
+pub fn is_inside(cmds: &[PathCmd], px: f32, py: f32, fill: FillRule) -> bool {
+    let w = winding_number(cmds, px, py);
+    match fill {
+        FillRule::NonZero => w != 0,
+        FillRule::EvenOdd => w & 1 != 0,
+    }
+}
+
+/// Squared distance from `(px, py)` to the segment `p0..p1`.
+#[inline]
+pub(crate) fn point_line_dist_sq(px: f32, py: f32, p0: (f32, f32), p1: (f32, f32)) -> f32 {
+    let dx = p1.0 - p0.0;
+    let dy = p1.1 - p0.1;
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq < 1e-9 {
+        0.0
+    } else {
+        (((px - p0.0) * dx + (py - p0.1) * dy) / len_sq).clamp(0.0, 1.0)
+    };
+    let ex = px - (p0.0 + t * dx);
+    let ey = py - (p0.1 + t * dy);
+    ex * ex + ey * ey
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit square, counter-clockwise.
+    const SQUARE: &[PathCmd] = &[
+        PathCmd::MoveTo(0.0, 0.0),
+        PathCmd::LineTo(1.0, 0.0),
+        PathCmd::LineTo(1.0, 1.0),
+        PathCmd::LineTo(0.0, 1.0),
+    ];
+
+    #[test]
+    fn segments_close_implicitly() {
+        let segs: Vec<Segment> = segments(SQUARE).collect();
+        assert_eq!(segs.len(), 4);
+        assert_eq!(segs[3], Segment::Line((0.0, 1.0), (0.0, 0.0)));
+    }
+
+    #[test]
+    fn square_winding() {
+        assert_eq!(winding_number(SQUARE, 0.5, 0.5), 1);
+        assert_eq!(winding_number(SQUARE, 1.5, 0.5), 0);
+        assert_eq!(winding_number(SQUARE, 0.5, 0.0), 1);
+    }
+
+    #[test]
+    fn overlapping_contours_differ_by_fill_rule() {
+        let mut cmds = Vec::from(SQUARE);
+        cmds.extend_from_slice(&[
+            PathCmd::MoveTo(0.5, 0.5),
+            PathCmd::LineTo(1.5, 0.5),
+            PathCmd::LineTo(1.5, 1.5),
+            PathCmd::LineTo(0.5, 1.5),
+        ]);
+        assert!(is_inside(&cmds, 0.75, 0.75, FillRule::NonZero));
+        assert!(!is_inside(&cmds, 0.75, 0.75, FillRule::EvenOdd));
+        assert!(is_inside(&cmds, 0.25, 0.25, FillRule::EvenOdd));
+    }
+
+    #[test]
+    fn quad_distance_is_exact_on_arc_apex() {
+        // Apex of this parabola is (0.5, 0.5); a point straight above it is
+        // exactly 0.25 away, which a chord approximation would overestimate.
+        let seg = Segment::Quad((0.0, 0.0), (0.5, 1.0), (1.0, 0.0));
+        let d = seg.distance_sq(0.5, 0.75);
+        assert!((d - 0.0625).abs() < 1e-6, "d² = {d}");
+    }
+
+    #[test]
+    fn cubic_distance_matches_dense_sampling() {
+        let seg = Segment::Cubic((0.0, 0.0), (0.2, 1.0), (0.8, -1.0), (1.0, 0.0));
+        for &(px, py) in &[(0.3, 0.4), (0.7, -0.3), (0.5, 0.0), (1.2, 0.2)] {
+            let mut brute = f32::MAX;
+            for i in 0..=20_000 {
+                let (x, y) = seg.eval(i as f32 / 20_000.0);
+                brute = brute.min((x - px) * (x - px) + (y - py) * (y - py));
+            }
+            let d = seg.distance_sq(px, py);
+            assert!((d - brute).abs() < 1e-5, "({px},{py}): {d} vs {brute}");
+        }
+    }
+
+    #[test]
+    fn quad_circle_winding() {
+        // Diamond of four quads bulging outward around (0.5, 0.5).
+        let cmds = [
+            PathCmd::MoveTo(0.5, 0.0),
+            PathCmd::QuadTo(1.0, 0.0, 1.0, 0.5),
+            PathCmd::QuadTo(1.0, 1.0, 0.5, 1.0),
+            PathCmd::QuadTo(0.0, 1.0, 0.0, 0.5),
+            PathCmd::QuadTo(0.0, 0.0, 0.5, 0.0),
+        ];
+        assert_eq!(winding_number(&cmds, 0.5, 0.5), 1);
+        // Inside the bulge but outside the chord diamond.
+        assert_eq!(winding_number(&cmds, 0.72, 0.2), 1);
+        assert_eq!(winding_number(&cmds, 0.97, 0.03), 0);
+    }
+
+    #[test]
+    fn symmetric_cubic_arch_winding() {
+        // y'(t) has a zero t² coefficient up to rounding; the extremum at
+        // t = 0.5 must still be found or both crossings are lost.
+        let cmds = [
+            PathCmd::MoveTo(0.3, 0.3),
+            PathCmd::CubicTo(0.3, 0.6, 0.7, 0.6, 0.7, 0.3),
+        ];
+        assert_eq!(winding_number(&cmds, 0.5, 0.31), -1);
+        assert_eq!(winding_number(&cmds, 0.5, 0.6), 0);
+    }
+
+    #[test]
+    fn lerp_requires_compatible_outlines() {
+        let thin = [PathCmd::MoveTo(0.0, 0.0), PathCmd::LineTo(1.0, 0.0)];
+        let wide = [PathCmd::MoveTo(0.0, 0.0), PathCmd::LineTo(2.0, 0.0)];
+        let mid = lerp(&thin, &wide, 0.5).unwrap();
+        assert_eq!(mid[1], PathCmd::LineTo(1.5, 0.0));
+        let curved = [
+            PathCmd::MoveTo(0.0, 0.0),
+            PathCmd::QuadTo(1.0, 1.0, 2.0, 0.0),
+        ];
+        assert!(lerp(&thin, &curved, 0.5).is_none());
+        assert!(lerp(&thin, &thin[..1], 0.5).is_none());
+    }
+
+    #[test]
+    fn map_points_touches_controls() {
+        let cmd = PathCmd::QuadTo(1.0, 2.0, 3.0, 4.0).map_points(|x, y| (x * 2.0, y));
+        assert_eq!(cmd, PathCmd::QuadTo(2.0, 2.0, 6.0, 4.0));
+    }
+}
@@ -0,0 +1,703 @@
+//! Glyph providers — pluggable glyph sources and the fallback chain.
+//!
+//! A [`GlyphProvider`] answers three questions for a code point: does it
+//! cover it, what are its metrics, and what does its SDF look like. The
+//! built-in sources (embedded outline table, Latin, Greek and Cyrillic
+//! skeletons, small capitals and superior / inferior figures, geometric
+//! shapes, box drawing, Braille, kana, kanji, Hangul and CJK symbol
+//! generators) are providers, and so is every runtime
+//! [`SfntFont`](crate::glyph::sfnt::SfntFont) when the `sfnt` feature is on.
+//!
+//! [`ProviderChain`] keeps an ordered list of providers. Applications can
+//! register their own (icon sets, custom scripts) in front of the built-ins
+//! or append them as a last-resort fallback. The active
+//! [`GlyphSourcePolicy`] decides whether outline or parametric providers
+//! are tried first; within one kind the chain order is kept.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::glyph::digits::FigureMetrics;
+use crate::glyph::dispatcher::{self, GlyphSource, GlyphSourcePolicy};
+use crate::glyph::outline::FillRule;
+pub use crate::glyph::GlyphMetrics;
+use crate::glyph::{
+    self, box_drawing, braille, cjk_symbols, cyrillic, font_metrics, font_render, fullwidth,
+    geometric, greek, hangul, hiragana, kanji, katakana, latin, notdef, variants, FontMetrics,
+    GlyphGenerator, GlyphResult, GlyphSkeleton,
+};
+use crate::param::MetaFontParams;
+
+/// Kind of glyph a provider produces, used by [`GlyphSourcePolicy`] to
+/// order the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    /// Designed outlines (embedded table, runtime fonts, icon sets).
+    Outline,
+    /// Skeletons driven by `MetaFontParams`.
+    Parametric,
+}
+
+/// A source of glyphs.
+///
+/// `generate` may return `None` even when `covers` is true (e.g. a runtime
+/// font with a broken outline); the chain then moves on to the next
+/// provider.
+pub trait GlyphProvider: Send + Sync {
+    /// Whether this provider produces outlines or parametric skeletons.
+    fn kind(&self) -> ProviderKind;
+
+    /// Does this provider have a glyph for `ch`?
+    fn covers(&self, ch: char) -> bool;
+
+    /// Metrics of `ch` without rasterizing it.
+    fn metrics(&self, ch: char, params: &MetaFontParams) -> Option<GlyphMetrics>;
+
+    /// Rasterize `ch` into an SDF tile, reporting how it was produced.
+    fn generate(&self, ch: char, params: &MetaFontParams) -> Option<GlyphResult>;
+
+    /// Stroke skeleton of `ch` and the generator to draw it with, for
+    /// providers that draw from skeletons. Grid fitting and the fixed-point
+    /// renderer work from this; other providers keep the default `None`.
+    fn skeleton(
+        &self,
+        _ch: char,
+        _gen: &GlyphGenerator,
+    ) -> Option<(GlyphGenerator, GlyphSkeleton)> {
+        None
+    }
+}
+
+/// The embedded BIZ UDPGothic outline table ([`font_render`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutlineTableProvider;
+
+impl GlyphProvider for OutlineTableProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Outline
+    }
+
+    fn covers(&self, ch: char) -> bool {
+        font_render::has(ch)
+    }
+
+    fn metrics(&self, ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+        font_render::styled_outline(ch, params).map(|o| o.metrics())
+    }
+
+    fn generate(&self, ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+        font_render::styled_outline(ch, params)
+            .map(|o| GlyphResult::outline(o.rasterize(FillRule::NonZero)).with_metrics(o.metrics()))
+    }
+}
+
+/// Declares a unit-struct [`ProviderKind::Parametric`] provider.
+///
+/// `skeleton` providers draw what `covers` accepts from the skeleton
+/// `build(gen, ch)` returns, with the generator `pen(gen, ch)` when a pen is
+/// given (the variants thin theirs) and `gen` otherwise. `procedural`
+/// providers delegate to the `covers`, `metrics` and `render` functions of
+/// a module that draws from geometry rather than skeletons.
+macro_rules! parametric_provider {
+    ($(#[$attr:meta])* skeleton $name:ident: $covers:path, $build:path $(, pen = $pen:path)?) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl GlyphProvider for $name {
+            fn kind(&self) -> ProviderKind {
+                ProviderKind::Parametric
+            }
+
+            fn covers(&self, ch: char) -> bool {
+                $covers(ch)
+            }
+
+            fn metrics(&self, ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+                self.skeleton(ch, &GlyphGenerator::new(params))
+                    .map(|(gen, skel)| gen.metrics(&skel))
+            }
+
+            fn generate(&self, ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+                self.skeleton(ch, &GlyphGenerator::new(params))
+                    .map(|(gen, skel)| gen.render(&skel))
+            }
+
+            fn skeleton(
+                &self,
+                ch: char,
+                gen: &GlyphGenerator,
+            ) -> Option<(GlyphGenerator, GlyphSkeleton)> {
+                let skel = $build(gen, ch)?;
+                Some((parametric_provider!(@pen gen, ch $(, $pen)?), skel))
+            }
+        }
+    };
+    (@pen $gen:ident, $ch:ident) => {
+        *$gen
+    };
+    (@pen $gen:ident, $ch:ident, $pen:path) => {
+        $pen($gen, $ch)
+    };
+    ($(#[$attr:meta])* procedural $name:ident: $module:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl GlyphProvider for $name {
+            fn kind(&self) -> ProviderKind {
+                ProviderKind::Parametric
+            }
+
+            fn covers(&self, ch: char) -> bool {
+                $module::covers(ch)
+            }
+
+            fn metrics(&self, ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+                $module::metrics(ch, params)
+            }
+
+            fn generate(&self, ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+                $module::render(ch, params)
+            }
+        }
+    };
+}
+
+parametric_provider! {
+    /// Parametric ASCII skeletons ([`GlyphGenerator`]), U+0020 to U+007E.
+    skeleton AsciiProvider: glyph::covers_ascii, glyph::build_ascii_skeleton
+}
+
+parametric_provider! {
+    /// Parametric Latin-1 Supplement and Latin Extended-A skeletons
+    /// ([`latin`]), U+00A0 to U+017F.
+    skeleton LatinProvider: latin::covers, latin::build_skeleton
+}
+
+parametric_provider! {
+    /// Small capitals, superior and inferior figures ([`variants`]),
+    /// synthesized from the ASCII skeletons with a thinner pen.
+    skeleton VariantsProvider: variants::covers, variants::build_skeleton, pen = variants::pen
+}
+
+parametric_provider! {
+    /// Parametric Greek skeletons ([`greek`]).
+    skeleton GreekProvider: greek::covers, greek::build_skeleton
+}
+
+parametric_provider! {
+    /// Parametric Cyrillic skeletons ([`cyrillic`]).
+    skeleton CyrillicProvider: cyrillic::covers, cyrillic::build_skeleton
+}
+
+parametric_provider! {
+    /// Parametric hiragana skeletons ([`hiragana`]).
+    skeleton HiraganaProvider: hiragana::covers, hiragana::build_skeleton
+}
+
+parametric_provider! {
+    /// Parametric katakana skeletons ([`katakana`]).
+    skeleton KatakanaProvider: katakana::covers, katakana::build_skeleton
+}
+
+parametric_provider! {
+    /// IDS-composed kanji ([`kanji`]).
+    skeleton KanjiProvider: kanji::covers, kanji::build_skeleton
+}
+
+parametric_provider! {
+    /// Hangul syllables composed from jamo (U+AC00 to U+D7A3) and the
+    /// compatibility jamo.
+    skeleton HangulProvider: hangul::covers, hangul::build_skeleton
+}
+
+parametric_provider! {
+    /// CJK Symbols and Punctuation ([`cjk_symbols`]): 、。「」 and friends.
+    skeleton CjkSymbolsProvider: cjk_symbols::covers, cjk_symbols::build_skeleton
+}
+
+parametric_provider! {
+    /// Halfwidth and Fullwidth Forms ([`fullwidth`]), derived from the ASCII,
+    /// kana and Hangul skeletons.
+    skeleton FullwidthProvider: fullwidth::covers, fullwidth::build_skeleton
+}
+
+parametric_provider! {
+    /// Box drawing and block elements ([`box_drawing`]), drawn as cell
+    /// geometry rather than skeletons.
+    procedural BoxDrawingProvider: box_drawing
+}
+
+parametric_provider! {
+    /// Geometric shapes and arrows ([`geometric`]): ■ ● ★ → ⇒ and friends.
+    procedural GeometricProvider: geometric
+}
+
+parametric_provider! {
+    /// Braille patterns ([`braille`]), drawn from the 8-dot bit pattern.
+    procedural BrailleProvider: braille
+}
+
+/// The built-in providers in their default order.
+pub(crate) static BUILTIN: [&dyn GlyphProvider; 15] = [
+    &OutlineTableProvider,
+    &AsciiProvider,
+    &VariantsProvider,
+    &LatinProvider,
+    &GreekProvider,
+    &CyrillicProvider,
+    &GeometricProvider,
+    &BoxDrawingProvider,
+    &BrailleProvider,
+    &HiraganaProvider,
+    &KatakanaProvider,
+    &KanjiProvider,
+    &HangulProvider,
+    &CjkSymbolsProvider,
+    &FullwidthProvider,
+];
+
+/// Lets [`ProviderChain`] hold the static built-in providers.
+impl GlyphProvider for &'static dyn GlyphProvider {
+    fn kind(&self) -> ProviderKind {
+        (**self).kind()
+    }
+
+    fn covers(&self, ch: char) -> bool {
+        (**self).covers(ch)
+    }
+
+    fn metrics(&self, ch: char, params: &MetaFontParams) -> Option<GlyphMetrics> {
+        (**self).metrics(ch, params)
+    }
+
+    fn generate(&self, ch: char, params: &MetaFontParams) -> Option<GlyphResult> {
+        (**self).generate(ch, params)
+    }
+
+    fn skeleton(&self, ch: char, gen: &GlyphGenerator) -> Option<(GlyphGenerator, GlyphSkeleton)> {
+        (**self).skeleton(ch, gen)
+    }
+}
+
+/// Ordered list of glyph providers.
+///
+/// Providers added with [`ProviderChain::register`] go in front of the
+/// built-ins (in registration order); [`ProviderChain::push`] appends a
+/// last-resort fallback.
+pub struct ProviderChain {
+    providers: Vec<Box<dyn GlyphProvider>>,
+    /// Number of providers inserted through `register`
+    registered: usize,
+    /// Number of runtime fonts among `providers`
+    #[cfg(feature = "sfnt")]
+    fonts: usize,
+}
+
+impl ProviderChain {
+    /// Chain without any provider; every character resolves to the
+    /// missing-glyph tile.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            providers: Vec::new(),
+            registered: 0,
+            #[cfg(feature = "sfnt")]
+            fonts: 0,
+        }
+    }
+
+    /// Chain holding the built-in providers: outline table, ASCII, small
+    /// capitals and superior / inferior figures, Latin, Greek, Cyrillic,
+    /// geometric shapes and arrows, box drawing, Braille, hiragana,
+    /// katakana, kanji, Hangul, CJK symbols, halfwidth and fullwidth forms.
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut chain = Self::empty();
+        for provider in BUILTIN {
+            chain.push(provider);
+        }
+        chain
+    }
+
+    /// Add `provider` after previously registered ones but before the
+    /// providers added with [`ProviderChain::push`] (the built-ins).
+    pub fn register(&mut self, provider: impl GlyphProvider + 'static) {
+        #[cfg(feature = "sfnt")]
+        self.count_font(&provider);
+        self.providers.insert(self.registered, Box::new(provider));
+        self.registered += 1;
+    }
+
+    /// Append `provider` at the end of the chain.
+    pub fn push(&mut self, provider: impl GlyphProvider + 'static) {
+        #[cfg(feature = "sfnt")]
+        self.count_font(&provider);
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Number of runtime fonts ([`SfntFont`](crate::glyph::sfnt::SfntFont))
+    /// in the chain.
+    #[cfg(feature = "sfnt")]
+    #[must_use]
+    pub const fn font_count(&self) -> usize {
+        self.fonts
+    }
+
+    #[cfg(feature = "sfnt")]
+    fn count_font(&mut self, provider: &dyn core::any::Any) {
+        if provider.is::<crate::glyph::sfnt::SfntFont>() {
+            self.fonts += 1;
+        }
+    }
+
+    /// Number of providers in the chain.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    /// Is the chain empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// Is `ch` covered by any provider?
+    #[must_use]
+    pub fn covers(&self, ch: char) -> bool {
+        self.providers.iter().any(|p| p.covers(ch))
+    }
+
+    /// Metrics of `ch` from the first provider `policy` selects.
+    #[must_use]
+    pub fn metrics(
+        &self,
+        ch: char,
+        params: &MetaFontParams,
+        policy: &GlyphSourcePolicy,
+    ) -> Option<GlyphMetrics> {
+        metrics_from(self.iter(), ch, params, policy)
+    }
+
+    /// Font-wide metrics measured through this chain.
+    #[must_use]
+    pub fn font_metrics(&self, params: &MetaFontParams, policy: &GlyphSourcePolicy) -> FontMetrics {
+        font_metrics::from_providers(self.iter(), params, policy)
+    }
+
+    /// Widths the figure styles are set on, measured through this chain.
+    #[must_use]
+    pub fn figure_metrics(
+        &self,
+        params: &MetaFontParams,
+        policy: &GlyphSourcePolicy,
+    ) -> FigureMetrics {
+        FigureMetrics::measure(|c| self.metrics(c, params, policy))
+    }
+
+    /// Generate `ch` from the first provider that covers it, in the order
+    /// `policy` selects. Falls back to the missing-glyph tile.
+    #[must_use]
+    pub fn generate(
+        &self,
+        ch: char,
+        params: &MetaFontParams,
+        policy: &GlyphSourcePolicy,
+    ) -> GlyphResult {
+        generate_from(self.iter(), ch, params, policy)
+    }
+
+    /// [`generate`](Self::generate) for every character of `chars`, spread
+    /// over up to `threads` scoped threads (0 = the available
+    /// parallelism). Results come back in input order and are identical to
+    /// generating the characters one by one.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn generate_batch(
+        &self,
+        chars: &[char],
+        params: &MetaFontParams,
+        policy: &GlyphSourcePolicy,
+        threads: usize,
+    ) -> Vec<GlyphResult> {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let threads = if threads == 0 {
+            std::thread::available_parallelism().map_or(1, core::num::NonZeroUsize::get)
+        } else {
+            threads
+        };
+        let threads = threads.min(chars.len());
+        if threads <= 1 {
+            return chars
+                .iter()
+                .map(|&ch| self.generate(ch, params, policy))
+                .collect();
+        }
+
+        // Workers pull the next index from a shared counter so a run of
+        // expensive glyphs (kanji) does not stall a single thread.
+        let next = AtomicUsize::new(0);
+        let mut slots: Vec<Option<GlyphResult>> = (0..chars.len()).map(|_| None).collect();
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(&ch) = chars.get(i) else {
+                                break done;
+                            };
+                            done.push((i, self.generate(ch, params, policy)));
+                        }
+                    })
+                })
+                .collect();
+            for worker in workers {
+                let done = worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                for (i, result) in done {
+                    slots[i] = Some(result);
+                }
+            }
+        });
+        slots.into_iter().flatten().collect()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &dyn GlyphProvider> + Clone {
+        self.providers.iter().map(|p| &**p)
+    }
+}
+
+impl Default for ProviderChain {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// Run the policy-ordered chain over `providers` and fall back to the
+/// policy's `.notdef` tile.
+pub(crate) fn generate_from<'a>(
+    providers: impl Iterator<Item = &'a dyn GlyphProvider> + Clone,
+    ch: char,
+    params: &MetaFontParams,
+    policy: &GlyphSourcePolicy,
+) -> GlyphResult {
+    resolve(providers, ch, policy, |p| p.generate(ch, params))
+        .unwrap_or_else(|| GlyphResult::missing(notdef::generate(ch, params, policy.notdef())))
+}
+
+/// Metrics of `ch` from the first covering provider, in `policy` order.
+pub(crate) fn metrics_from<'a>(
+    providers: impl Iterator<Item = &'a dyn GlyphProvider> + Clone,
+    ch: char,
+    params: &MetaFontParams,
+    policy: &GlyphSourcePolicy,
+) -> Option<GlyphMetrics> {
+    resolve(providers, ch, policy, |p| p.metrics(ch, params))
+}
+
+/// First `Some` from `f` over the covering providers, outline or
+/// parametric kinds first according to `policy`.
+fn resolve<'a, T>(
+    providers: impl Iterator<Item = &'a dyn GlyphProvider> + Clone,
+    ch: char,
+    policy: &GlyphSourcePolicy,
+    mut f: impl FnMut(&dyn GlyphProvider) -> Option<T>,
+) -> Option<T> {
+    let kinds: &[ProviderKind] = match policy.source_for(dispatcher::category(ch)) {
+        GlyphSource::OutlineFirst => &[ProviderKind::Outline, ProviderKind::Parametric],
+        GlyphSource::ParametricFirst => &[ProviderKind::Parametric, ProviderKind::Outline],
+        GlyphSource::OutlineOnly => &[ProviderKind::Outline],
+        GlyphSource::ParametricOnly => &[ProviderKind::Parametric],
+    };
+    kinds.iter().find_map(|&kind| {
+        providers
+            .clone()
+            .filter(|&p| p.kind() == kind && p.covers(ch))
+            .find_map(&mut f)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::{GlyphProvenance, GlyphSdf};
+
+    /// Private-use icon set drawn as a filled square.
+    struct IconProvider;
+
+    impl GlyphProvider for IconProvider {
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Outline
+        }
+
+        fn covers(&self, ch: char) -> bool {
+            ch == '\u{E000}'
+        }
+
+        fn metrics(&self, ch: char, _params: &MetaFontParams) -> Option<GlyphMetrics> {
+            self.covers(ch).then_some(GlyphMetrics::blank(1.0))
+        }
+
+        fn generate(&self, ch: char, _params: &MetaFontParams) -> Option<GlyphResult> {
+            self.covers(ch).then(|| {
+                let mut sdf = GlyphSdf::empty();
+                sdf.data.fill(-1.0);
+                sdf.advance = 1.0;
+                GlyphResult::outline(sdf)
+            })
+        }
+    }
+
+    #[test]
+    fn builtin_chain_matches_dispatcher() {
+        let chain = ProviderChain::builtin();
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::default();
+        for ch in ['A', 'あ', 'ア', '明', '가'] {
+            let a = chain.generate(ch, &params, &policy).sdf;
+            let b = dispatcher::generate(ch, &params);
+            assert_eq!(a.data, b.data, "{ch}");
+            assert!((a.advance - b.advance).abs() < 1e-6, "{ch}");
+        }
+    }
+
+    #[test]
+    fn builtin_coverage() {
+        assert!(AsciiProvider.covers('~'));
+        assert!(!AsciiProvider.covers('\u{7F}'));
+        assert!(LatinProvider.covers('ß'));
+        assert!(!LatinProvider.covers('\u{180}'));
+        assert!(GreekProvider.covers('λ') && !GreekProvider.covers('ϐ'));
+        assert!(CyrillicProvider.covers('ї') && !CyrillicProvider.covers('Ѡ'));
+        assert!(HiraganaProvider.covers('あ'));
+        assert!(KatakanaProvider.covers('ア'));
+        assert!(!KatakanaProvider.covers('あ'));
+        assert!(HangulProvider.covers('가') && HangulProvider.covers('ㅎ'));
+        assert!(CjkSymbolsProvider.covers('。') && !CjkSymbolsProvider.covers('〿'));
+        assert!(FullwidthProvider.covers('Ａ') && FullwidthProvider.covers('ｱ'));
+        assert!(BoxDrawingProvider.covers('┼') && !BoxDrawingProvider.covers('■'));
+        assert!(GeometricProvider.covers('■') && GeometricProvider.covers('⇒'));
+        assert!(BrailleProvider.covers('⠀') && !BrailleProvider.covers('⤀'));
+        assert!(VariantsProvider.covers('ᴀ') && VariantsProvider.covers('¹'));
+        assert!(!VariantsProvider.covers('ª'));
+        assert!(!ProviderChain::builtin().covers('\u{E000}'));
+    }
+
+    #[test]
+    fn metrics_match_generated_advance() {
+        let params = MetaFontParams::sans_regular();
+        for (provider, ch) in [
+            (&AsciiProvider as &dyn GlyphProvider, 'W'),
+            (&LatinProvider, 'ø'),
+            (&GreekProvider, 'Ω'),
+            (&CyrillicProvider, 'ж'),
+            (&HiraganaProvider, 'あ'),
+            (&KanjiProvider, '明'),
+            (&HangulProvider, '한'),
+            (&CjkSymbolsProvider, '「'),
+            (&FullwidthProvider, 'ｶ'),
+            (&BoxDrawingProvider, '╭'),
+            (&GeometricProvider, '★'),
+            (&BrailleProvider, '⣿'),
+            (&VariantsProvider, 'ᴍ'),
+        ] {
+            let m = provider.metrics(ch, &params).unwrap();
+            let sdf = provider.generate(ch, &params).unwrap().sdf;
+            assert!((m.advance - sdf.advance).abs() < 1e-6, "{ch}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn batch_generation_matches_serial() {
+        let mut chain = ProviderChain::builtin();
+        chain.register(IconProvider);
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::parametric_only();
+        let chars: Vec<char> = "Aé λ あア明한「╭★⣿\u{E000}\u{E123}A".chars().collect();
+        let serial: Vec<GlyphResult> = chars
+            .iter()
+            .map(|&ch| chain.generate(ch, &params, &policy))
+            .collect();
+        for threads in [0, 1, 3, 64] {
+            let batch = chain.generate_batch(&chars, &params, &policy, threads);
+            assert_eq!(batch.len(), chars.len());
+            for ((ch, a), b) in chars.iter().zip(&serial).zip(&batch) {
+                assert_eq!(a.provenance, b.provenance, "{ch}");
+                assert_eq!(a.metrics, b.metrics, "{ch}");
+                assert!(a.sdf.data == b.sdf.data, "{ch}");
+            }
+        }
+        assert!(chain.generate_batch(&[], &params, &policy, 4).is_empty());
+    }
+
+    #[test]
+    fn registered_provider_fills_gap() {
+        let mut chain = ProviderChain::builtin();
+        chain.register(IconProvider);
+        assert_eq!(chain.len(), ProviderChain::builtin().len() + 1);
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::default();
+        let result = chain.generate('\u{E000}', &params, &policy);
+        assert_eq!(result.provenance, GlyphProvenance::Outline);
+        assert!(result.sdf.data.iter().all(|d| *d < 0.0));
+        let m = chain.metrics('\u{E000}', &params, &policy).unwrap();
+        assert!((m.advance - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn registered_outline_provider_respects_policy() {
+        struct CoverA;
+        impl GlyphProvider for CoverA {
+            fn kind(&self) -> ProviderKind {
+                ProviderKind::Outline
+            }
+            fn covers(&self, ch: char) -> bool {
+                ch == 'A'
+            }
+            fn metrics(&self, _ch: char, _params: &MetaFontParams) -> Option<GlyphMetrics> {
+                None
+            }
+            fn generate(&self, _ch: char, _params: &MetaFontParams) -> Option<GlyphResult> {
+                let mut sdf = GlyphSdf::empty();
+                sdf.advance = 0.9;
+                Some(GlyphResult::outline(sdf))
+            }
+        }
+
+        let mut chain = ProviderChain::builtin();
+        chain.register(CoverA);
+        let params = MetaFontParams::sans_regular();
+        let outline = chain.generate('A', &params, &GlyphSourcePolicy::outline_first());
+        assert!((outline.sdf.advance - 0.9).abs() < 1e-6);
+        let skeleton = chain.generate('A', &params, &GlyphSourcePolicy::parametric_first());
+        assert_eq!(skeleton.provenance, GlyphProvenance::Parametric);
+        assert_eq!(
+            skeleton.sdf.data,
+            GlyphGenerator::new(&params).generate(b'A').data
+        );
+    }
+
+    #[test]
+    fn empty_chain_yields_missing_tile() {
+        let chain = ProviderChain::empty();
+        assert!(chain.is_empty());
+        let params = MetaFontParams::sans_regular();
+        let result = chain.generate('A', &params, &GlyphSourcePolicy::default());
+        assert_eq!(result.provenance, GlyphProvenance::Missing);
+        assert_eq!(
+            result.sdf.data,
+            notdef::generate('A', &params, notdef::NotdefStyle::HexBox).data
+        );
+    }
+}
@@ -0,0 +1,834 @@
+//! `CFF ` table reader and Type 2 charstring interpreter.
+//!
+//! Supports name-keyed and CID-keyed (FDArray / FDSelect) CFF fonts. Hint
+//! operators are parsed only far enough to skip their mask bytes; `seac`
+//! accented composites and the arithmetic / storage operators (deprecated in
+//! OpenType) are ignored.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use super::{read_u16, read_u32, read_u8, PathBuilder, SfntError};
+
+/// Maximum argument stack depth for Type 2 charstrings.
+const MAX_STACK: usize = 48;
+
+/// Maximum subroutine nesting depth (Type 2 spec limit).
+const MAX_SUBR_DEPTH: u32 = 10;
+
+/// Offsets into the font data needed to evaluate charstrings.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CffFace {
+    /// Start of the `CFF ` table (DICT offsets are relative to it).
+    base: usize,
+    charstrings: usize,
+    global_subrs: usize,
+    /// Local subrs for name-keyed fonts.
+    local_subrs: Option<usize>,
+    /// `(FDArray INDEX, FDSelect)` for CID-keyed fonts.
+    cid: Option<(usize, usize)>,
+}
+
+impl CffFace {
+    /// Parse the CFF header, Top DICT and Private DICT of the table at `cff`.
+    pub(super) fn parse(data: &[u8], cff: usize, len: usize) -> Result<Self, SfntError> {
+        let table = data
+            .get(cff..add_offset(cff, len)?)
+            .ok_or(SfntError::Truncated)?;
+        let hdr_size = usize::from(read_u8(table, 2)?);
+        let name_index = hdr_size;
+        let top_dict_index = index_end(table, name_index)?;
+        let string_index = index_end(table, top_dict_index)?;
+        let global_subrs = index_end(table, string_index)?;
+
+        let (top_start, top_end) = index_item(table, top_dict_index, 0)?;
+        let top = parse_dict(table.get(top_start..top_end).ok_or(SfntError::Truncated)?)?;
+        let charstrings = top.charstrings.ok_or(SfntError::Malformed)?;
+
+        let cid = match (top.fd_array, top.fd_select) {
+            (Some(fd_array), Some(fd_select)) => {
+                Some((add_offset(cff, fd_array)?, add_offset(cff, fd_select)?))
+            }
+            _ => None,
+        };
+        let local_subrs = match top.private {
+            Some((size, offset)) if cid.is_none() => private_subrs(table, offset, size)?,
+            _ => None,
+        };
+
+        Ok(Self {
+            base: cff,
+            charstrings: add_offset(cff, charstrings)?,
+            global_subrs: add_offset(cff, global_subrs)?,
+            local_subrs: local_subrs.map(|o| add_offset(cff, o)).transpose()?,
+            cid,
+        })
+    }
+
+    /// Decode the charstring of glyph `gid` into `sink`.
+    pub(super) fn outline(
+        &self,
+        data: &[u8],
+        gid: u16,
+        sink: &mut PathBuilder,
+    ) -> Result<(), SfntError> {
+        let (start, end) = index_item(data, self.charstrings, usize::from(gid))?;
+        let local = match self.cid {
+            Some((fd_array, fd_select)) => self.cid_local_subrs(data, fd_array, fd_select, gid)?,
+            None => self.local_subrs,
+        };
+        let mut interp = Interpreter {
+            data,
+            global_subrs: self.global_subrs,
+            local_subrs: local,
+            sink,
+            stack: [0.0; MAX_STACK],
+            sp: 0,
+            x: 0.0,
+            y: 0.0,
+            num_stems: 0,
+            seen_width: false,
+            open: false,
+        };
+        interp.run(start, end, 0)?;
+        interp.close_path();
+        Ok(())
+    }
+
+    /// Resolve the local subrs INDEX of the Font DICT selected for `gid`.
+    fn cid_local_subrs(
+        &self,
+        data: &[u8],
+        fd_array: usize,
+        fd_select: usize,
+        gid: u16,
+    ) -> Result<Option<usize>, SfntError> {
+        let fd = fd_select_lookup(data, fd_select, gid)?;
+        let (start, end) = index_item(data, fd_array, usize::from(fd))?;
+        let font_dict = parse_dict(data.get(start..end).ok_or(SfntError::Truncated)?)?;
+        let Some((size, offset)) = font_dict.private else {
+            return Ok(None);
+        };
+        let table = data.get(self.base..).ok_or(SfntError::Truncated)?;
+        private_subrs(table, offset, size)?
+            .map(|o| add_offset(self.base, o))
+            .transpose()
+    }
+}
+
+/// Parsed Top / Font DICT fields we care about.
+#[derive(Debug, Default)]
+struct Dict {
+    charstrings: Option<usize>,
+    private: Option<(usize, usize)>,
+    fd_array: Option<usize>,
+    fd_select: Option<usize>,
+    subrs: Option<usize>,
+}
+
+fn parse_dict(bytes: &[u8]) -> Result<Dict, SfntError> {
+    let mut dict = Dict::default();
+    let mut operands = [0.0f64; MAX_STACK];
+    let mut n = 0usize;
+    let mut pos = 0usize;
+    while pos < bytes.len() {
+        let b0 = bytes[pos];
+        pos += 1;
+        match b0 {
+            0..=21 => {
+                let op = if b0 == 12 {
+                    let b1 = read_u8(bytes, pos)?;
+                    pos += 1;
+                    1200 + u16::from(b1)
+                } else {
+                    u16::from(b0)
+                };
+                let arg = |i: usize| dict_offset(operands[i]);
+                match op {
+                    17 if n >= 1 => dict.charstrings = Some(arg(n - 1)?),
+                    18 if n >= 2 => dict.private = Some((arg(n - 2)?, arg(n - 1)?)),
+                    19 if n >= 1 => dict.subrs = Some(arg(n - 1)?),
+                    1236 if n >= 1 => dict.fd_array = Some(arg(n - 1)?),
+                    1237 if n >= 1 => dict.fd_select = Some(arg(n - 1)?),
+                    _ => {}
+                }
+                n = 0;
+            }
+            28 => {
+                let v = read_u16(bytes, pos)? as i16;
+                pos += 2;
+                push_operand(&mut operands, &mut n, f64::from(v));
+            }
+            29 => {
+                let v = read_u32(bytes, pos)? as i32;
+                pos += 4;
+                push_operand(&mut operands, &mut n, f64::from(v));
+            }
+            30 => {
+                let (v, len) = parse_real(&bytes[pos..])?;
+                pos += len;
+                push_operand(&mut operands, &mut n, v);
+            }
+            32..=246 => push_operand(&mut operands, &mut n, f64::from(i32::from(b0) - 139)),
+            247..=250 => {
+                let b1 = read_u8(bytes, pos)?;
+                pos += 1;
+                let v = (i32::from(b0) - 247) * 256 + i32::from(b1) + 108;
+                push_operand(&mut operands, &mut n, f64::from(v));
+            }
+            251..=254 => {
+                let b1 = read_u8(bytes, pos)?;
+                pos += 1;
+                let v = -(i32::from(b0) - 251) * 256 - i32::from(b1) - 108;
+                push_operand(&mut operands, &mut n, f64::from(v));
+            }
+            _ => return Err(SfntError::Malformed),
+        }
+    }
+    Ok(dict)
+}
+
+/// A DICT size or offset operand; negative values and values above
+/// `u32::MAX` are malformed.
+fn dict_offset(v: f64) -> Result<usize, SfntError> {
+    if (0.0..=f64::from(u32::MAX)).contains(&v) {
+        Ok(v as usize)
+    } else {
+        Err(SfntError::Malformed)
+    }
+}
+
+/// `base + rel`, or [`SfntError::Malformed`] on overflow.
+fn add_offset(base: usize, rel: usize) -> Result<usize, SfntError> {
+    base.checked_add(rel).ok_or(SfntError::Malformed)
+}
+
+#[inline]
+fn push_operand(operands: &mut [f64; MAX_STACK], n: &mut usize, v: f64) {
+    if *n < MAX_STACK {
+        operands[*n] = v;
+        *n += 1;
+    }
+}
+
+/// Decode a DICT real number (packed BCD nibbles). Returns `(value, bytes)`.
+fn parse_real(bytes: &[u8]) -> Result<(f64, usize), SfntError> {
+    let mut mantissa = 0.0f64;
+    let mut frac_scale = 0.0f64;
+    let mut exponent = 0i32;
+    let mut exp_sign = 0i32;
+    let mut negative = false;
+    for (i, &byte) in bytes.iter().enumerate() {
+        for nibble in [byte >> 4, byte & 0x0F] {
+            match nibble {
+                0..=9 => {
+                    if exp_sign != 0 {
+                        exponent = exponent * 10 + i32::from(nibble);
+                    } else if frac_scale > 0.0 {
+                        mantissa += f64::from(nibble) * frac_scale;
+                        frac_scale *= 0.1;
+                    } else {
+                        mantissa = mantissa * 10.0 + f64::from(nibble);
+                    }
+                }
+                0xA => frac_scale = 0.1,
+                0xB => exp_sign = 1,
+                0xC => exp_sign = -1,
+                0xE => negative = true,
+                0xF => {
+                    let mut v = mantissa;
+                    for _ in 0..exponent.unsigned_abs() {
+                        v = if exp_sign < 0 { v * 0.1 } else { v * 10.0 };
+                    }
+                    return Ok((if negative { -v } else { v }, i + 1));
+                }
+                _ => {}
+            }
+        }
+    }
+    Err(SfntError::Truncated)
+}
+
+/// Read the `Subrs` entry of a Private DICT. Returns the local subrs INDEX
+/// offset relative to the CFF table start.
+fn private_subrs(table: &[u8], offset: usize, size: usize) -> Result<Option<usize>, SfntError> {
+    let bytes = table
+        .get(offset..add_offset(offset, size)?)
+        .ok_or(SfntError::Truncated)?;
+    let private = parse_dict(bytes)?;
+    private.subrs.map(|s| add_offset(offset, s)).transpose()
+}
+
+fn fd_select_lookup(data: &[u8], fd_select: usize, gid: u16) -> Result<u8, SfntError> {
+    match read_u8(data, fd_select)? {
+        0 => read_u8(data, fd_select + 1 + usize::from(gid)),
+        3 => {
+            let n_ranges = usize::from(read_u16(data, fd_select + 1)?);
+            let mut fd = 0;
+            for i in 0..n_ranges {
+                let rec = fd_select + 3 + i * 3;
+                let first = read_u16(data, rec)?;
+                if first > gid {
+                    break;
+                }
+                fd = read_u8(data, rec + 2)?;
+            }
+            Ok(fd)
+        }
+        _ => Err(SfntError::Malformed),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// INDEX helpers
+// ----------------------------------------------------------------------------
+
+fn index_count(data: &[u8], index: usize) -> Result<usize, SfntError> {
+    read_u16(data, index).map(usize::from)
+}
+
+fn read_offset(data: &[u8], pos: usize, off_size: u8) -> Result<usize, SfntError> {
+    let mut v = 0usize;
+    for i in 0..usize::from(off_size) {
+        v = (v << 8) | usize::from(read_u8(data, pos + i)?);
+    }
+    Ok(v)
+}
+
+/// Byte range `[start, end)` of item `i` in the INDEX at `index`.
+fn index_item(data: &[u8], index: usize, i: usize) -> Result<(usize, usize), SfntError> {
+    let count = index_count(data, index)?;
+    if i >= count {
+        return Err(SfntError::Malformed);
+    }
+    let off_size = read_u8(data, index + 2)?;
+    if !(1..=4).contains(&off_size) {
+        return Err(SfntError::Malformed);
+    }
+    let offsets = index + 3;
+    let base = offsets + (count + 1) * usize::from(off_size) - 1;
+    let start = read_offset(data, offsets + i * usize::from(off_size), off_size)?;
+    let end = read_offset(data, offsets + (i + 1) * usize::from(off_size), off_size)?;
+    if start > end || base + end > data.len() {
+        return Err(SfntError::Truncated);
+    }
+    Ok((base + start, base + end))
+}
+
+/// Offset just past the INDEX at `index`.
+fn index_end(data: &[u8], index: usize) -> Result<usize, SfntError> {
+    let count = index_count(data, index)?;
+    if count == 0 {
+        return Ok(index + 2);
+    }
+    let off_size = read_u8(data, index + 2)?;
+    let offsets = index + 3;
+    let last = read_offset(data, offsets + count * usize::from(off_size), off_size)?;
+    Ok(offsets + (count + 1) * usize::from(off_size) - 1 + last)
+}
+
+/// Subroutine number bias (Type 2 charstring spec §4.7).
+fn subr_bias(count: usize) -> i32 {
+    if count < 1240 {
+        107
+    } else if count < 33900 {
+        1131
+    } else {
+        32768
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type 2 charstring interpreter
+// ----------------------------------------------------------------------------
+
+struct Interpreter<'a, 'b> {
+    data: &'a [u8],
+    global_subrs: usize,
+    local_subrs: Option<usize>,
+    sink: &'b mut PathBuilder,
+    stack: [f32; MAX_STACK],
+    sp: usize,
+    x: f32,
+    y: f32,
+    num_stems: usize,
+    seen_width: bool,
+    open: bool,
+}
+
+impl Interpreter<'_, '_> {
+    fn push(&mut self, v: f32) -> Result<(), SfntError> {
+        if self.sp >= MAX_STACK {
+            return Err(SfntError::Malformed);
+        }
+        self.stack[self.sp] = v;
+        self.sp += 1;
+        Ok(())
+    }
+
+    /// Skip the optional leading advance-width operand on the first
+    /// stack-clearing operator. `even_without_width` is true when the
+    /// operator's own argument count is even. Returns the first argument slot.
+    fn take_width(&mut self, even_without_width: bool) -> usize {
+        let mut first = 0;
+        if !self.seen_width {
+            self.seen_width = true;
+            let odd = self.sp % 2 == 1;
+            if odd == even_without_width {
+                first = 1;
+            }
+        }
+        first
+    }
+
+    fn move_to(&mut self, dx: f32, dy: f32) {
+        self.close_path();
+        self.x += dx;
+        self.y += dy;
+        self.sink.move_to(self.x, self.y);
+        self.open = true;
+    }
+
+    fn line_to(&mut self, dx: f32, dy: f32) {
+        self.x += dx;
+        self.y += dy;
+        self.sink.line_to(self.x, self.y);
+    }
+
+    fn curve_to(&mut self, d: [f32; 6]) {
+        let x1 = self.x + d[0];
+        let y1 = self.y + d[1];
+        let x2 = x1 + d[2];
+        let y2 = y1 + d[3];
+        self.x = x2 + d[4];
+        self.y = y2 + d[5];
+        self.sink.curve_to(x1, y1, x2, y2, self.x, self.y);
+    }
+
+    fn close_path(&mut self) {
+        if self.open {
+            self.sink.close();
+            self.open = false;
+        }
+    }
+
+    fn call_subr(&mut self, index: Option<usize>, depth: u32) -> Result<bool, SfntError> {
+        let index = index.ok_or(SfntError::Malformed)?;
+        if self.sp == 0 || depth >= MAX_SUBR_DEPTH {
+            return Err(SfntError::Malformed);
+        }
+        self.sp -= 1;
+        let count = index_count(self.data, index)?;
+        let n = self.stack[self.sp] as i32 + subr_bias(count);
+        let n = usize::try_from(n).map_err(|_| SfntError::Malformed)?;
+        let (start, end) = index_item(self.data, index, n)?;
+        self.run(start, end, depth + 1)
+    }
+
+    /// Execute a charstring. Returns `true` when `endchar` was reached.
+    #[allow(clippy::too_many_lines)]
+    fn run(&mut self, start: usize, end: usize, depth: u32) -> Result<bool, SfntError> {
+        let mut pos = start;
+        while pos < end {
+            let b0 = read_u8(self.data, pos)?;
+            pos += 1;
+            match b0 {
+                // hstem, vstem, hstemhm, vstemhm
+                1 | 3 | 18 | 23 => {
+                    let first = self.take_width(true);
+                    self.num_stems += (self.sp - first) / 2;
+                    self.sp = 0;
+                }
+                // hintmask, cntrmask
+                19 | 20 => {
+                    let first = self.take_width(true);
+                    self.num_stems += (self.sp - first) / 2;
+                    self.sp = 0;
+                    pos += self.num_stems.div_ceil(8);
+                }
+                // rmoveto
+                21 => {
+                    let first = self.take_width(true);
+                    if self.sp < first + 2 {
+                        return Err(SfntError::Malformed);
+                    }
+                    self.move_to(self.stack[first], self.stack[first + 1]);
+                    self.sp = 0;
+                }
+                // hmoveto
+                22 => {
+                    let first = self.take_width(false);
+                    let dx = self.stack.get(first).copied().unwrap_or(0.0);
+                    self.move_to(dx, 0.0);
+                    self.sp = 0;
+                }
+                // vmoveto
+                4 => {
+                    let first = self.take_width(false);
+                    let dy = self.stack.get(first).copied().unwrap_or(0.0);
+                    self.move_to(0.0, dy);
+                    self.sp = 0;
+                }
+                // rlineto
+                5 => {
+                    let mut i = 0;
+                    while i + 2 <= self.sp {
+                        self.line_to(self.stack[i], self.stack[i + 1]);
+                        i += 2;
+                    }
+                    self.sp = 0;
+                }
+                // hlineto / vlineto — alternating
+                6 | 7 => {
+                    let mut horizontal = b0 == 6;
+                    for i in 0..self.sp {
+                        let v = self.stack[i];
+                        if horizontal {
+                            self.line_to(v, 0.0);
+                        } else {
+                            self.line_to(0.0, v);
+                        }
+                        horizontal = !horizontal;
+                    }
+                    self.sp = 0;
+                }
+                // rrcurveto
+                8 => {
+                    let mut i = 0;
+                    while i + 6 <= self.sp {
+                        self.curve_to(self.six(i));
+                        i += 6;
+                    }
+                    self.sp = 0;
+                }
+                // callsubr
+                10 => {
+                    if self.call_subr(self.local_subrs, depth)? {
+                        return Ok(true);
+                    }
+                }
+                // return
+                11 => return Ok(false),
+                // escape
+                12 => {
+                    let b1 = read_u8(self.data, pos)?;
+                    pos += 1;
+                    self.flex(b1);
+                    self.sp = 0;
+                }
+                // endchar
+                14 => {
+                    self.take_width(true);
+                    self.sp = 0;
+                    self.close_path();
+                    return Ok(true);
+                }
+                // rcurveline
+                24 => {
+                    let mut i = 0;
+                    while i + 6 <= self.sp.saturating_sub(2) {
+                        self.curve_to(self.six(i));
+                        i += 6;
+                    }
+                    if i + 2 <= self.sp {
+                        self.line_to(self.stack[i], self.stack[i + 1]);
+                    }
+                    self.sp = 0;
+                }
+                // rlinecurve
+                25 => {
+                    let mut i = 0;
+                    while i + 2 <= self.sp.saturating_sub(6) {
+                        self.line_to(self.stack[i], self.stack[i + 1]);
+                        i += 2;
+                    }
+                    if i + 6 <= self.sp {
+                        self.curve_to(self.six(i));
+                    }
+                    self.sp = 0;
+                }
+                // vvcurveto
+                26 => {
+                    let mut i = 0;
+                    let mut dx1 = 0.0;
+                    if self.sp % 2 == 1 {
+                        dx1 = self.stack[0];
+                        i = 1;
+                    }
+                    while i + 4 <= self.sp {
+                        let s = &self.stack;
+                        let d = [dx1, s[i], s[i + 1], s[i + 2], 0.0, s[i + 3]];
+                        self.curve_to(d);
+                        dx1 = 0.0;
+                        i += 4;
+                    }
+                    self.sp = 0;
+                }
+                // hhcurveto
+                27 => {
+                    let mut i = 0;
+                    let mut dy1 = 0.0;
+                    if self.sp % 2 == 1 {
+                        dy1 = self.stack[0];
+                        i = 1;
+                    }
+                    while i + 4 <= self.sp {
+                        let s = &self.stack;
+                        let d = [s[i], dy1, s[i + 1], s[i + 2], s[i + 3], 0.0];
+                        self.curve_to(d);
+                        dy1 = 0.0;
+                        i += 4;
+                    }
+                    self.sp = 0;
+                }
+                // shortint
+                28 => {
+                    let v = read_u16(self.data, pos)? as i16;
+                    pos += 2;
+                    self.push(f32::from(v))?;
+                }
+                // callgsubr
+                29 => {
+                    if self.call_subr(Some(self.global_subrs), depth)? {
+                        return Ok(true);
+                    }
+                }
+                // vhcurveto / hvcurveto — alternating tangents
+                30 | 31 => {
+                    self.alternating_curves(b0 == 31);
+                    self.sp = 0;
+                }
+                32..=246 => self.push(f32::from(i16::from(b0) - 139))?,
+                247..=250 => {
+                    let b1 = read_u8(self.data, pos)?;
+                    pos += 1;
+                    self.push(f32::from((i16::from(b0) - 247) * 256 + i16::from(b1) + 108))?;
+                }
+                251..=254 => {
+                    let b1 = read_u8(self.data, pos)?;
+                    pos += 1;
+                    self.push(f32::from(
+                        -(i16::from(b0) - 251) * 256 - i16::from(b1) - 108,
+                    ))?;
+                }
+                // 16.16 fixed
+                255 => {
+                    const INV_65536: f32 = 1.0 / 65536.0;
+                    let v = read_u32(self.data, pos)? as i32;
+                    pos += 4;
+                    self.push(v as f32 * INV_65536)?;
+                }
+                _ => self.sp = 0,
+            }
+        }
+        Ok(false)
+    }
+
+    #[inline]
+    fn six(&self, i: usize) -> [f32; 6] {
+        let s = &self.stack;
+        [s[i], s[i + 1], s[i + 2], s[i + 3], s[i + 4], s[i + 5]]
+    }
+
+    /// `hvcurveto` (`start_horizontal`) / `vhcurveto`.
+    fn alternating_curves(&mut self, start_horizontal: bool) {
+        let mut horizontal = start_horizontal;
+        let mut i = 0;
+        while i + 4 <= self.sp {
+            let s = &self.stack;
+            let last = i + 5 == self.sp;
+            let extra = if last { s[i + 4] } else { 0.0 };
+            let d = if horizontal {
+                [s[i], 0.0, s[i + 1], s[i + 2], extra, s[i + 3]]
+            } else {
+                [0.0, s[i], s[i + 1], s[i + 2], s[i + 3], extra]
+            };
+            self.curve_to(d);
+            horizontal = !horizontal;
+            i += 4;
+        }
+    }
+
+    /// Flex family (`12 34..37`). Other escaped operators are ignored.
+    fn flex(&mut self, op: u8) {
+        let s = self.stack;
+        match op {
+            // hflex
+            34 if self.sp >= 7 => {
+                let y0 = self.y;
+                self.curve_to([s[0], 0.0, s[1], s[2], s[3], 0.0]);
+                self.curve_to([s[4], 0.0, s[5], y0 - self.y, s[6], 0.0]);
+            }
+            // flex
+            35 if self.sp >= 12 => {
+                self.curve_to([s[0], s[1], s[2], s[3], s[4], s[5]]);
+                self.curve_to([s[6], s[7], s[8], s[9], s[10], s[11]]);
+            }
+            // hflex1
+            36 if self.sp >= 9 => {
+                let y0 = self.y;
+                self.curve_to([s[0], s[1], s[2], s[3], s[4], 0.0]);
+                let dy = y0 - (self.y + s[7]);
+                self.curve_to([s[5], 0.0, s[6], s[7], s[8], dy]);
+            }
+            // flex1 — the final delta runs along the dominant axis and the
+            // other coordinate returns to the starting point.
+            37 if self.sp >= 11 => {
+                let mut dx = 0.0;
+                let mut dy = 0.0;
+                for k in 0..5 {
+                    dx += s[k * 2];
+                    dy += s[k * 2 + 1];
+                }
+                let (dx6, dy6) = if dx.abs() > dy.abs() {
+                    (s[10], -dy)
+                } else {
+                    (-dx, s[10])
+                };
+                self.curve_to([s[0], s[1], s[2], s[3], s[4], s[5]]);
+                self.curve_to([s[6], s[7], s[8], s[9], dx6, dy6]);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::outline::PathCmd;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    /// Wrap charstrings into a CFF INDEX with 1-byte offsets.
+    fn build_index(items: &[&[u8]]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(items.len() as u16).to_be_bytes());
+        out.push(1);
+        let mut off = 1u8;
+        out.push(off);
+        for item in items {
+            off += item.len() as u8;
+            out.push(off);
+        }
+        for item in items {
+            out.extend_from_slice(item);
+        }
+        out
+    }
+
+    #[test]
+    fn index_item_ranges() {
+        let idx = build_index(&[b"ab", b"cde"]);
+        let (s0, e0) = index_item(&idx, 0, 0).unwrap();
+        let (s1, e1) = index_item(&idx, 0, 1).unwrap();
+        assert_eq!(&idx[s0..e0], b"ab");
+        assert_eq!(&idx[s1..e1], b"cde");
+        assert_eq!(index_end(&idx, 0).unwrap(), idx.len());
+    }
+
+    #[test]
+    fn dict_reads_charstrings_and_private() {
+        // 100 17 (CharStrings), 20 200 18 (Private size=20 offset=200)
+        let dict = parse_dict(&[239, 17, 159, 247, 92, 18]).unwrap();
+        assert_eq!(dict.charstrings, Some(100));
+        assert_eq!(dict.private, Some((20, 200)));
+    }
+
+    #[test]
+    fn dict_rejects_out_of_range_offsets() {
+        // -1 17 (CharStrings at a negative offset)
+        assert_eq!(parse_dict(&[138, 17]).unwrap_err(), SfntError::Malformed);
+        // 1e30 17 as a real: nibbles 1 E+ 3 0 end
+        assert_eq!(
+            parse_dict(&[30, 0x1B, 0x30, 0xFF, 17]).unwrap_err(),
+            SfntError::Malformed
+        );
+        // 2^31 - 1 is still accepted
+        let dict = parse_dict(&[29, 0x7F, 0xFF, 0xFF, 0xFF, 17]).unwrap();
+        assert_eq!(dict.charstrings, Some(0x7FFF_FFFF));
+        assert_eq!(add_offset(usize::MAX, 1).unwrap_err(), SfntError::Malformed);
+    }
+
+    #[test]
+    fn dict_real_number() {
+        // 0.001 encoded as nibbles: 0 . 0 0 1 end → 0x0A 0x00 0x1F
+        let (v, len) = parse_real(&[0x0A, 0x00, 0x1F]).unwrap();
+        assert!((v - 0.001).abs() < 1e-9);
+        assert_eq!(len, 3);
+    }
+
+    #[test]
+    fn charstring_draws_closed_triangle() {
+        // width 500, rmoveto(100,0), rlineto(400,0, -200,300), endchar
+        let cs = [
+            248, 136, // 500 (width)
+            239, 139, 21, // 100 0 rmoveto
+            248, 36, 139, 251, 92, 247, 192, 5, // 400 0 -200 300 rlineto
+            14,
+        ];
+        let data = build_index(&[&cs]);
+        let mut sink = PathBuilder::new(0.001);
+        let face = CffFace {
+            base: 0,
+            charstrings: 0,
+            global_subrs: data.len(),
+            local_subrs: None,
+            cid: None,
+        };
+        let mut full = data.clone();
+        full.extend_from_slice(&[0, 0]); // empty global subrs INDEX
+        face.outline(&full, 0, &mut sink).unwrap();
+        let c: Vec<(f32, f32)> = sink.cmds.iter().map(PathCmd::end_point).collect();
+        assert_eq!(c.len(), 3, "move + 2 lines, closing line implicit");
+        assert!((c[0].0 - 0.1).abs() < 1e-6);
+        assert!((c[1].0 - 0.5).abs() < 1e-6);
+        assert!((c[2].1 - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn charstring_calls_global_subr() {
+        // Global subr 0 (biased: -107) draws the line, then returns.
+        let subr = [248, 36, 139, 5, 11]; // 400 0 rlineto return
+        let cs = [239, 139, 21, 32, 29, 139, 248, 36, 5, 14]; // -107 callgsubr
+        let charstrings = build_index(&[&cs]);
+        let gsubrs = build_index(&[&subr]);
+        let mut data = charstrings.clone();
+        let gs_at = data.len();
+        data.extend_from_slice(&gsubrs);
+        let face = CffFace {
+            base: 0,
+            charstrings: 0,
+            global_subrs: gs_at,
+            local_subrs: None,
+            cid: None,
+        };
+        let mut sink = PathBuilder::new(1.0);
+        face.outline(&data, 0, &mut sink).unwrap();
+        let c: Vec<(f32, f32)> = sink.cmds.iter().map(PathCmd::end_point).collect();
+        assert!((c[1].0 - 500.0).abs() < 1e-3);
+        assert!((c[2].1 - 400.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn subr_bias_thresholds() {
+        assert_eq!(subr_bias(10), 107);
+        assert_eq!(subr_bias(2000), 1131);
+        assert_eq!(subr_bias(40_000), 32768);
+    }
+
+    #[test]
+    fn rejects_stack_overflow() {
+        let cs = vec![139u8; MAX_STACK + 1];
+        let data = build_index(&[&cs]);
+        let face = CffFace {
+            base: 0,
+            charstrings: 0,
+            global_subrs: data.len(),
+            local_subrs: None,
+            cid: None,
+        };
+        let mut sink = PathBuilder::new(1.0);
+        assert_eq!(face.outline(&data, 0, &mut sink), Err(SfntError::Malformed));
+    }
+}
@@ -0,0 +1,1177 @@
+//! Runtime sfnt (TrueType / OpenType) outline loader.
+//!
+//! Parses user-supplied font files at runtime so they can be registered as
+//! glyph sources next to the compiled-in BIZ UDPGothic table. Only the
+//! tables needed to produce outlines and horizontal advances are read:
+//!
+//!   - `head` / `maxp` / `hhea` / `hmtx` — em size, glyph count, advances
+//!   - `cmap` — formats 4 and 12 (Unicode BMP / full repertoire)
+//!   - `glyf` / `loca` — TrueType quadratic outlines (simple + composite)
+//!   - `CFF ` — Type 2 charstrings (see the `cff` submodule)
+//!
+//! Outlines are converted into em-normalized [`PathCmd`] streams — the same
+//! format as the embedded table emitted by `tools/import-font`, quadratic
+//! and cubic segments preserved — and rasterized through
+//! [`font_render::rasterize_outline`]. No hinting, kerning or variation
+//! tables are interpreted.
+//!
+//! Enabled via `--features sfnt`. `no_std` + `alloc`.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use super::dispatcher::GlyphSourcePolicy;
+use super::outline::{FillRule, PathCmd};
+use super::provider::{self, GlyphProvider, ProviderKind};
+use super::{font_render, GlyphMetrics, GlyphResult, GlyphSdf};
+use crate::param::MetaFontParams;
+
+mod cff;
+
+/// Maximum nesting depth for TrueType composite glyphs.
+const MAX_COMPONENT_DEPTH: u32 = 8;
+
+/// sfnt parse error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SfntError {
+    /// A read ran past the end of the font data or of a table.
+    Truncated,
+    /// The sfnt version tag is not TrueType, OpenType/CFF or a collection.
+    UnknownFormat,
+    /// The requested face index is out of range for a font collection.
+    FaceIndexOutOfRange,
+    /// A required table is absent.
+    MissingTable([u8; 4]),
+    /// No Unicode `cmap` subtable in a supported format (4 or 12).
+    UnsupportedCmap,
+    /// A table is present but structurally invalid.
+    Malformed,
+}
+
+/// Outline storage of a parsed face.
+#[derive(Debug, Clone, Copy)]
+enum OutlineSource {
+    /// TrueType `glyf` + `loca`.
+    Glyf {
+        glyf: usize,
+        glyf_len: usize,
+        loca: usize,
+        long_offsets: bool,
+    },
+    /// OpenType `CFF ` table.
+    Cff(cff::CffFace),
+}
+
+/// A font face parsed from an sfnt file (`.ttf`, `.otf` or `.ttc`).
+///
+/// Owns its byte buffer; every accessor re-reads the relevant table on
+/// demand, so construction is cheap and the struct stays small.
+#[derive(Debug, Clone)]
+pub struct SfntFont {
+    data: Vec<u8>,
+    units_per_em: u16,
+    num_glyphs: u16,
+    ascender: i16,
+    descender: i16,
+    line_gap: i16,
+    hmtx: usize,
+    num_h_metrics: u16,
+    cmap_subtable: usize,
+    cmap_format: u16,
+    outlines: OutlineSource,
+}
+
+impl SfntFont {
+    /// Parse the first face of an sfnt file.
+    ///
+    /// # Errors
+    /// Returns an [`SfntError`] if the data is not a supported sfnt file or a
+    /// required table is missing or truncated.
+    pub fn parse(data: Vec<u8>) -> Result<Self, SfntError> {
+        Self::parse_index(data, 0)
+    }
+
+    /// Parse face `index` of an sfnt file or `ttcf` collection.
+    ///
+    /// # Errors
+    /// See [`SfntFont::parse`]. Also fails with
+    /// [`SfntError::FaceIndexOutOfRange`] for an invalid collection index.
+    pub fn parse_index(data: Vec<u8>, index: u32) -> Result<Self, SfntError> {
+        let dir = face_offset(&data, index)?;
+        let tables = TableDirectory::read(&data, dir)?;
+
+        let head = tables.require(b"head")?;
+        let units_per_em = read_u16(&data, head.0 + 18)?;
+        let index_to_loc = read_i16(&data, head.0 + 50)?;
+        if units_per_em == 0 {
+            return Err(SfntError::Malformed);
+        }
+
+        let maxp = tables.require(b"maxp")?;
+        let num_glyphs = read_u16(&data, maxp.0 + 4)?;
+
+        let hhea = tables.require(b"hhea")?;
+        let ascender = read_i16(&data, hhea.0 + 4)?;
+        let descender = read_i16(&data, hhea.0 + 6)?;
+        let line_gap = read_i16(&data, hhea.0 + 8)?;
+        let num_h_metrics = read_u16(&data, hhea.0 + 34)?;
+        let hmtx = tables.require(b"hmtx")?;
+        if num_h_metrics == 0 || hmtx.1 < usize::from(num_h_metrics) * 4 {
+            return Err(SfntError::Malformed);
+        }
+
+        let cmap = tables.require(b"cmap")?;
+        let (cmap_subtable, cmap_format) = find_unicode_cmap(&data, cmap.0)?;
+
+        let outlines = if let Some(glyf) = tables.find(b"glyf") {
+            let loca = tables.require(b"loca")?;
+            OutlineSource::Glyf {
+                glyf: glyf.0,
+                glyf_len: glyf.1,
+                loca: loca.0,
+                long_offsets: index_to_loc != 0,
+            }
+        } else if let Some(cff) = tables.find(b"CFF ") {
+            OutlineSource::Cff(cff::CffFace::parse(&data, cff.0, cff.1)?)
+        } else {
+            return Err(SfntError::MissingTable(*b"glyf"));
+        };
+
+        Ok(Self {
+            data,
+            units_per_em,
+            num_glyphs,
+            ascender,
+            descender,
+            line_gap,
+            hmtx: hmtx.0,
+            num_h_metrics,
+            cmap_subtable,
+            cmap_format,
+            outlines,
+        })
+    }
+
+    /// Design units per em (`head.unitsPerEm`).
+    #[must_use]
+    pub const fn units_per_em(&self) -> u16 {
+        self.units_per_em
+    }
+
+    /// Number of glyphs in the face (`maxp.numGlyphs`).
+    #[must_use]
+    pub const fn num_glyphs(&self) -> u16 {
+        self.num_glyphs
+    }
+
+    /// `hhea` ascender in em units.
+    #[must_use]
+    pub fn ascender(&self) -> f32 {
+        f32::from(self.ascender) * self.inv_upem()
+    }
+
+    /// `hhea` descender in em units (negative = below baseline).
+    #[must_use]
+    pub fn descender(&self) -> f32 {
+        f32::from(self.descender) * self.inv_upem()
+    }
+
+    /// `hhea` line gap in em units.
+    #[must_use]
+    pub fn line_gap(&self) -> f32 {
+        f32::from(self.line_gap) * self.inv_upem()
+    }
+
+    /// Is this face backed by CFF (cubic) rather than `glyf` outlines?
+    #[must_use]
+    pub const fn is_cff(&self) -> bool {
+        matches!(self.outlines, OutlineSource::Cff(_))
+    }
+
+    /// Map a character to a glyph id. Returns `None` for unmapped characters
+    /// (including those mapped to `.notdef`).
+    #[must_use]
+    pub fn glyph_index(&self, ch: char) -> Option<u16> {
+        let gid = match self.cmap_format {
+            4 => cmap4_lookup(&self.data, self.cmap_subtable, ch as u32),
+            12 => cmap12_lookup(&self.data, self.cmap_subtable, ch as u32),
+            _ => None,
+        }?;
+        (gid != 0 && gid < self.num_glyphs).then_some(gid)
+    }
+
+    /// True iff the face maps `ch` to a glyph.
+    #[must_use]
+    pub fn has(&self, ch: char) -> bool {
+        self.glyph_index(ch).is_some()
+    }
+
+    /// Horizontal advance of glyph `gid` in em units.
+    #[must_use]
+    pub fn advance(&self, gid: u16) -> f32 {
+        let idx = if gid < self.num_h_metrics {
+            gid
+        } else {
+            self.num_h_metrics - 1
+        };
+        let units = read_u16(&self.data, self.hmtx + usize::from(idx) * 4).unwrap_or(0);
+        f32::from(units) * self.inv_upem()
+    }
+
+    /// Em-normalized outline of glyph `gid`.
+    ///
+    /// Returns `None` if the glyph data is malformed. Glyphs without
+    /// contours (space) return an empty list.
+    #[must_use]
+    pub fn outline(&self, gid: u16) -> Option<Vec<PathCmd>> {
+        let mut sink = PathBuilder::new(self.inv_upem());
+        match self.outlines {
+            OutlineSource::Glyf {
+                glyf,
+                glyf_len,
+                loca,
+                long_offsets,
+            } => {
+                let tables = GlyfTables {
+                    data: &self.data,
+                    glyf,
+                    glyf_len,
+                    loca,
+                    long_offsets,
+                    num_glyphs: self.num_glyphs,
+                };
+                tables
+                    .outline(gid, Transform::IDENTITY, 0, &mut sink)
+                    .ok()?;
+            }
+            OutlineSource::Cff(ref face) => {
+                face.outline(&self.data, gid, &mut sink).ok()?;
+            }
+        }
+        Some(sink.cmds)
+    }
+
+    /// Rasterize `ch` into a `GLYPH_SDF_SIZE`² SDF with the non-zero fill
+    /// rule. Returns `None` if the face does not map the character.
+    #[must_use]
+    pub fn rasterize(&self, ch: char) -> Option<GlyphSdf> {
+        self.rasterize_with_fill(ch, FillRule::NonZero)
+    }
+
+    /// [`SfntFont::rasterize`] with an explicit fill rule.
+    #[must_use]
+    pub fn rasterize_with_fill(&self, ch: char, fill: FillRule) -> Option<GlyphSdf> {
+        let gid = self.glyph_index(ch)?;
+        let advance = self.advance(gid);
+        let cmds = self.outline(gid)?;
+        if cmds.is_empty() {
+            let mut sdf = GlyphSdf::empty();
+            sdf.advance = advance;
+            return Some(sdf);
+        }
+        let mut sdf = font_render::rasterize_outline(&cmds, advance, fill);
+        sdf.lsb = font_render::outline_metrics(&cmds, advance, 0.0).lsb;
+        Some(sdf)
+    }
+
+    /// Advance, side bearings and ink box of `ch` (em units).
+    #[must_use]
+    pub fn glyph_metrics(&self, ch: char) -> Option<GlyphMetrics> {
+        let gid = self.glyph_index(ch)?;
+        let advance = self.advance(gid);
+        let cmds = self.outline(gid)?;
+        Some(font_render::outline_metrics(&cmds, advance, 0.0))
+    }
+
+    #[inline]
+    fn inv_upem(&self) -> f32 {
+        1.0 / f32::from(self.units_per_em)
+    }
+}
+
+/// Generate the SDF for `ch`, trying each registered runtime font in order
+/// before falling back to [`super::dispatcher::generate`].
+#[must_use]
+pub fn generate_with_fonts(ch: char, params: &MetaFontParams, fonts: &[SfntFont]) -> GlyphSdf {
+    generate_with_fonts_and_policy(ch, params, fonts, &GlyphSourcePolicy::default())
+}
+
+/// Like [`generate_with_fonts`], but honours `policy`. Registered fonts
+/// count as outline sources and are tried before the built-in providers.
+#[must_use]
+pub fn generate_with_fonts_and_policy(
+    ch: char,
+    params: &MetaFontParams,
+    fonts: &[SfntFont],
+    policy: &GlyphSourcePolicy,
+) -> GlyphSdf {
+    let providers = fonts
+        .iter()
+        .map(|font| font as &dyn GlyphProvider)
+        .chain(provider::BUILTIN.iter().copied());
+    provider::generate_from(providers, ch, params, policy).sdf
+}
+
+impl GlyphProvider for SfntFont {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Outline
+    }
+
+    fn covers(&self, ch: char) -> bool {
+        self.has(ch)
+    }
+
+    fn metrics(&self, ch: char, _params: &MetaFontParams) -> Option<GlyphMetrics> {
+        self.glyph_metrics(ch)
+    }
+
+    fn generate(&self, ch: char, _params: &MetaFontParams) -> Option<GlyphResult> {
+        let metrics = self.glyph_metrics(ch)?;
+        self.rasterize(ch)
+            .map(|sdf| GlyphResult::outline(sdf).with_metrics(metrics))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Table directory
+// ----------------------------------------------------------------------------
+
+/// Resolve the offset of the table directory for face `index`.
+fn face_offset(data: &[u8], index: u32) -> Result<usize, SfntError> {
+    match read_tag(data, 0)? {
+        [0, 1, 0, 0] | [b'O', b'T', b'T', b'O'] | [b't', b'r', b'u', b'e'] => {
+            if index == 0 {
+                Ok(0)
+            } else {
+                Err(SfntError::FaceIndexOutOfRange)
+            }
+        }
+        [b't', b't', b'c', b'f'] => {
+            let num_fonts = read_u32(data, 8)?;
+            if index >= num_fonts {
+                return Err(SfntError::FaceIndexOutOfRange);
+            }
+            Ok(read_u32(data, 12 + index as usize * 4)? as usize)
+        }
+        _ => Err(SfntError::UnknownFormat),
+    }
+}
+
+/// Parsed table records: `(tag, offset, length)`.
+struct TableDirectory {
+    records: Vec<([u8; 4], usize, usize)>,
+}
+
+impl TableDirectory {
+    fn read(data: &[u8], dir: usize) -> Result<Self, SfntError> {
+        let num_tables = read_u16(data, dir + 4)?;
+        let mut records = Vec::with_capacity(usize::from(num_tables));
+        for i in 0..usize::from(num_tables) {
+            let rec = dir + 12 + i * 16;
+            let tag = read_tag(data, rec)?;
+            let offset = read_u32(data, rec + 8)? as usize;
+            let length = read_u32(data, rec + 12)? as usize;
+            if offset
+                .checked_add(length)
+                .is_none_or(|end| end > data.len())
+            {
+                return Err(SfntError::Truncated);
+            }
+            records.push((tag, offset, length));
+        }
+        Ok(Self { records })
+    }
+
+    fn find(&self, tag: &[u8; 4]) -> Option<(usize, usize)> {
+        self.records
+            .iter()
+            .find(|(t, _, _)| t == tag)
+            .map(|&(_, off, len)| (off, len))
+    }
+
+    fn require(&self, tag: &[u8; 4]) -> Result<(usize, usize), SfntError> {
+        self.find(tag).ok_or(SfntError::MissingTable(*tag))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// cmap
+// ----------------------------------------------------------------------------
+
+/// Pick the best Unicode subtable: format 12 preferred over format 4.
+fn find_unicode_cmap(data: &[u8], cmap: usize) -> Result<(usize, u16), SfntError> {
+    let num = read_u16(data, cmap + 2)?;
+    let mut best: Option<(usize, u16)> = None;
+    for i in 0..usize::from(num) {
+        let rec = cmap + 4 + i * 8;
+        let platform = read_u16(data, rec)?;
+        let encoding = read_u16(data, rec + 2)?;
+        let offset = cmap + read_u32(data, rec + 4)? as usize;
+        let unicode = matches!((platform, encoding), (0, _) | (3, 1 | 10));
+        if !unicode {
+            continue;
+        }
+        let format = read_u16(data, offset)?;
+        match format {
+            12 => return Ok((offset, 12)),
+            4 if best.is_none() => best = Some((offset, 4)),
+            _ => {}
+        }
+    }
+    best.ok_or(SfntError::UnsupportedCmap)
+}
+
+fn cmap4_lookup(data: &[u8], sub: usize, cp: u32) -> Option<u16> {
+    let cp = u16::try_from(cp).ok()?;
+    let seg_x2 = usize::from(read_u16(data, sub + 6).ok()?);
+    let ends = sub + 14;
+    let starts = ends + seg_x2 + 2;
+    let deltas = starts + seg_x2;
+    let range_offsets = deltas + seg_x2;
+
+    // Binary search over segment end codes.
+    let (mut lo, mut hi) = (0usize, seg_x2 / 2);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let end = read_u16(data, ends + mid * 2).ok()?;
+        if end < cp {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    if lo >= seg_x2 / 2 {
+        return None;
+    }
+    let start = read_u16(data, starts + lo * 2).ok()?;
+    if cp < start {
+        return None;
+    }
+    let delta = read_u16(data, deltas + lo * 2).ok()?;
+    let ro_pos = range_offsets + lo * 2;
+    let range_offset = read_u16(data, ro_pos).ok()?;
+    if range_offset == 0 {
+        return Some(cp.wrapping_add(delta));
+    }
+    let glyph_pos = ro_pos + usize::from(range_offset) + usize::from(cp - start) * 2;
+    let gid = read_u16(data, glyph_pos).ok()?;
+    (gid != 0).then(|| gid.wrapping_add(delta))
+}
+
+fn cmap12_lookup(data: &[u8], sub: usize, cp: u32) -> Option<u16> {
+    let num_groups = read_u32(data, sub + 12).ok()? as usize;
+    let (mut lo, mut hi) = (0usize, num_groups);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let group = sub + 16 + mid * 12;
+        let start = read_u32(data, group).ok()?;
+        let end = read_u32(data, group + 4).ok()?;
+        if cp < start {
+            hi = mid;
+        } else if cp > end {
+            lo = mid + 1;
+        } else {
+            let first = read_u32(data, group + 8).ok()?;
+            return first
+                .checked_add(cp - start)
+                .and_then(|gid| u16::try_from(gid).ok());
+        }
+    }
+    None
+}
+
+// ----------------------------------------------------------------------------
+// glyf / loca
+// ----------------------------------------------------------------------------
+
+/// 2×2 matrix + translation applied to composite glyph components.
+#[derive(Debug, Clone, Copy)]
+struct Transform {
+    xx: f32,
+    xy: f32,
+    yx: f32,
+    yy: f32,
+    dx: f32,
+    dy: f32,
+}
+
+impl Transform {
+    const IDENTITY: Self = Self {
+        xx: 1.0,
+        xy: 0.0,
+        yx: 0.0,
+        yy: 1.0,
+        dx: 0.0,
+        dy: 0.0,
+    };
+
+    #[inline]
+    fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.xx * x + self.yx * y + self.dx,
+            self.xy * x + self.yy * y + self.dy,
+        )
+    }
+
+    /// `self ∘ inner` — apply `inner` first, then `self`.
+    fn compose(&self, inner: &Self) -> Self {
+        let (dx, dy) = self.apply(inner.dx, inner.dy);
+        Self {
+            xx: self.xx * inner.xx + self.yx * inner.xy,
+            xy: self.xy * inner.xx + self.yy * inner.xy,
+            yx: self.xx * inner.yx + self.yx * inner.yy,
+            yy: self.xy * inner.yx + self.yy * inner.yy,
+            dx,
+            dy,
+        }
+    }
+}
+
+struct GlyfTables<'a> {
+    data: &'a [u8],
+    glyf: usize,
+    glyf_len: usize,
+    loca: usize,
+    long_offsets: bool,
+    num_glyphs: u16,
+}
+
+impl GlyfTables<'_> {
+    /// Byte range of glyph `gid` within the font data (`None` = empty glyph).
+    fn glyph_range(&self, gid: u16) -> Result<Option<(usize, usize)>, SfntError> {
+        if gid >= self.num_glyphs {
+            return Err(SfntError::Malformed);
+        }
+        let i = usize::from(gid);
+        let (start, end) = if self.long_offsets {
+            (
+                read_u32(self.data, self.loca + i * 4)? as usize,
+                read_u32(self.data, self.loca + i * 4 + 4)? as usize,
+            )
+        } else {
+            (
+                usize::from(read_u16(self.data, self.loca + i * 2)?) * 2,
+                usize::from(read_u16(self.data, self.loca + i * 2 + 2)?) * 2,
+            )
+        };
+        if start >= end {
+            return Ok(None);
+        }
+        if end > self.glyf_len {
+            return Err(SfntError::Truncated);
+        }
+        Ok(Some((self.glyf + start, self.glyf + end)))
+    }
+
+    fn outline(
+        &self,
+        gid: u16,
+        xf: Transform,
+        depth: u32,
+        sink: &mut PathBuilder,
+    ) -> Result<(), SfntError> {
+        let Some((start, end)) = self.glyph_range(gid)? else {
+            return Ok(());
+        };
+        let glyph = self.data.get(start..end).ok_or(SfntError::Truncated)?;
+        let num_contours = read_i16(glyph, 0)?;
+        if num_contours >= 0 {
+            simple_glyph(glyph, num_contours as usize, &xf, sink)
+        } else if depth < MAX_COMPONENT_DEPTH {
+            self.composite_glyph(glyph, &xf, depth, sink)
+        } else {
+            Err(SfntError::Malformed)
+        }
+    }
+
+    fn composite_glyph(
+        &self,
+        glyph: &[u8],
+        xf: &Transform,
+        depth: u32,
+        sink: &mut PathBuilder,
+    ) -> Result<(), SfntError> {
+        const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+        const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+        const WE_HAVE_A_SCALE: u16 = 0x0008;
+        const MORE_COMPONENTS: u16 = 0x0020;
+        const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+        const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+
+        let mut pos = 10;
+        loop {
+            let flags = read_u16(glyph, pos)?;
+            let component = read_u16(glyph, pos + 2)?;
+            pos += 4;
+            let (arg1, arg2) = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                let a = read_i16(glyph, pos)?;
+                let b = read_i16(glyph, pos + 2)?;
+                pos += 4;
+                (f32::from(a), f32::from(b))
+            } else {
+                let a = *glyph.get(pos).ok_or(SfntError::Truncated)? as i8;
+                let b = *glyph.get(pos + 1).ok_or(SfntError::Truncated)? as i8;
+                pos += 2;
+                (f32::from(a), f32::from(b))
+            };
+            let mut local = Transform::IDENTITY;
+            if flags & WE_HAVE_A_SCALE != 0 {
+                let s = read_f2dot14(glyph, pos)?;
+                pos += 2;
+                local.xx = s;
+                local.yy = s;
+            } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                local.xx = read_f2dot14(glyph, pos)?;
+                local.yy = read_f2dot14(glyph, pos + 2)?;
+                pos += 4;
+            } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                local.xx = read_f2dot14(glyph, pos)?;
+                local.xy = read_f2dot14(glyph, pos + 2)?;
+                local.yx = read_f2dot14(glyph, pos + 4)?;
+                local.yy = read_f2dot14(glyph, pos + 6)?;
+                pos += 8;
+            }
+            // Point-matching placement (args are point indices) is rare and
+            // only used for hinting-era fonts; treat it as a zero offset.
+            if flags & ARGS_ARE_XY_VALUES != 0 {
+                local.dx = arg1;
+                local.dy = arg2;
+            }
+            self.outline(component, xf.compose(&local), depth + 1, sink)?;
+            if flags & MORE_COMPONENTS == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Decode a simple (non-composite) TrueType glyph and feed its contours.
+fn simple_glyph(
+    glyph: &[u8],
+    num_contours: usize,
+    xf: &Transform,
+    sink: &mut PathBuilder,
+) -> Result<(), SfntError> {
+    const ON_CURVE: u8 = 0x01;
+    const X_SHORT: u8 = 0x02;
+    const Y_SHORT: u8 = 0x04;
+    const REPEAT: u8 = 0x08;
+    const X_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_SAME_OR_POSITIVE: u8 = 0x20;
+
+    if num_contours == 0 {
+        return Ok(());
+    }
+    let mut end_pts = Vec::with_capacity(num_contours);
+    for i in 0..num_contours {
+        end_pts.push(usize::from(read_u16(glyph, 10 + i * 2)?));
+    }
+    let num_points = end_pts.last().map_or(0, |e| e + 1);
+    let ins_len = usize::from(read_u16(glyph, 10 + num_contours * 2)?);
+    let mut pos = 12 + num_contours * 2 + ins_len;
+
+    // Flags (run-length encoded).
+    let mut flags = Vec::with_capacity(num_points);
+    while flags.len() < num_points {
+        let f = *glyph.get(pos).ok_or(SfntError::Truncated)?;
+        pos += 1;
+        flags.push(f);
+        if f & REPEAT != 0 {
+            let n = *glyph.get(pos).ok_or(SfntError::Truncated)?;
+            pos += 1;
+            for _ in 0..n {
+                flags.push(f);
+            }
+        }
+    }
+    flags.truncate(num_points);
+
+    // Coordinates (delta encoded).
+    let mut xs = Vec::with_capacity(num_points);
+    let mut acc = 0i32;
+    for &f in &flags {
+        if f & X_SHORT != 0 {
+            let d = i32::from(*glyph.get(pos).ok_or(SfntError::Truncated)?);
+            pos += 1;
+            acc += if f & X_SAME_OR_POSITIVE != 0 { d } else { -d };
+        } else if f & X_SAME_OR_POSITIVE == 0 {
+            acc += i32::from(read_i16(glyph, pos)?);
+            pos += 2;
+        }
+        xs.push(acc as f32);
+    }
+    let mut ys = Vec::with_capacity(num_points);
+    acc = 0;
+    for &f in &flags {
+        if f & Y_SHORT != 0 {
+            let d = i32::from(*glyph.get(pos).ok_or(SfntError::Truncated)?);
+            pos += 1;
+            acc += if f & Y_SAME_OR_POSITIVE != 0 { d } else { -d };
+        } else if f & Y_SAME_OR_POSITIVE == 0 {
+            acc += i32::from(read_i16(glyph, pos)?);
+            pos += 2;
+        }
+        ys.push(acc as f32);
+    }
+
+    let mut start = 0usize;
+    for &end in &end_pts {
+        if end < start || end >= num_points {
+            return Err(SfntError::Malformed);
+        }
+        let pts: Vec<(f32, f32, bool)> = (start..=end)
+            .map(|i| {
+                let (x, y) = xf.apply(xs[i], ys[i]);
+                (x, y, flags[i] & ON_CURVE != 0)
+            })
+            .collect();
+        emit_quadratic_contour(&pts, sink);
+        start = end + 1;
+    }
+    Ok(())
+}
+
+/// Convert a TrueType on/off-curve point loop into move/line/quad commands.
+fn emit_quadratic_contour(pts: &[(f32, f32, bool)], sink: &mut PathBuilder) {
+    let n = pts.len();
+    if n == 0 {
+        return;
+    }
+    // Find a starting on-curve point; if none exist, start at the implied
+    // midpoint of the first two off-curve points.
+    let first_on = pts.iter().position(|p| p.2);
+    let (start, start_idx) = match first_on {
+        Some(i) => ((pts[i].0, pts[i].1), i),
+        None => {
+            let (a, b) = (pts[0], pts[1 % n]);
+            (((a.0 + b.0) * 0.5, (a.1 + b.1) * 0.5), 0)
+        }
+    };
+    sink.move_to(start.0, start.1);
+    let mut ctrl: Option<(f32, f32)> = None;
+    for k in 1..=n {
+        let p = pts[(start_idx + k) % n];
+        if first_on.is_none() && k == n {
+            // Close back through the first off-curve point.
+            let c = ctrl.take().unwrap_or((p.0, p.1));
+            sink.quad_to(c.0, c.1, start.0, start.1);
+            break;
+        }
+        if p.2 {
+            match ctrl.take() {
+                Some(c) => sink.quad_to(c.0, c.1, p.0, p.1),
+                // The closing line back to the start is implicit.
+                None if k == n => {}
+                None => sink.line_to(p.0, p.1),
+            }
+        } else if let Some(c) = ctrl {
+            let mid = ((c.0 + p.0) * 0.5, (c.1 + p.1) * 0.5);
+            sink.quad_to(c.0, c.1, mid.0, mid.1);
+            ctrl = Some((p.0, p.1));
+        } else {
+            ctrl = Some((p.0, p.1));
+        }
+    }
+    if let Some(c) = ctrl {
+        sink.quad_to(c.0, c.1, start.0, start.1);
+    }
+    sink.close();
+}
+
+// ----------------------------------------------------------------------------
+// Outline sink — collects em-normalized path commands
+// ----------------------------------------------------------------------------
+
+/// Collects outline commands in font units and emits em-normalized
+/// [`PathCmd`]s. Mirrors the `PathBuilder` in `tools/import-font`.
+pub(crate) struct PathBuilder {
+    scale: f32,
+    cmds: Vec<PathCmd>,
+}
+
+impl PathBuilder {
+    fn new(scale: f32) -> Self {
+        Self {
+            scale,
+            cmds: Vec::new(),
+        }
+    }
+
+    pub(crate) fn move_to(&mut self, x: f32, y: f32) {
+        // A lone MoveTo (empty contour) contributes nothing; drop it.
+        if let Some(PathCmd::MoveTo(..)) = self.cmds.last() {
+            self.cmds.pop();
+        }
+        let s = self.scale;
+        self.cmds.push(PathCmd::MoveTo(x * s, y * s));
+    }
+
+    pub(crate) fn line_to(&mut self, x: f32, y: f32) {
+        let s = self.scale;
+        self.cmds.push(PathCmd::LineTo(x * s, y * s));
+    }
+
+    pub(crate) fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let s = self.scale;
+        self.cmds
+            .push(PathCmd::QuadTo(x1 * s, y1 * s, x * s, y * s));
+    }
+
+    pub(crate) fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let s = self.scale;
+        self.cmds.push(PathCmd::CubicTo(
+            x1 * s,
+            y1 * s,
+            x2 * s,
+            y2 * s,
+            x * s,
+            y * s,
+        ));
+    }
+
+    /// Close the current contour. Contours close implicitly at the next
+    /// `MoveTo`, so this only discards a trailing empty contour.
+    pub(crate) fn close(&mut self) {
+        if let Some(PathCmd::MoveTo(..)) = self.cmds.last() {
+            self.cmds.pop();
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Big-endian readers
+// ----------------------------------------------------------------------------
+
+#[inline]
+pub(crate) fn read_u8(data: &[u8], pos: usize) -> Result<u8, SfntError> {
+    data.get(pos).copied().ok_or(SfntError::Truncated)
+}
+
+#[inline]
+pub(crate) fn read_u16(data: &[u8], pos: usize) -> Result<u16, SfntError> {
+    match data.get(pos..pos + 2) {
+        Some(b) => Ok(u16::from_be_bytes([b[0], b[1]])),
+        None => Err(SfntError::Truncated),
+    }
+}
+
+#[inline]
+fn read_i16(data: &[u8], pos: usize) -> Result<i16, SfntError> {
+    read_u16(data, pos).map(|v| v as i16)
+}
+
+#[inline]
+pub(crate) fn read_u32(data: &[u8], pos: usize) -> Result<u32, SfntError> {
+    match data.get(pos..pos + 4) {
+        Some(b) => Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(SfntError::Truncated),
+    }
+}
+
+#[inline]
+fn read_tag(data: &[u8], pos: usize) -> Result<[u8; 4], SfntError> {
+    match data.get(pos..pos + 4) {
+        Some(b) => Ok([b[0], b[1], b[2], b[3]]),
+        None => Err(SfntError::Truncated),
+    }
+}
+
+/// F2Dot14 fixed-point (composite glyph scale).
+#[inline]
+fn read_f2dot14(data: &[u8], pos: usize) -> Result<f32, SfntError> {
+    const INV_16384: f32 = 1.0 / 16384.0;
+    read_i16(data, pos).map(|v| f32::from(v) * INV_16384)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Assemble a minimal TrueType font with `.notdef`, space, and a square
+    /// glyph mapped to 'A' (glyph 2) plus a composite 'B' (glyph 3) that
+    /// references the square with a translation.
+    fn build_test_ttf() -> Vec<u8> {
+        fn be16(v: &mut Vec<u8>, x: u16) {
+            v.extend_from_slice(&x.to_be_bytes());
+        }
+        fn be32(v: &mut Vec<u8>, x: u32) {
+            v.extend_from_slice(&x.to_be_bytes());
+        }
+
+        // head (54 bytes): unitsPerEm at 18, indexToLocFormat at 50.
+        let mut head = vec![0u8; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        head[50..52].copy_from_slice(&0i16.to_be_bytes());
+
+        // maxp (v0.5, 6 bytes).
+        let mut maxp = Vec::new();
+        be32(&mut maxp, 0x0000_5000);
+        be16(&mut maxp, 4);
+
+        // hhea (36 bytes): ascender 800, descender -200, numberOfHMetrics 4.
+        let mut hhea = vec![0u8; 36];
+        hhea[4..6].copy_from_slice(&800i16.to_be_bytes());
+        hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+        hhea[34..36].copy_from_slice(&4u16.to_be_bytes());
+
+        let mut hmtx = Vec::new();
+        for adv in [500u16, 250, 600, 700] {
+            be16(&mut hmtx, adv);
+            be16(&mut hmtx, 0);
+        }
+
+        // cmap format 4: ' ' → 1, 'A' → 2, 'B' → 3.
+        let mut sub = Vec::new();
+        let seg_count = 3u16; // [0x20], [0x41..0x42], [0xFFFF]
+        be16(&mut sub, 4);
+        be16(&mut sub, 14 + seg_count * 8 + 2);
+        be16(&mut sub, 0);
+        be16(&mut sub, seg_count * 2);
+        be16(&mut sub, 4);
+        be16(&mut sub, 1);
+        be16(&mut sub, 2);
+        for end in [0x20u16, 0x42, 0xFFFF] {
+            be16(&mut sub, end);
+        }
+        be16(&mut sub, 0);
+        for start in [0x20u16, 0x41, 0xFFFF] {
+            be16(&mut sub, start);
+        }
+        for delta in [1u16.wrapping_sub(0x20), 2u16.wrapping_sub(0x41), 1] {
+            be16(&mut sub, delta);
+        }
+        for _ in 0..seg_count {
+            be16(&mut sub, 0);
+        }
+        let mut cmap = Vec::new();
+        be16(&mut cmap, 0);
+        be16(&mut cmap, 1);
+        be16(&mut cmap, 3);
+        be16(&mut cmap, 1);
+        be32(&mut cmap, 12);
+        cmap.extend_from_slice(&sub);
+
+        // glyf: glyph 2 = square (100,0)-(500,400), glyph 3 = composite of 2.
+        let mut square = Vec::new();
+        be16(&mut square, 1);
+        for v in [100i16, 0, 500, 400] {
+            be16(&mut square, v as u16);
+        }
+        be16(&mut square, 3); // endPts
+        be16(&mut square, 0); // instructionLength
+        square.extend_from_slice(&[0x01; 4]); // on-curve, long x/y
+        for x in [100i16, 400, 0, -400] {
+            be16(&mut square, x as u16);
+        }
+        for y in [0i16, 0, 400, 0] {
+            be16(&mut square, y as u16);
+        }
+        if square.len() % 2 == 1 {
+            square.push(0);
+        }
+        let mut composite = Vec::new();
+        be16(&mut composite, 0xFFFF);
+        for v in [100i16, 0, 500, 400] {
+            be16(&mut composite, v as u16);
+        }
+        be16(&mut composite, 0x0001 | 0x0002); // words + xy values
+        be16(&mut composite, 2);
+        be16(&mut composite, 100);
+        be16(&mut composite, 0);
+        let mut glyf = square.clone();
+        glyf.extend_from_slice(&composite);
+
+        let mut loca = Vec::new();
+        let sq = square.len() as u16 / 2;
+        let cp = composite.len() as u16 / 2;
+        for off in [0u16, 0, 0, sq, sq + cp] {
+            be16(&mut loca, off);
+        }
+
+        let tables: [(&[u8; 4], Vec<u8>); 7] = [
+            (b"cmap", cmap),
+            (b"glyf", glyf),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"maxp", maxp),
+        ];
+        let mut font = Vec::new();
+        be32(&mut font, 0x0001_0000);
+        be16(&mut font, tables.len() as u16);
+        be16(&mut font, 0);
+        be16(&mut font, 0);
+        be16(&mut font, 0);
+        let mut offset = 12 + tables.len() * 16;
+        let mut body = Vec::new();
+        for (tag, data) in &tables {
+            font.extend_from_slice(*tag);
+            be32(&mut font, 0);
+            be32(&mut font, offset as u32);
+            be32(&mut font, data.len() as u32);
+            body.extend_from_slice(data);
+            while body.len() % 4 != 0 {
+                body.push(0);
+            }
+            offset = 12 + tables.len() * 16 + body.len();
+        }
+        font.extend_from_slice(&body);
+        font
+    }
+
+    #[test]
+    fn parse_minimal_truetype() {
+        let font = SfntFont::parse(build_test_ttf()).expect("valid font");
+        assert_eq!(font.units_per_em(), 1000);
+        assert_eq!(font.num_glyphs(), 4);
+        assert!(!font.is_cff());
+        assert!((font.ascender() - 0.8).abs() < 1e-6);
+        assert!((font.descender() + 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cmap_format4_maps_characters() {
+        let font = SfntFont::parse(build_test_ttf()).unwrap();
+        assert_eq!(font.glyph_index(' '), Some(1));
+        assert_eq!(font.glyph_index('A'), Some(2));
+        assert_eq!(font.glyph_index('B'), Some(3));
+        assert_eq!(font.glyph_index('C'), None);
+        assert_eq!(font.glyph_index('株'), None);
+    }
+
+    #[test]
+    fn cmap_format12_rejects_corrupt_groups() {
+        // Header (format, reserved, length, language, numGroups) + groups.
+        let mut sub = Vec::new();
+        sub.extend_from_slice(&12u16.to_be_bytes());
+        sub.extend_from_slice(&[0; 10]);
+        sub.extend_from_slice(&2u32.to_be_bytes());
+        for (start, end, first) in [(0x41u32, 0x5A, 10), (0x1_0000, 0x1_FFFF, u32::MAX - 4)] {
+            for v in [start, end, first] {
+                sub.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+        assert_eq!(cmap12_lookup(&sub, 0, 0x42), Some(11));
+        assert_eq!(cmap12_lookup(&sub, 0, 0x1_0000), None);
+        assert_eq!(
+            cmap12_lookup(&sub, 0, 0x1_0010),
+            None,
+            "overflowing glyph id"
+        );
+        assert_eq!(cmap12_lookup(&sub, 0, 0x30), None);
+        // Truncated subtable: group count larger than the data.
+        assert_eq!(cmap12_lookup(&sub[..30], 0, 0x1_0010), None);
+    }
+
+    #[test]
+    fn advances_are_em_normalized() {
+        let font = SfntFont::parse(build_test_ttf()).unwrap();
+        assert!((font.advance(2) - 0.6).abs() < 1e-6);
+        assert!((font.advance(3) - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn simple_glyph_outline_is_square() {
+        let font = SfntFont::parse(build_test_ttf()).unwrap();
+        let cmds = font.outline(2).unwrap();
+        assert!(matches!(cmds[0], PathCmd::MoveTo(..)));
+        assert_eq!(cmds.len(), 4, "move + 3 lines, closing line implicit");
+        let pts: Vec<(f32, f32)> = cmds.iter().map(PathCmd::end_point).collect();
+        let max_x = pts.iter().map(|p| p.0).fold(f32::MIN, f32::max);
+        let max_y = pts.iter().map(|p| p.1).fold(f32::MIN, f32::max);
+        assert!((max_x - 0.5).abs() < 1e-6);
+        assert!((max_y - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn composite_glyph_applies_offset() {
+        let font = SfntFont::parse(build_test_ttf()).unwrap();
+        let cmds = font.outline(3).unwrap();
+        let min_x = cmds
+            .iter()
+            .map(|c| c.end_point().0)
+            .fold(f32::MAX, f32::min);
+        assert!((min_x - 0.2).abs() < 1e-6, "component shifted by 100 units");
+    }
+
+    #[test]
+    fn rasterize_has_inside_pixels() {
+        let font = SfntFont::parse(build_test_ttf()).unwrap();
+        let sdf = font.rasterize('A').expect("A mapped");
+        assert!(sdf.data.iter().any(|d| *d < 0.0));
+        assert!((sdf.advance - 0.6).abs() < 1e-6);
+        let space = font.rasterize(' ').expect("space mapped");
+        assert!(space.data.iter().all(|d| *d > 0.0));
+        assert!((space.advance - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn generate_with_fonts_falls_back_to_dispatcher() {
+        let fonts = [SfntFont::parse(build_test_ttf()).unwrap()];
+        let params = MetaFontParams::sans_regular();
+        let from_font = generate_with_fonts('A', &params, &fonts);
+        assert!((from_font.advance - 0.6).abs() < 1e-6);
+        let fallback = generate_with_fonts('あ', &params, &fonts);
+        assert!(fallback.data.iter().any(|d| *d < 0.0));
+    }
+
+    #[test]
+    fn font_count_follows_the_provider_chain() {
+        use crate::atlas::SdfAtlas;
+        use crate::glyph::provider::ProviderChain;
+
+        let font = || SfntFont::parse(build_test_ttf()).unwrap();
+        let mut atlas = SdfAtlas::new(4, MetaFontParams::sans_regular());
+        atlas.register_font(font());
+        atlas.register_provider(font());
+        assert_eq!(atlas.font_count(), 2);
+        let mut chain = ProviderChain::builtin();
+        chain.push(font());
+        atlas.set_providers(chain);
+        assert_eq!(atlas.font_count(), 1);
+        atlas.set_providers(ProviderChain::builtin());
+        assert_eq!(atlas.font_count(), 0);
+    }
+
+    #[test]
+    fn parametric_only_policy_skips_runtime_fonts() {
+        let fonts = [SfntFont::parse(build_test_ttf()).unwrap()];
+        let params = MetaFontParams::sans_regular();
+        let policy = GlyphSourcePolicy::parametric_only();
+        let sdf = generate_with_fonts_and_policy('A', &params, &fonts, &policy);
+        assert_eq!(
+            sdf.data,
+            crate::glyph::dispatcher::generate_with_policy('A', &params, &policy).data
+        );
+        assert!((sdf.advance - 0.6).abs() > 1e-6);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(
+            SfntFont::parse(vec![1, 2, 3, 4, 5]).unwrap_err(),
+            SfntError::UnknownFormat
+        );
+        assert_eq!(
+            SfntFont::parse(vec![0, 1]).unwrap_err(),
+            SfntError::Truncated
+        );
+    }
+
+    #[test]
+    fn missing_table_is_reported() {
+        let mut data = build_test_ttf();
+        // Rename the `head` record tag so it can't be found.
+        let pos = data.windows(4).position(|w| w == b"head").unwrap();
+        data[pos..pos + 4].copy_from_slice(b"hexx");
+        assert_eq!(
+            SfntFont::parse(data).unwrap_err(),
+            SfntError::MissingTable(*b"head")
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! Typographic variants — small capitals, superior and inferior figures
+//!
+//! The variants are synthesized from the existing builders rather than
+//! drawn separately: a small capital is the uppercase ASCII skeleton
+//! scaled down to the x-height and widened slightly, superior and inferior
+//! figures are the digit (and sign and lowercase) skeletons at
+//! [`SUPERIOR_SCALE`], raised to the cap height or dropped below the
+//! baseline.
+//!
+//! Scaling a skeleton keeps the pen, so stems come out as thick as those
+//! of the full-size letters. At the smaller size that reads too dark —
+//! the counters shrink but the stems do not — so the pen is thinned by
+//! [`OPTICAL_THINNING`] of the scale drop, which keeps small and full-size
+//! stems optically equal.
+//!
+//! Every variant has a Unicode code point (ᴀ ʙ ᴄ, ¹ ² ³, ₀ ₁ ₂, ...), so
+//! atlases and shapers handle them like any other character;
+//! [`FontFeatures`] maps plain text to them (`smcp`, `c2sc`, `sups`,
+//! `subs`, `ordn`) and is applied by
+//! [`TextShaper::set_features`](crate::shaper::TextShaper::set_features).
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use super::latin::append_mapped;
+use super::{GlyphGenerator, GlyphSkeleton};
+use crate::stroke::Point2;
+
+/// Scale of superior and inferior figures (and of the ordinal indicators
+/// ª º).
+pub const SUPERIOR_SCALE: f32 = 0.6;
+/// Fraction of the scale drop taken off the pen width of a scaled variant:
+/// 0 keeps the full-size stems, 1 scales them with the skeleton.
+pub const OPTICAL_THINNING: f32 = 0.3;
+/// Horizontal stretch of small capitals relative to their height, so
+/// they do not look condensed next to lowercase letters.
+const SMALL_CAP_WIDEN: f32 = 1.06;
+/// Extra advance of small capitals (em), restoring the side bearings lost
+/// in scaling.
+const SMALL_CAP_TRACKING: f32 = 0.03;
+/// Left side bearing of superior and inferior figures (em).
+const FIGURE_BEARING: f32 = 0.02;
+/// Baseline of inferior figures below the text baseline (em).
+const INFERIOR_DROP: f32 = 0.15;
+
+/// Lowercase letters and their small capitals. `x` has none in Unicode
+/// and stays lowercase.
+const SMALL_CAPS: [(char, char); 25] = [
+    ('a', 'ᴀ'),
+    ('b', 'ʙ'),
+    ('c', 'ᴄ'),
+    ('d', 'ᴅ'),
+    ('e', 'ᴇ'),
+    ('f', 'ꜰ'),
+    ('g', 'ɢ'),
+    ('h', 'ʜ'),
+    ('i', 'ɪ'),
+    ('j', 'ᴊ'),
+    ('k', 'ᴋ'),
+    ('l', 'ʟ'),
+    ('m', 'ᴍ'),
+    ('n', 'ɴ'),
+    ('o', 'ᴏ'),
+    ('p', 'ᴘ'),
+    ('q', 'ꞯ'),
+    ('r', 'ʀ'),
+    ('s', 'ꜱ'),
+    ('t', 'ᴛ'),
+    ('u', 'ᴜ'),
+    ('v', 'ᴠ'),
+    ('w', 'ᴡ'),
+    ('y', 'ʏ'),
+    ('z', 'ᴢ'),
+];
+
+/// Superior forms. `q` has none in Unicode.
+const SUPERIORS: [(char, char); 40] = [
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('a', 'ᵃ'),
+    ('b', 'ᵇ'),
+    ('c', 'ᶜ'),
+    ('d', 'ᵈ'),
+    ('e', 'ᵉ'),
+    ('f', 'ᶠ'),
+    ('g', 'ᵍ'),
+    ('h', 'ʰ'),
+    ('i', 'ⁱ'),
+    ('j', 'ʲ'),
+    ('k', 'ᵏ'),
+    ('l', 'ˡ'),
+    ('m', 'ᵐ'),
+    ('n', 'ⁿ'),
+    ('o', 'ᵒ'),
+    ('p', 'ᵖ'),
+    ('r', 'ʳ'),
+    ('s', 'ˢ'),
+    ('t', 'ᵗ'),
+    ('u', 'ᵘ'),
+    ('v', 'ᵛ'),
+    ('w', 'ʷ'),
+    ('x', 'ˣ'),
+    ('y', 'ʸ'),
+    ('z', 'ᶻ'),
+];
+
+/// Inferior forms. Unicode only encodes some of the letters.
+const INFERIORS: [(char, char); 32] = [
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('h', 'ₕ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('l', 'ₗ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('p', 'ₚ'),
+    ('r', 'ᵣ'),
+    ('s', 'ₛ'),
+    ('t', 'ₜ'),
+    ('u', 'ᵤ'),
+    ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+/// How a variant derives from its base character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantForm {
+    /// Uppercase skeleton at x-height (ᴀ ʙ ᴄ, base `a` `b` `c`)
+    SmallCap,
+    /// Reduced and raised to the cap height (¹ ⁿ ⁽, base `1` `n` `(`)
+    Superior,
+    /// Reduced and dropped below the baseline (₁ ₙ ₍, base `1` `n` `(`)
+    Inferior,
+}
+
+/// Which variants [`FontFeatures::substitute`] switches to, named after
+/// the OpenType features they imitate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FontFeatures(u8);
+
+impl FontFeatures {
+    /// Plain text.
+    pub const NONE: Self = Self(0);
+    /// `smcp`: lowercase letters become small capitals.
+    pub const SMALL_CAPS: Self = Self(1);
+    /// `c2sc`: uppercase letters become small capitals.
+    pub const CAPS_TO_SMALL_CAPS: Self = Self(1 << 1);
+    /// `sups`: digits, `+ - = ( )` and lowercase letters become superior.
+    pub const SUPERSCRIPT: Self = Self(1 << 2);
+    /// `subs`: digits, `+ - = ( )` and lowercase letters become inferior.
+    pub const SUBSCRIPT: Self = Self(1 << 3);
+    /// `ordn`: lowercase letters right after a figure become superior
+    /// (1st → 1ˢᵗ).
+    pub const ORDINALS: Self = Self(1 << 4);
+
+    /// The character to draw for `ch`. `after_figure` tells whether the
+    /// previous character was a digit or an ordinal, for
+    /// [`FontFeatures::ORDINALS`]. Superscript wins over subscript, both
+    /// over ordinals and small capitals; characters without the requested
+    /// variant pass through.
+    #[must_use]
+    pub const fn substitute(self, ch: char, after_figure: bool) -> char {
+        let variant = if self.contains(Self::SUPERSCRIPT) {
+            superior(ch)
+        } else if self.contains(Self::SUBSCRIPT) {
+            inferior(ch)
+        } else if self.contains(Self::ORDINALS) && after_figure && ch.is_ascii_lowercase() {
+            superior(ch)
+        } else if self.contains(Self::SMALL_CAPS) && ch.is_ascii_lowercase() {
+            small_capital(ch)
+        } else if self.contains(Self::CAPS_TO_SMALL_CAPS) && ch.is_ascii_uppercase() {
+            small_capital(ch.to_ascii_lowercase())
+        } else {
+            None
+        };
+        match variant {
+            Some(v) => v,
+            None => ch,
+        }
+    }
+}
+
+flag_set!(FontFeatures);
+
+/// `table` entry whose first (`forward`) or second element is `ch`.
+const fn find(table: &[(char, char)], ch: char, forward: bool) -> Option<char> {
+    let mut i = 0;
+    while i < table.len() {
+        let (base, variant) = table[i];
+        if forward && base == ch {
+            return Some(variant);
+        }
+        if !forward && variant == ch {
+            return Some(base);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Small capital of the lowercase letter `ch`.
+#[must_use]
+pub const fn small_capital(ch: char) -> Option<char> {
+    find(&SMALL_CAPS, ch, true)
+}
+
+/// Superior form of `ch`.
+#[must_use]
+pub const fn superior(ch: char) -> Option<char> {
+    find(&SUPERIORS, ch, true)
+}
+
+/// Inferior form of `ch`.
+#[must_use]
+pub const fn inferior(ch: char) -> Option<char> {
+    find(&INFERIORS, ch, true)
+}
+
+/// The form of the variant `ch` and the ASCII character it is built from,
+/// or `None` when `ch` is not a variant.
+#[must_use]
+pub const fn variant_form(ch: char) -> Option<(VariantForm, char)> {
+    if let Some(base) = find(&SMALL_CAPS, ch, false) {
+        Some((VariantForm::SmallCap, base))
+    } else if let Some(base) = find(&SUPERIORS, ch, false) {
+        Some((VariantForm::Superior, base))
+    } else if let Some(base) = find(&INFERIORS, ch, false) {
+        Some((VariantForm::Inferior, base))
+    } else {
+        None
+    }
+}
+
+/// Does this module draw `ch`?
+#[must_use]
+pub const fn covers(ch: char) -> bool {
+    variant_form(ch).is_some()
+}
+
+impl VariantForm {
+    /// Size of the variant relative to its base skeleton under `gen`.
+    fn scale(self, gen: &GlyphGenerator) -> f32 {
+        match self {
+            Self::SmallCap => gen.x_height / gen.cap_height,
+            Self::Superior | Self::Inferior => SUPERIOR_SCALE,
+        }
+    }
+}
+
+impl GlyphGenerator {
+    /// This generator with the pen thinned for drawing `form`
+    /// ([`OPTICAL_THINNING`]).
+    #[must_use]
+    pub fn compensated(&self, form: VariantForm) -> Self {
+        let mut gen = *self;
+        gen.pen.base_width *= 1.0 - (1.0 - form.scale(self)) * OPTICAL_THINNING;
+        gen
+    }
+
+    /// `cap` (an uppercase ASCII letter) scaled to the x-height.
+    fn small_cap(&self, cap: char) -> GlyphSkeleton {
+        let base = self.ascii(cap);
+        let s = VariantForm::SmallCap.scale(self);
+        let sx = s * SMALL_CAP_WIDEN;
+        let dx = SMALL_CAP_TRACKING * 0.5;
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = base.advance * sx + SMALL_CAP_TRACKING;
+        append_mapped(&mut skel, &base, |p| Point2::new(p.x * sx + dx, p.y * s));
+        skel
+    }
+
+    /// Superior form of the ASCII character `base`: its skeleton at
+    /// [`SUPERIOR_SCALE`] with the top of the figures at the cap height.
+    pub(super) fn superior(&self, base: char) -> GlyphSkeleton {
+        self.figure(base, self.cap_height * (1.0 - SUPERIOR_SCALE))
+    }
+
+    /// `base` at [`SUPERIOR_SCALE`], its baseline moved to `rise`.
+    fn figure(&self, base: char, rise: f32) -> GlyphSkeleton {
+        let base = self.ascii(base);
+        let s = SUPERIOR_SCALE;
+        let mut skel = GlyphSkeleton::empty();
+        skel.advance = base.advance * s + 2.0 * FIGURE_BEARING;
+        append_mapped(&mut skel, &base, |p| {
+            Point2::new(p.x * s + FIGURE_BEARING, p.y * s + rise)
+        });
+        skel
+    }
+}
+
+/// Skeleton of the variant `ch`, or `None` when `ch` is not one. Draw it
+/// with [`pen`] to keep the stems optically equal to the full-size letters.
+pub(crate) fn build_skeleton(gen: &GlyphGenerator, ch: char) -> Option<GlyphSkeleton> {
+    let (form, base) = variant_form(ch)?;
+    Some(gen.upright(|up| match form {
+        VariantForm::SmallCap => up.small_cap(base.to_ascii_uppercase()),
+        VariantForm::Superior => up.superior(base),
+        VariantForm::Inferior => up.figure(base, -INFERIOR_DROP),
+    }))
+}
+
+/// The generator to draw `ch` with: `gen` compensated for the variant's
+/// form, or `gen` itself when `ch` is not a variant.
+pub(crate) fn pen(gen: &GlyphGenerator, ch: char) -> GlyphGenerator {
+    variant_form(ch).map_or(*gen, |(form, _)| gen.compensated(form))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::latin::extent;
+    use crate::glyph::provider::{GlyphProvider, VariantsProvider};
+    use crate::glyph::GlyphMetrics;
+    use crate::param::MetaFontParams;
+
+    fn gen() -> GlyphGenerator {
+        GlyphGenerator::new(&MetaFontParams::sans_regular())
+    }
+
+    #[test]
+    fn tables_round_trip() {
+        for table in [&SMALL_CAPS[..], &SUPERIORS[..], &INFERIORS[..]] {
+            for &(base, variant) in table {
+                let (_, back) = variant_form(variant).unwrap();
+                assert_eq!(back, base, "{variant}");
+                assert!(!covers(base), "{base}");
+            }
+        }
+        assert_eq!(variant_form('ᴀ'), Some((VariantForm::SmallCap, 'a')));
+        assert_eq!(variant_form('²'), Some((VariantForm::Superior, '2')));
+        assert_eq!(variant_form('₍'), Some((VariantForm::Inferior, '(')));
+        assert_eq!(variant_form('x'), None);
+    }
+
+    #[test]
+    fn substitution_follows_features() {
+        let f = FontFeatures::SMALL_CAPS;
+        assert_eq!(f.substitute('a', false), 'ᴀ');
+        assert_eq!(f.substitute('A', false), 'A');
+        assert_eq!(f.substitute('x', false), 'x');
+        let f = f | FontFeatures::CAPS_TO_SMALL_CAPS;
+        assert_eq!(f.substitute('A', false), 'ᴀ');
+        assert_eq!(f.substitute('1', false), '1');
+
+        let f = FontFeatures::SUPERSCRIPT | FontFeatures::SUBSCRIPT;
+        assert_eq!(f.substitute('2', false), '²');
+        assert_eq!(FontFeatures::SUBSCRIPT.substitute('2', false), '₂');
+        assert_eq!(FontFeatures::SUBSCRIPT.substitute('b', false), 'b');
+
+        let f = FontFeatures::ORDINALS | FontFeatures::SMALL_CAPS;
+        assert_eq!(f.substitute('s', true), 'ˢ');
+        assert_eq!(f.substitute('s', false), 'ꜱ');
+        assert_eq!(FontFeatures::NONE.substitute('a', true), 'a');
+        assert!(FontFeatures::NONE.is_empty());
+        assert!(f.contains(FontFeatures::ORDINALS) && !f.contains(FontFeatures::SUPERSCRIPT));
+    }
+
+    #[test]
+    fn small_caps_reach_x_height() {
+        let gen = gen();
+        for ch in ['ʜ', 'ᴇ', 'ᴛ'] {
+            let (lo, hi) = extent(&build_skeleton(&gen, ch).unwrap());
+            assert!(lo.y.abs() < 0.01, "{ch}: {lo:?}");
+            assert!((hi.y - gen.x_height).abs() < 0.01, "{ch}: {hi:?}");
+        }
+    }
+
+    #[test]
+    fn superiors_hang_from_cap_height() {
+        let gen = gen();
+        for ch in ['¹', '⁴', '⁹'] {
+            let (_, hi) = extent(&build_skeleton(&gen, ch).unwrap());
+            assert!((hi.y - gen.cap_height).abs() < 0.02, "{ch}: {hi:?}");
+        }
+        let (lo, hi) = extent(&build_skeleton(&gen, '₄').unwrap());
+        assert!((lo.y + INFERIOR_DROP).abs() < 0.02, "{lo:?}");
+        assert!(hi.y < gen.cap_height * SUPERIOR_SCALE);
+    }
+
+    #[test]
+    fn compensated_pen_is_thinner_but_not_scaled() {
+        let gen = gen();
+        let full = gen.pen.base_width;
+        for form in [VariantForm::SmallCap, VariantForm::Superior] {
+            let thin = gen.compensated(form).pen.base_width;
+            assert!(thin < full && thin > full * form.scale(&gen), "{form:?}");
+        }
+        // Stems of a small capital stay close to those of the capital.
+        let cap = gen.metrics(&gen.build_skeleton(b'I'));
+        let small = VariantsProvider
+            .metrics('ɪ', &MetaFontParams::sans_regular())
+            .unwrap();
+        let stem = |m: GlyphMetrics| m.ink_max.x - m.ink_min.x;
+        assert!(stem(small) < stem(cap) && stem(small) > stem(cap) * 0.8);
+    }
+
+    #[test]
+    fn render_matches_metrics() {
+        let params = MetaFontParams::serif_italic();
+        for ch in ['ᴀ', 'ᴍ', '³', 'ⁿ', '₉', '₎'] {
+            let result = VariantsProvider.generate(ch, &params).unwrap();
+            let metrics = VariantsProvider.metrics(ch, &params).unwrap();
+            assert_eq!(result.metrics, metrics, "{ch}");
+            assert!(result.sdf.data.iter().any(|d| *d < 0.0), "{ch}");
+        }
+        assert!(VariantsProvider.generate('a', &params).is_none());
+    }
+}
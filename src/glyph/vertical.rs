@@ -0,0 +1,292 @@
+//! Vertical writing (tategaki) glyph variants — the `vert` alternates
+//!
+//! A handful of characters change shape or position when Japanese is set
+//! top to bottom: the long vowel mark, dashes and brackets turn 90°, 、 and
+//! 。 move to the upper-right corner, and small kana shift up and right.
+//! Everything else is used as is.
+//!
+//! The variants are applied to a finished [`GlyphResult`] (tile, frame and
+//! metrics together), so they work the same for embedded outlines,
+//! registered fonts and parametric skeletons. Vertical forms sit on a
+//! fixed 1-em pitch: a proportional glyph is first centred in a 1-em cell.
+//!
+//! License: MIT
+//! Author: Moroya Sakamoto
+
+use crate::glyph::font_render::{TILE_EM_BOTTOM, TILE_EM_TOP};
+use crate::glyph::{GlyphMetrics, GlyphProvenance, GlyphResult, GLYPH_SDF_SIZE};
+use crate::stroke::Point2;
+
+/// Vertical pitch of a glyph in vertical text (em units).
+const VERTICAL_PITCH: f32 = 1.0;
+/// How far small kana move up and right of the em centre in vertical text.
+const SMALL_KANA_OFFSET: f32 = 0.12;
+/// Vertical centre of the unit box the CJK skeletons are drawn in.
+const SKELETON_EM_CENTRE: f32 = 0.5;
+
+/// Direction text is set in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WritingMode {
+    /// Left to right, lines top to bottom
+    #[default]
+    Horizontal,
+    /// Top to bottom, lines right to left (tategaki)
+    Vertical,
+}
+
+/// How a character's vertical form derives from its horizontal glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalForm {
+    /// Turned 90° clockwise about the em-box centre and centred in the
+    /// column (ー, 〜, —, brackets)
+    Rotated,
+    /// Ink moved to the diagonally opposite corner of the em box (、。)
+    Raised,
+    /// Ink moved up and right of the em centre (ぁ, ッ, ...)
+    SmallKana,
+}
+
+/// The vertical form of `ch`, or `None` when the horizontal glyph is used
+/// unchanged.
+#[must_use]
+pub const fn vertical_form(ch: char) -> Option<VerticalForm> {
+    match ch {
+        'ー'
+        | '〜'
+        | '〰'
+        | '～'
+        | '‐'
+        | '–'
+        | '—'
+        | '―'
+        | '‥'
+        | '…'
+        | '－'
+        | '：'
+        | '＿'
+        | '＝'
+        | '〈'..='】'
+        | '〔'..='〛'
+        | '（'
+        | '）'
+        | '［'
+        | '］'
+        | '｛'
+        | '｝'
+        | '＜'
+        | '＞' => Some(VerticalForm::Rotated),
+        '、' | '。' | '，' | '．' => Some(VerticalForm::Raised),
+        'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' | 'っ' | 'ゃ' | 'ゅ' | 'ょ' | 'ゎ' | 'ゕ' | 'ゖ'
+        | 'ァ' | 'ィ' | 'ゥ' | 'ェ' | 'ォ' | 'ッ' | 'ャ' | 'ュ' | 'ョ' | 'ヮ' | 'ヵ' | 'ヶ' => {
+            Some(VerticalForm::SmallKana)
+        }
+        _ => None,
+    }
+}
+
+/// `result` (the horizontal glyph of `ch`) in its form for `mode`.
+/// Missing glyphs and characters without a vertical form pass through.
+#[must_use]
+pub fn orient(ch: char, result: GlyphResult, mode: WritingMode) -> GlyphResult {
+    match (mode, vertical_form(ch)) {
+        (WritingMode::Vertical, Some(form)) if result.provenance != GlyphProvenance::Missing => {
+            form.apply(result)
+        }
+        _ => result,
+    }
+}
+
+impl VerticalForm {
+    /// Turn a horizontal glyph into this vertical form.
+    #[must_use]
+    pub fn apply(self, result: GlyphResult) -> GlyphResult {
+        let dx = (VERTICAL_PITCH - result.metrics.advance) * 0.5;
+        let mut result = translated(result, dx, 0.0);
+        result.metrics.advance = VERTICAL_PITCH;
+        result.sdf.advance = VERTICAL_PITCH;
+        let centre = Point2::new(VERTICAL_PITCH * 0.5, em_centre(result.provenance));
+        let (lo, hi) = (result.metrics.ink_min, result.metrics.ink_max);
+        let ink_centre = Point2::new((lo.x + hi.x) * 0.5, (lo.y + hi.y) * 0.5);
+        let mut result = match self {
+            Self::Rotated => {
+                // Turned glyphs sit in the middle of the column whatever
+                // their horizontal baseline offset was.
+                let turned = rotated(result, centre);
+                let (lo, hi) = (turned.metrics.ink_min, turned.metrics.ink_max);
+                let dx = if turned.metrics.has_ink() {
+                    centre.x - (lo.x + hi.x) * 0.5
+                } else {
+                    0.0
+                };
+                translated(turned, dx, 0.0)
+            }
+            Self::Raised => translated(
+                result,
+                2.0 * (centre.x - ink_centre.x),
+                2.0 * (centre.y - ink_centre.y),
+            ),
+            Self::SmallKana => translated(
+                result,
+                centre.x + SMALL_KANA_OFFSET - ink_centre.x,
+                centre.y + SMALL_KANA_OFFSET - ink_centre.y,
+            ),
+        };
+        result.sdf.lsb = result.metrics.lsb;
+        result
+    }
+}
+
+/// Vertical centre of the em box the glyph was drawn in: outline tiles
+/// sample the font's em square, skeletons use the CJK unit box.
+fn em_centre(provenance: GlyphProvenance) -> f32 {
+    match provenance {
+        GlyphProvenance::Outline => (TILE_EM_BOTTOM + TILE_EM_TOP) * 0.5,
+        _ => SKELETON_EM_CENTRE,
+    }
+}
+
+/// Move tile and ink by `(dx, dy)`; the advance stays.
+fn translated(mut result: GlyphResult, dx: f32, dy: f32) -> GlyphResult {
+    let d = Point2::new(dx, dy);
+    result.sdf.bbox_min = result.sdf.bbox_min + d;
+    result.sdf.bbox_max = result.sdf.bbox_max + d;
+    if result.metrics.has_ink() {
+        let m = &result.metrics;
+        result.metrics = GlyphMetrics::from_ink(m.advance, m.ink_min + d, m.ink_max + d);
+    }
+    result
+}
+
+/// Turn tile and ink 90° clockwise about `c`: `(x, y) → (c.x + y - c.y,
+/// c.y - x + c.x)`. The tile is re-indexed, not resampled, so the SDF is
+/// exact.
+fn rotated(mut result: GlyphResult, c: Point2) -> GlyphResult {
+    let turn = |p: Point2| Point2::new(c.x + p.y - c.y, c.y - p.x + c.x);
+    let sdf = &mut result.sdf;
+    let old = sdf.data;
+    let n = GLYPH_SDF_SIZE;
+    for y in 0..n {
+        for x in 0..n {
+            sdf.data[y * n + x] = old[x * n + (n - 1 - y)];
+        }
+    }
+    let (a, b) = (turn(sdf.bbox_min), turn(sdf.bbox_max));
+    sdf.bbox_min = Point2::new(a.x, b.y);
+    sdf.bbox_max = Point2::new(b.x, a.y);
+    if result.metrics.has_ink() {
+        let m = &result.metrics;
+        let (a, b) = (turn(m.ink_min), turn(m.ink_max));
+        result.metrics =
+            GlyphMetrics::from_ink(m.advance, Point2::new(a.x, b.y), Point2::new(b.x, a.y));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::dispatcher::{generate_result, GlyphSourcePolicy};
+    use crate::param::MetaFontParams;
+
+    fn parametric(ch: char) -> GlyphResult {
+        generate_result(
+            ch,
+            &MetaFontParams::sans_regular(),
+            &GlyphSourcePolicy::parametric_only(),
+        )
+    }
+
+    fn size(m: &GlyphMetrics) -> (f32, f32) {
+        (m.ink_max.x - m.ink_min.x, m.ink_max.y - m.ink_min.y)
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(vertical_form('ー'), Some(VerticalForm::Rotated));
+        assert_eq!(vertical_form('「'), Some(VerticalForm::Rotated));
+        assert_eq!(vertical_form('。'), Some(VerticalForm::Raised));
+        assert_eq!(vertical_form('ゃ'), Some(VerticalForm::SmallKana));
+        assert_eq!(vertical_form('あ'), None);
+        assert_eq!(vertical_form('A'), None);
+    }
+
+    #[test]
+    fn long_vowel_mark_stands_upright() {
+        let h = parametric('ー');
+        let v = orient('ー', parametric('ー'), WritingMode::Vertical);
+        let (hw, hh) = size(&h.metrics);
+        let (vw, vh) = size(&v.metrics);
+        assert!(hw > 3.0 * hh && vh > 3.0 * vw);
+        assert!((vw - hh).abs() < 1e-4 && (vh - hw).abs() < 1e-4);
+        assert!((v.metrics.advance - 1.0).abs() < 1e-6);
+        assert!((v.sdf.lsb - v.metrics.lsb).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rotation_moves_samples_with_the_frame() {
+        let c = Point2::new(0.5, SKELETON_EM_CENTRE);
+        let h = parametric('「');
+        let v = rotated(parametric('「'), c);
+        let n = GLYPH_SDF_SIZE;
+        let at = |r: &GlyphResult, x: usize, y: usize| {
+            let f = |i: usize, lo: f32, hi: f32| lo + (hi - lo) * i as f32 / (n - 1) as f32;
+            let s = &r.sdf;
+            (
+                Point2::new(
+                    f(x, s.bbox_min.x, s.bbox_max.x),
+                    f(y, s.bbox_min.y, s.bbox_max.y),
+                ),
+                s.data[y * n + x],
+            )
+        };
+        for (x, y) in [(0, 0), (10, 50), (63, 7), (31, 31)] {
+            let (p, d) = at(&v, x, y);
+            // Undo the clockwise turn and look the sample up in the source.
+            let q = Point2::new(c.x + c.y - p.y, c.y + p.x - c.x);
+            let (qx, qy) = (n - 1 - y, x);
+            let (q2, d2) = at(&h, qx, qy);
+            assert!((q.x - q2.x).abs() < 1e-4 && (q.y - q2.y).abs() < 1e-4);
+            assert_eq!(d, d2);
+        }
+        // The opening bracket opens downwards: its ink moves to the lower
+        // half, centred in the column.
+        let v = orient('「', h, WritingMode::Vertical);
+        assert!(v.metrics.ink_max.y < 0.75 && v.metrics.ink_min.y < 0.5);
+        assert!(((v.metrics.ink_min.x + v.metrics.ink_max.x) * 0.5 - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn punctuation_moves_to_the_upper_right() {
+        for ch in ['、', '。'] {
+            let v = orient(ch, parametric(ch), WritingMode::Vertical);
+            assert!(v.metrics.ink_min.x > 0.5, "{ch}");
+            assert!(v.metrics.ink_min.y > 0.5, "{ch}");
+        }
+    }
+
+    #[test]
+    fn small_kana_shift_up_and_right() {
+        let h = parametric('ゃ');
+        let v = orient('ゃ', parametric('ゃ'), WritingMode::Vertical);
+        let cx = (v.metrics.ink_min.x + v.metrics.ink_max.x) * 0.5;
+        let cy = (v.metrics.ink_min.y + v.metrics.ink_max.y) * 0.5;
+        assert!((cx - 0.62).abs() < 1e-4 && (cy - 0.62).abs() < 1e-4);
+        assert_eq!(v.sdf.data, h.sdf.data);
+        assert!(v.metrics.ink_max.x <= 1.0 && v.metrics.ink_max.y <= 1.0);
+    }
+
+    #[test]
+    fn horizontal_mode_and_plain_characters_pass_through() {
+        let h = parametric('ー');
+        assert_eq!(
+            orient('ー', parametric('ー'), WritingMode::Horizontal)
+                .sdf
+                .data,
+            h.sdf.data
+        );
+        let a = parametric('あ');
+        let v = orient('あ', parametric('あ'), WritingMode::Vertical);
+        assert_eq!(v.sdf.data, a.sdf.data);
+        assert_eq!(v.sdf.bbox_min, a.sdf.bbox_min);
+    }
+}
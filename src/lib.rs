@@ -67,10 +67,45 @@ extern crate std;
 
 extern crate alloc;
 
+/// `contains`, `union`, `is_empty` and `|` for a `u8` newtype of bit
+/// flags. The flag constants stay with the type.
+macro_rules! flag_set {
+    ($name:ident) => {
+        impl $name {
+            /// Are all flags of `other` set?
+            #[must_use]
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Flags of both sets.
+            #[must_use]
+            pub const fn union(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            /// No flag set?
+            #[must_use]
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                self.union(rhs)
+            }
+        }
+    };
+}
+
 pub mod atlas;
 pub mod bidi;
 pub mod cjk;
 pub mod composite;
+pub mod decoration;
 #[cfg(feature = "ffi")]
 pub mod ffi;
 pub mod game;
@@ -4,6 +4,9 @@
 //! ready for rendering. Handles:
 //! - Horizontal advance accumulation
 //! - Kerning pair adjustments (hash-based O(1) lookup via sorted array + binary search)
+//! - Small caps, superscripts, subscripts and ordinals ([`FontFeatures`])
+//! - Tabular, proportional and oldstyle figures ([`FigureStyle`])
+//! - Underline, strikethrough and overline with ink skipping ([`Decorations`])
 //! - Line breaking
 //! - Text metrics (width, height, baseline)
 //!
@@ -14,6 +17,12 @@ extern crate alloc;
 use alloc::vec::Vec;
 
 use crate::atlas::SdfAtlas;
+pub use crate::decoration::Decorations;
+use crate::decoration::{self, DecorationLine};
+pub use crate::glyph::digits::FigureStyle;
+use crate::glyph::dispatcher::GlyphSourcePolicy;
+pub use crate::glyph::variants::FontFeatures;
+use crate::glyph::FontMetrics;
 use crate::param::MetaFontParams;
 
 /// Maximum kerning pairs in table
@@ -22,15 +31,15 @@ const MAX_KERN_PAIRS: usize = 64;
 /// Positioned glyph for rendering
 #[derive(Debug, Clone, Copy)]
 pub struct ShapedGlyph {
-    /// Character
+    /// Character drawn (after [`FontFeatures`] substitution)
     pub codepoint: char,
-    /// X position (em units from line start)
+    /// Pen position (em units from line start); the ink starts at `x + lsb`
     pub x: f32,
     /// Y position (em units from text origin)
     pub y: f32,
     /// Glyph advance width
     pub advance: f32,
-    /// Left side bearing
+    /// Left side bearing (pen origin to the left edge of the ink)
     pub lsb: f32,
 }
 
@@ -43,6 +52,9 @@ pub struct ShapedLine {
     pub width: f32,
     /// Line Y offset
     pub y_offset: f32,
+    /// Underlines, strikethroughs and overlines (baseline-relative, like
+    /// the glyphs)
+    pub decorations: Vec<DecorationLine>,
 }
 
 /// Kerning pair — stored as a packed u64 key for O(log n) binary search.
@@ -86,6 +98,16 @@ pub struct TextShaper {
     letter_spacing: f32,
     /// Word spacing multiplier (relative to space advance)
     word_spacing: f32,
+    /// Glyph-source policy pushed into the atlas (None = keep the atlas's)
+    source_policy: Option<GlyphSourcePolicy>,
+    /// Typographic variants substituted while shaping
+    features: FontFeatures,
+    /// Figure style digits are shaped in (None = the atlas's)
+    figure_style: Option<FigureStyle>,
+    /// Decoration strokes emitted with each line
+    decorations: Decorations,
+    /// Break underlines and overlines around the ink
+    skip_ink: bool,
 }
 
 impl TextShaper {
@@ -98,6 +120,11 @@ impl TextShaper {
             line_height: 1.2,
             letter_spacing: 0.0,
             word_spacing: 1.0,
+            source_policy: None,
+            features: FontFeatures::NONE,
+            figure_style: None,
+            decorations: Decorations::NONE,
+            skip_ink: true,
         };
         shaper.build_default_kern_table();
         shaper
@@ -118,6 +145,78 @@ impl TextShaper {
         self.word_spacing = s;
     }
 
+    /// Shape with the given glyph-source policy. The atlas passed to
+    /// `shape_*` is switched to this policy (re-rendering its cache if it
+    /// differs); without one the atlas keeps its own policy.
+    pub fn set_source_policy(&mut self, policy: Option<GlyphSourcePolicy>) {
+        self.source_policy = policy;
+    }
+
+    /// Glyph-source policy applied to the atlas, if any.
+    #[must_use]
+    pub const fn source_policy(&self) -> Option<&GlyphSourcePolicy> {
+        self.source_policy.as_ref()
+    }
+
+    /// Set the typographic variants to shape with (default
+    /// [`FontFeatures::NONE`]), e.g. `FontFeatures::SMALL_CAPS |
+    /// FontFeatures::CAPS_TO_SMALL_CAPS` for headers.
+    pub const fn set_features(&mut self, features: FontFeatures) {
+        self.features = features;
+    }
+
+    /// Typographic variants applied while shaping.
+    #[must_use]
+    pub const fn features(&self) -> FontFeatures {
+        self.features
+    }
+
+    /// Shape digits in `style`, e.g. [`FigureStyle::TabularLining`] for
+    /// score counters and timers. The atlas caches the styled tiles next
+    /// to its own; `None` shapes digits in the atlas's
+    /// [figure style](SdfAtlas::figure_style).
+    pub const fn set_figure_style(&mut self, style: Option<FigureStyle>) {
+        self.figure_style = style;
+    }
+
+    /// Figure style digits are shaped in, if any.
+    #[must_use]
+    pub const fn figure_style(&self) -> Option<FigureStyle> {
+        self.figure_style
+    }
+
+    /// Set the decorations drawn under, through and over each line
+    /// (default [`Decorations::NONE`]).
+    pub const fn set_decorations(&mut self, decorations: Decorations) {
+        self.decorations = decorations;
+    }
+
+    /// Decorations emitted with each line.
+    #[must_use]
+    pub const fn decorations(&self) -> Decorations {
+        self.decorations
+    }
+
+    /// Break underlines and overlines where they would touch a glyph, such
+    /// as the descenders of g, j, p, y (default on). Strikethroughs are
+    /// always continuous.
+    pub const fn set_skip_ink(&mut self, skip: bool) {
+        self.skip_ink = skip;
+    }
+
+    /// Are underlines and overlines broken around the ink?
+    #[must_use]
+    pub const fn skip_ink(&self) -> bool {
+        self.skip_ink
+    }
+
+    /// Switch `atlas` to this shaper's source policy, if it has one.
+    fn sync_atlas(&self, atlas: &mut SdfAtlas) {
+        if let Some(policy) = &self.source_policy {
+            atlas.set_source_policy(policy.clone());
+        }
+    }
+
     /// Build default kerning table for common Latin pairs.
     ///
     /// After inserting all pairs the table is sorted so that `kern()`
@@ -231,18 +330,44 @@ impl TextShaper {
 
     /// Shape a single line of text using atlas for metrics
     pub fn shape_line(&self, text: &str, atlas: &mut SdfAtlas) -> ShapedLine {
+        self.sync_atlas(atlas);
+        let metrics = (!self.decorations.is_empty()).then(|| atlas.font_metrics());
+        self.layout_line(text, atlas, metrics.as_ref())
+    }
+
+    /// [`Self::shape_line`] on an atlas already synced to this shaper.
+    /// Decorations are placed from `metrics`; `None` leaves them out.
+    fn layout_line(
+        &self,
+        text: &str,
+        atlas: &mut SdfAtlas,
+        metrics: Option<&FontMetrics>,
+    ) -> ShapedLine {
         let mut glyphs = Vec::new();
         let mut cursor_x: f32 = 0.0;
         let mut prev_char: Option<char> = None;
 
         let space_advance = self.params.width * 0.3;
 
+        // Decoration strokes, stretched to the line width at the end, and
+        // the ink gaps collected for each of them.
+        let mut lines = metrics.map_or_else(Vec::new, |m| {
+            decoration::lines(self.decorations, m, 0.0, 0.0)
+        });
+        let mut gaps: Vec<Vec<(f32, f32)>> = lines.iter().map(|_| Vec::new()).collect();
+
+        // Ordinal suffixes run from a figure to the end of the word.
+        let mut after_figure = false;
         for ch in text.chars() {
             if ch == ' ' {
                 cursor_x += space_advance * self.word_spacing + self.letter_spacing;
                 prev_char = Some(ch);
+                after_figure = false;
                 continue;
             }
+            let glyph = self.features.substitute(ch, after_figure);
+            after_figure = ch.is_ascii_digit() || (after_figure && ch.is_ascii_lowercase());
+            let ch = glyph;
 
             // Apply kerning
             if let Some(prev) = prev_char {
@@ -250,37 +375,82 @@ impl TextShaper {
             }
 
             // Get glyph metrics from atlas
-            let entry = atlas.get_or_insert(ch);
+            let figures = self.figure_style.or(atlas.figure_style());
+            let entry = atlas.get_or_insert_styled(ch, figures);
+            let metrics = entry.metrics;
+
+            if self.skip_ink {
+                for (line, gaps) in lines.iter().zip(&mut gaps) {
+                    if line.kind.skips_ink() {
+                        let spans = decoration::ink_spans(
+                            line,
+                            |col, row| atlas.sample_tile(&entry, col, row),
+                            entry.bbox_min,
+                            entry.bbox_max,
+                        );
+                        gaps.extend(spans.iter().map(|&(a, b)| (cursor_x + a, cursor_x + b)));
+                    }
+                }
+            }
 
             glyphs.push(ShapedGlyph {
                 codepoint: ch,
-                x: cursor_x + entry.lsb,
+                x: cursor_x,
                 y: 0.0,
-                advance: entry.advance,
-                lsb: entry.lsb,
+                advance: metrics.advance,
+                lsb: metrics.lsb,
             });
 
-            cursor_x += entry.advance + self.letter_spacing;
+            cursor_x += metrics.advance + self.letter_spacing;
             prev_char = Some(ch);
         }
 
+        for line in &mut lines {
+            line.x_end = cursor_x;
+        }
+        let decorations = lines
+            .into_iter()
+            .zip(&gaps)
+            .flat_map(|(line, gaps)| decoration::skip(line, gaps))
+            .collect();
+
         ShapedLine {
             width: cursor_x,
             glyphs,
             y_offset: 0.0,
+            decorations,
         }
     }
 
+    /// Font-wide metrics of `atlas` under this shaper's source policy.
+    pub fn font_metrics(&self, atlas: &mut SdfAtlas) -> FontMetrics {
+        self.sync_atlas(atlas);
+        atlas.font_metrics()
+    }
+
     /// Shape text with automatic line breaking at `max_width`
     pub fn shape_text(&self, text: &str, atlas: &mut SdfAtlas, max_width: f32) -> Vec<ShapedLine> {
+        self.shape_lines(text, atlas, max_width).0
+    }
+
+    /// [`Self::shape_text`], plus the baseline-to-baseline distance the
+    /// lines are set at: the line-height factor times
+    /// [`FontMetrics::line_height`].
+    fn shape_lines(
+        &self,
+        text: &str,
+        atlas: &mut SdfAtlas,
+        max_width: f32,
+    ) -> (Vec<ShapedLine>, f32) {
         let mut lines: Vec<ShapedLine> = Vec::new();
-        let line_step = self.line_height * (self.params.ascender + self.params.descender);
+        let metrics = self.font_metrics(atlas);
+        let line_step = self.line_height * metrics.line_height();
 
         // Split by explicit newlines first
         for raw_line in text.split('\n') {
             if max_width <= 0.0 || max_width >= 1e6 {
                 // No wrapping
-                let mut shaped = self.shape_line(raw_line, atlas);
+                let mut shaped = self.layout_line(raw_line, atlas, Some(&metrics));
                 shaped.y_offset = lines.len() as f32 * line_step;
                 lines.push(shaped);
             } else {
@@ -298,11 +468,11 @@ impl TextShaper {
                         s
                     };
 
-                    let test_shaped = self.shape_line(&test_line, atlas);
+                    let test_shaped = self.layout_line(&test_line, atlas, None);
 
                     if test_shaped.width > max_width && !current_line.is_empty() {
                         // Emit current line
-                        let mut shaped = self.shape_line(&current_line, atlas);
+                        let mut shaped = self.layout_line(&current_line, atlas, Some(&metrics));
                         shaped.y_offset = lines.len() as f32 * line_step;
                         lines.push(shaped);
                         current_line = alloc::string::String::from(*word);
@@ -312,7 +482,7 @@ impl TextShaper {
 
                     // Last word in line
                     if i == words.len() - 1 && !current_line.is_empty() {
-                        let mut shaped = self.shape_line(&current_line, atlas);
+                        let mut shaped = self.layout_line(&current_line, atlas, Some(&metrics));
                         shaped.y_offset = lines.len() as f32 * line_step;
                         lines.push(shaped);
                     }
@@ -324,29 +494,30 @@ impl TextShaper {
                         glyphs: Vec::new(),
                         width: 0.0,
                         y_offset: lines.len() as f32 * line_step,
+                        decorations: Vec::new(),
                     });
                 }
             }
         }
 
-        lines
+        (lines, line_step)
     }
 
     /// Measure text width without full shaping
     pub fn measure_width(&self, text: &str, atlas: &mut SdfAtlas) -> f32 {
-        self.shape_line(text, atlas).width
+        self.sync_atlas(atlas);
+        self.layout_line(text, atlas, None).width
     }
 
     /// Compute text bounding box (width, height) for multi-line text
     pub fn measure_text(&self, text: &str, atlas: &mut SdfAtlas, max_width: f32) -> (f32, f32) {
-        let lines = self.shape_text(text, atlas, max_width);
+        let (lines, line_step) = self.shape_lines(text, atlas, max_width);
         let mut total_width: f32 = 0.0;
         for line in &lines {
             if line.width > total_width {
                 total_width = line.width;
             }
         }
-        let line_step = self.line_height * (self.params.ascender + self.params.descender);
         let total_height = lines.len() as f32 * line_step;
         (total_width, total_height)
     }
@@ -368,6 +539,7 @@ pub struct TextMetrics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decoration::DecorationKind;
 
     fn make_atlas() -> SdfAtlas {
         SdfAtlas::new(4, MetaFontParams::sans_regular())
@@ -379,6 +551,20 @@ mod tests {
         assert!(shaper.line_height > 0.0);
     }
 
+    #[test]
+    fn test_source_policy_reaches_atlas() {
+        let mut shaper = TextShaper::new(MetaFontParams::sans_regular());
+        let mut atlas = make_atlas();
+        shaper.shape_line("AB", &mut atlas);
+        assert_eq!(atlas.source_policy(), &GlyphSourcePolicy::default());
+
+        shaper.set_source_policy(Some(GlyphSourcePolicy::parametric_only()));
+        let line = shaper.shape_line("AB", &mut atlas);
+        assert_eq!(atlas.source_policy(), &GlyphSourcePolicy::parametric_only());
+        assert_eq!(line.glyphs.len(), 2);
+        assert_eq!(atlas.occupied(), 2);
+    }
+
     #[test]
     fn test_kern_lookup() {
         let shaper = TextShaper::new(MetaFontParams::sans_regular());
@@ -426,6 +612,123 @@ mod tests {
         assert_eq!(line.glyphs[0].codepoint, 'A');
     }
 
+    #[test]
+    fn test_shaped_glyph_uses_ink_metrics() {
+        let shaper = TextShaper::new(MetaFontParams::sans_regular());
+        let mut atlas = SdfAtlas::new(4, MetaFontParams::sans_regular());
+        let line = shaper.shape_line("H", &mut atlas);
+        let entry = atlas.get_or_insert('H');
+        let g = &line.glyphs[0];
+        assert_eq!(g.x, 0.0);
+        assert!(g.lsb > 0.0);
+        assert_eq!(g.lsb, entry.metrics.lsb);
+        assert_eq!(g.advance, entry.metrics.advance);
+    }
+
+    #[test]
+    fn test_features_substitute_variants() {
+        let mut shaper = TextShaper::new(MetaFontParams::sans_regular());
+        let mut atlas = make_atlas();
+        assert!(shaper.features().is_empty());
+        let plain = shaper.shape_line("Hi", &mut atlas);
+
+        shaper.set_features(FontFeatures::SMALL_CAPS | FontFeatures::CAPS_TO_SMALL_CAPS);
+        let caps = shaper.shape_line("Hi", &mut atlas);
+        let drawn: Vec<char> = caps.glyphs.iter().map(|g| g.codepoint).collect();
+        assert_eq!(drawn, ['ʜ', 'ɪ']);
+        assert!(caps.glyphs[0].advance < plain.glyphs[0].advance);
+
+        shaper.set_features(FontFeatures::ORDINALS);
+        let line = shaper.shape_line("1st st", &mut atlas);
+        let drawn: Vec<char> = line.glyphs.iter().map(|g| g.codepoint).collect();
+        assert_eq!(drawn, ['1', 'ˢ', 'ᵗ', 's', 't']);
+    }
+
+    #[test]
+    fn test_tabular_figures_keep_timers_steady() {
+        let mut shaper = TextShaper::new(MetaFontParams::sans_regular());
+        let mut atlas = SdfAtlas::new(8, MetaFontParams::sans_regular());
+        // The embedded outlines have a proportional `1`.
+        #[cfg(feature = "outlines-latin")]
+        {
+            let ones = shaper.measure_width("11:11", &mut atlas);
+            let eights = shaper.measure_width("88:88", &mut atlas);
+            assert!(ones < eights);
+        }
+
+        shaper.set_figure_style(Some(FigureStyle::TabularLining));
+        let ones = shaper.measure_width("11:11", &mut atlas);
+        let eights = shaper.measure_width("88:88", &mut atlas);
+        assert!((ones - eights).abs() < 1e-5);
+        // The atlas itself is left alone.
+        assert_eq!(atlas.figure_style(), None);
+        assert_eq!(atlas.source_policy(), &GlyphSourcePolicy::default());
+        assert_eq!(
+            atlas.get_or_insert('1').figures,
+            None,
+            "plain tiles stay cached next to the tabular ones"
+        );
+
+        // Without a style of its own the shaper follows the atlas.
+        shaper.set_figure_style(None);
+        #[cfg(feature = "outlines-latin")]
+        assert!(shaper.measure_width("11:11", &mut atlas) < eights);
+        atlas.set_figure_style(Some(FigureStyle::TabularLining));
+        let ones = shaper.measure_width("11:11", &mut atlas);
+        assert!((ones - eights).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_decorations_skip_descenders() {
+        let mut shaper = TextShaper::new(MetaFontParams::sans_regular());
+        let mut atlas = make_atlas();
+        assert!(shaper.shape_line("type", &mut atlas).decorations.is_empty());
+
+        shaper.set_decorations(Decorations::UNDERLINE | Decorations::STRIKETHROUGH);
+        let metrics = shaper.font_metrics(&mut atlas);
+        let plain = shaper.shape_line("mum", &mut atlas);
+        assert_eq!(plain.decorations.len(), 2);
+        assert!(plain
+            .decorations
+            .iter()
+            .all(|l| l.x_start == 0.0 && (l.x_end - plain.width).abs() < 1e-6));
+        assert!((plain.decorations[0].y - metrics.underline_position).abs() < 1e-6);
+
+        // g, y and p break the underline; the strikethrough stays whole.
+        let line = shaper.shape_line("gyp", &mut atlas);
+        let underline: Vec<_> = line
+            .decorations
+            .iter()
+            .filter(|l| l.kind == DecorationKind::Underline)
+            .collect();
+        let strike = line
+            .decorations
+            .iter()
+            .filter(|l| l.kind == DecorationKind::Strikethrough)
+            .count();
+        assert_eq!(strike, 1);
+        let covered: f32 = underline.iter().map(|l| l.x_end - l.x_start).sum();
+        assert!(covered < line.width * 0.8, "{underline:?}");
+
+        // The embedded kana have descending tails too.
+        #[cfg(feature = "outlines-kana")]
+        {
+            let kana = shaper.shape_line("すゆ", &mut atlas);
+            assert!(kana.decorations.len() > 2);
+        }
+
+        shaper.set_skip_ink(false);
+        assert_eq!(shaper.shape_line("gyp", &mut atlas).decorations.len(), 2);
+
+        // Wrapped lines are decorated too.
+        let lines = shaper.shape_text("gyp mum gyp", &mut atlas, 1.5);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert_eq!(line.decorations.len(), 2);
+            assert!((line.decorations[0].x_end - line.width).abs() < 1e-6);
+        }
+    }
+
     #[test]
     fn test_shape_word() {
         let shaper = TextShaper::new(MetaFontParams::sans_regular());
@@ -457,6 +760,18 @@ mod tests {
         assert!(lines[1].y_offset > lines[0].y_offset);
     }
 
+    #[test]
+    fn test_line_step_uses_font_metrics() {
+        let mut shaper = TextShaper::new(MetaFontParams::sans_regular());
+        shaper.set_source_policy(Some(GlyphSourcePolicy::parametric_only()));
+        let mut atlas = make_atlas();
+        let lines = shaper.shape_text("A\nB", &mut atlas, 0.0);
+        let metrics = shaper.font_metrics(&mut atlas);
+        assert!((lines[1].y_offset - 1.2 * metrics.line_height()).abs() < 1e-6);
+        // Parametric descenders reach below `MetaFontParams::descender`.
+        assert!(metrics.descent > shaper.params.descender);
+    }
+
     #[test]
     fn test_measure_width() {
         let shaper = TextShaper::new(MetaFontParams::sans_regular());
@@ -493,7 +808,8 @@ mod tests {
         let mut atlas = make_atlas();
         let (w, h) = shaper.measure_text("AB\nHI", &mut atlas, 0.0);
         assert!(w > 0.0);
-        assert!(h > 0.0);
+        let step = 1.2 * shaper.font_metrics(&mut atlas).line_height();
+        assert!((h - 2.0 * step).abs() < 1e-6);
     }
 
     #[test]
@@ -8,6 +8,7 @@
 //! License: MIT
 //! Author: Moroya Sakamoto
 
+use crate::glyph::font_render::fast_sqrt;
 use crate::param::MetaFontParams;
 
 /// Maximum control points per stroke
@@ -43,13 +44,13 @@ impl Point2 {
     pub fn distance(self, other: Self) -> f32 {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
-        fast_sqrt_stroke(dx * dx + dy * dy)
+        fast_sqrt(dx * dx + dy * dy)
     }
 
     #[inline(always)]
     #[must_use]
     pub fn length(self) -> f32 {
-        fast_sqrt_stroke(self.x * self.x + self.y * self.y)
+        fast_sqrt(self.x * self.x + self.y * self.y)
     }
 
     #[inline(always)]
@@ -318,30 +319,6 @@ impl SerifBracket {
     }
 }
 
-#[inline(always)]
-fn fast_sqrt_stroke(x: f32) -> f32 {
-    if x <= 0.0 {
-        return 0.0;
-    }
-    let half = 0.5 * x;
-    let i = f32::to_bits(x);
-    // Quake III Arena magic constant for fast inverse square root (1/√x).
-    // Attributed to Greg Walsh / John Carmack (id Software, 1999).
-    // Mechanism: IEEE 754 f32 bits encode value as (1 + mantissa) × 2^(exp-127).
-    // A right-shift by 1 halves the exponent in log2-space (approximates √),
-    // and subtracting from 0x5f3759df corrects the bias offset.
-    // The result is a first-order estimate; each Newton–Raphson step below
-    // halves the relative error. Two iterations yield ~4.7 × 10⁻⁷ relative
-    // error — within single-precision ULP for all normal positive inputs.
-    // Reference: Lomont, "Fast Inverse Square Root" (2003);
-    //            quake3-1.32b/code/game/q_math.c, Q_rsqrt().
-    let i = 0x5f37_59df - (i >> 1);
-    let y = f32::from_bits(i);
-    let y = y * (1.5 - half * y * y); // Newton–Raphson iteration 1
-    let y = y * (1.5 - half * y * y); // Newton–Raphson iteration 2
-    x * y // x * (1/√x) = √x
-}
-
 #[inline(always)]
 fn sin_approx_stroke(x: f32) -> f32 {
     let pi = core::f32::consts::PI;
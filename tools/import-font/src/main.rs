@@ -2,14 +2,20 @@
 //! target character set and emit `src/glyph/font_outlines_data.rs` (Rust
 //! const, no_std-compatible).
 //!
-//! Outlines are flattened to polylines (Bezier subdivided) and stored in
-//! em-normalized coordinates (units_per_em → 1.0). Each glyph carries its
-//! horizontal advance width in em units. The rasterizer in
-//! `glyph::font_render` consumes this data.
+//! Outlines are stored as path-command streams (move / line / quadratic /
+//! cubic, curves kept intact) in font units, delta + zigzag-varint encoded
+//! into one byte stream per weight and coverage group
+//! (`src/glyph/outlines/<weight>_<group>.bin`). Groups (latin / kana / kanji)
+//! match `glyph::font_render::OutlineGroup` and are gated by the
+//! `outlines-*` cargo features of the main crate.
+//! Identical glyph records are stored once. Each glyph carries its
+//! horizontal advance width in font units. The rasterizer in
+//! `glyph::font_render` decodes this data (see its module docs for the
+//! record layout).
 //!
-//! Two tables are emitted:
-//!   - FONT_OUTLINES_REGULAR — from BIZUDPGothic-Regular.ttf
-//!   - FONT_OUTLINES_BOLD    — from BIZUDPGothic-Bold.ttf
+//! Two weights are emitted:
+//!   - FONT_OUTLINES_REGULAR_* — from BIZUDPGothic-Regular.ttf
+//!   - FONT_OUTLINES_BOLD_*    — from BIZUDPGothic-Bold.ttf
 //!
 //! Usage:
 //!   cargo run --release
@@ -19,12 +25,10 @@
 //!
 //! Author: Moroya Sakamoto
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use ttf_parser::{Face, OutlineBuilder};
 
-const QUAD_SUBDIVS: usize = 8;
-const CUBIC_SUBDIVS: usize = 12;
-
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut chars: Vec<char> = std::env::args()
         .skip(1)
@@ -56,40 +60,96 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         bold_face.units_per_em()
     );
 
-    let out_path = manifest.join("../../src/glyph/font_outlines_data.rs");
+    let upem = regular_face.units_per_em();
+    if bold_face.units_per_em() != upem {
+        return Err("Regular and Bold faces must share units_per_em".into());
+    }
+
+    let out_dir = manifest.join("../../src/glyph");
+    let out_path = out_dir.join("font_outlines_data.rs");
     let mut out = String::new();
     out.push_str("// Auto-generated by tools/import-font. Do not edit by hand.\n");
     out.push_str("//\n");
     out.push_str("// Glyph outlines from BIZ UDPGothic (Morisawa, SIL OFL 1.1).\n");
     out.push_str("// See tools/import-font/fonts/OFL.txt for license terms.\n");
     out.push_str("//\n");
-    out.push_str("// Coordinates are normalized to em units: units_per_em -> 1.0.\n");
+    out.push_str("// Outlines live in the `outlines/*.bin` streams, quantized to font units and\n");
+    out.push_str("// delta/varint encoded (format documented in `glyph::font_render`). Glyphs\n");
+    out.push_str("// with byte-identical encodings share one record. Each coverage group and\n");
+    out.push_str("// weight is gated by its cargo feature (`outlines-latin` / `outlines-kana` /\n");
+    out.push_str("// `outlines-kanji`, plus `outlines-bold` for the Bold tables).\n");
     out.push_str("// Origin: baseline (y=0), pen start (x=0).\n");
-    out.push_str("// Third tuple element: horizontal advance in em units.\n");
     out.push_str("\n");
+    out.push_str("/// Font units per em of the quantized outline coordinates and advances.\n");
+    out.push_str(&format!("pub const OUTLINE_UNITS_PER_EM: u16 = {};\n", upem));
 
-    emit_table(&mut out, "FONT_OUTLINES_REGULAR", &regular_face, &chars);
-    out.push('\n');
-    emit_table(&mut out, "FONT_OUTLINES_BOLD", &bold_face, &chars);
+    std::fs::create_dir_all(out_dir.join("outlines"))?;
+    for (weight, face) in [("regular", &regular_face), ("bold", &bold_face)] {
+        for (group, feature) in GROUPS {
+            let name = format!(
+                "FONT_OUTLINES_{}_{}",
+                weight.to_uppercase(),
+                group.to_uppercase()
+            );
+            let file = format!("outlines/{}_{}.bin", weight, group);
+            let cfg = if weight == "bold" {
+                format!("#[cfg(all(feature = \"{}\", feature = \"outlines-bold\"))]", feature)
+            } else {
+                format!("#[cfg(feature = \"{}\")]", feature)
+            };
+            let group_chars: Vec<char> =
+                chars.iter().copied().filter(|c| group_of(*c) == group).collect();
+            out.push('\n');
+            let stream = emit_table(&mut out, &name, &file, &cfg, face, &group_chars);
+            let bin_path = out_dir.join(&file);
+            std::fs::write(&bin_path, &stream)?;
+            eprintln!("Wrote {} ({} bytes)", bin_path.display(), stream.len());
+        }
+    }
 
     std::fs::write(&out_path, &out)?;
     eprintln!("Wrote {}", out_path.display());
     Ok(())
 }
 
-fn emit_table(out: &mut String, name: &str, face: &Face<'_>, chars: &[char]) {
+/// Coverage groups and the main-crate feature gating each.
+const GROUPS: [(&str, &str); 3] = [
+    ("latin", "outlines-latin"),
+    ("kana", "outlines-kana"),
+    ("kanji", "outlines-kanji"),
+];
+
+/// Mirrors `glyph::font_render::OutlineGroup::of`.
+fn group_of(ch: char) -> &'static str {
+    match ch as u32 {
+        0x2E80..=0x2FDF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => "kanji",
+        0x3000..=0x30FF | 0xFF00..=0xFFEF => "kana",
+        _ => "latin",
+    }
+}
+
+/// Append the index for one face/group to `out` and return its encoded stream.
+fn emit_table(
+    out: &mut String,
+    name: &str,
+    file: &str,
+    cfg: &str,
+    face: &Face<'_>,
+    chars: &[char],
+) -> Vec<u8> {
     use core::fmt::Write;
-    let upem = face.units_per_em() as f32;
-    let inv_upem = 1.0 / upem;
-    writeln!(out, "/// (codepoint, contour-list, advance-em).").unwrap();
-    writeln!(out, "/// Sorted ascending for binary search.").unwrap();
     writeln!(
         out,
-        "pub static {}: &[(char, &[&[(f32, f32)]], f32)] = &[",
+        "/// (codepoint, byte offset into `{}_STREAM`, advance in font units).",
         name
     )
     .unwrap();
+    writeln!(out, "/// Sorted ascending for binary search.").unwrap();
+    writeln!(out, "{}", cfg).unwrap();
+    writeln!(out, "pub static {}: &[OutlineEntry] = &[", name).unwrap();
 
+    let mut stream = Vec::new();
+    let mut records: HashMap<Vec<u8>, usize> = HashMap::new();
     let mut emitted = 0usize;
     let mut skipped = 0usize;
     let mut advance_only = 0usize;
@@ -98,33 +158,44 @@ fn emit_table(out: &mut String, name: &str, face: &Face<'_>, chars: &[char]) {
             skipped += 1;
             continue;
         };
-        let advance_em =
-            face.glyph_hor_advance(glyph_id).unwrap_or(0) as f32 * inv_upem;
+        let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0);
 
         let mut builder = PathBuilder::new();
         let has_outline = face.outline_glyph(glyph_id, &mut builder).is_some();
-        if !has_outline || builder.contours.is_empty() {
-            // Space and other non-drawing glyphs: emit advance-only entry.
-            writeln!(
-                out,
-                "    ('\\u{{{:04X}}}', &[], {:.4}),",
-                *ch as u32, advance_em
-            )
-            .unwrap();
+        if !has_outline || builder.cmds.is_empty() {
+            // Space and other non-drawing glyphs: zero-command record.
+            builder.cmds.clear();
             advance_only += 1;
-            emitted += 1;
-            continue;
         }
 
-        let normalized = normalise_em(&builder.contours, inv_upem);
-        emit_char(out, *ch, &normalized, advance_em);
+        let record = encode_record(&builder.cmds);
+        let offset = *records.entry(record).or_insert_with_key(|rec| {
+            let at = stream.len();
+            stream.extend_from_slice(rec);
+            at
+        });
+        writeln!(out, "    ('\\u{{{:04X}}}', {}, {}),", *ch as u32, offset, advance).unwrap();
         emitted += 1;
     }
     writeln!(out, "];").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// Encoded outline records referenced by `{}`.", name).unwrap();
+    writeln!(out, "{}", cfg).unwrap();
+    writeln!(
+        out,
+        "pub static {}_STREAM: &[u8] = include_bytes!(\"{}\");",
+        name, file
+    )
+    .unwrap();
     eprintln!(
-        "  {}: {} chars emitted ({} advance-only), {} skipped",
-        name, emitted, advance_only, skipped
+        "  {}: {} chars emitted ({} advance-only, {} unique records), {} skipped",
+        name,
+        emitted,
+        advance_only,
+        records.len(),
+        skipped
     );
+    stream
 }
 
 fn default_charset() -> Vec<char> {
@@ -161,102 +232,101 @@ fn default_charset() -> Vec<char> {
     all.chars().collect()
 }
 
-fn normalise_em(contours: &[Vec<(f32, f32)>], inv_upem: f32) -> Vec<Vec<(f32, f32)>> {
-    let mut out = Vec::with_capacity(contours.len());
-    for c in contours {
-        let mut nc = Vec::with_capacity(c.len());
-        for &(x, y) in c {
-            // TTF y-up preserved. x in [0, ~advance_em], y in [-descent_em, ascent_em].
-            nc.push((x * inv_upem, y * inv_upem));
+/// Path command in font units (mirrors `glyph::outline::PathCmd`).
+enum Cmd {
+    M(i32, i32),
+    L(i32, i32),
+    Q(i32, i32, i32, i32),
+    C(i32, i32, i32, i32, i32, i32),
+}
+
+/// Encode one glyph: varint count, 2-bit opcodes, zigzag-varint point deltas.
+fn encode_record(cmds: &[Cmd]) -> Vec<u8> {
+    let mut out = Vec::new();
+    push_varint(&mut out, cmds.len() as u32);
+    let mut ops = vec![0u8; cmds.len().div_ceil(4)];
+    for (i, cmd) in cmds.iter().enumerate() {
+        let op = match cmd {
+            Cmd::M(..) => 0,
+            Cmd::L(..) => 1,
+            Cmd::Q(..) => 2,
+            Cmd::C(..) => 3,
+        };
+        ops[i / 4] |= op << ((i % 4) * 2);
+    }
+    out.extend_from_slice(&ops);
+
+    let (mut px, mut py) = (0i32, 0i32);
+    for cmd in cmds {
+        let pts: &[i32] = match cmd {
+            Cmd::M(x, y) | Cmd::L(x, y) => &[*x, *y],
+            Cmd::Q(x1, y1, x, y) => &[*x1, *y1, *x, *y],
+            Cmd::C(x1, y1, x2, y2, x, y) => &[*x1, *y1, *x2, *y2, *x, *y],
+        };
+        for p in pts.chunks(2) {
+            push_varint(&mut out, zigzag(p[0] - px));
+            push_varint(&mut out, zigzag(p[1] - py));
+            px = p[0];
+            py = p[1];
         }
-        out.push(nc);
     }
     out
 }
 
-fn emit_char(out: &mut String, ch: char, contours: &[Vec<(f32, f32)>], advance: f32) {
-    use core::fmt::Write;
-    write!(out, "    ('\\u{{{:04X}}}', &[", ch as u32).unwrap();
-    for (i, c) in contours.iter().enumerate() {
-        if i > 0 {
-            out.push_str(", ");
-        }
-        out.push_str("&[");
-        for (j, (x, y)) in c.iter().enumerate() {
-            if j > 0 {
-                out.push_str(", ");
-            }
-            write!(out, "({:.4},{:.4})", x, y).unwrap();
-        }
-        out.push_str("]");
+fn zigzag(v: i32) -> u32 {
+    ((v << 1) ^ (v >> 31)) as u32
+}
+
+fn push_varint(out: &mut Vec<u8>, mut v: u32) {
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
     }
-    writeln!(out, "], {:.4}),", advance).unwrap();
+    out.push(v as u8);
 }
 
-/// Build glyph outlines into a list of closed polylines.
+/// Collect glyph outlines as path commands in font units.
 struct PathBuilder {
-    contours: Vec<Vec<(f32, f32)>>,
-    cur: Vec<(f32, f32)>,
-    last: (f32, f32),
+    cmds: Vec<Cmd>,
+    start: (i32, i32),
 }
 
 impl PathBuilder {
     fn new() -> Self {
         Self {
-            contours: Vec::new(),
-            cur: Vec::new(),
-            last: (0.0, 0.0),
+            cmds: Vec::new(),
+            start: (0, 0),
         }
     }
+}
 
-    fn flush(&mut self) {
-        if !self.cur.is_empty() {
-            let c = std::mem::take(&mut self.cur);
-            self.contours.push(c);
-        }
-    }
+/// Quantize a font-unit coordinate (integral for TrueType, may be
+/// fractional for CFF).
+fn q(v: f32) -> i32 {
+    v.round() as i32
 }
 
 impl OutlineBuilder for PathBuilder {
     fn move_to(&mut self, x: f32, y: f32) {
-        self.flush();
-        self.cur.push((x, y));
-        self.last = (x, y);
+        self.start = (q(x), q(y));
+        self.cmds.push(Cmd::M(q(x), q(y)));
     }
     fn line_to(&mut self, x: f32, y: f32) {
-        self.cur.push((x, y));
-        self.last = (x, y);
+        self.cmds.push(Cmd::L(q(x), q(y)));
     }
     fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
-        for i in 1..=QUAD_SUBDIVS {
-            let t = i as f32 / QUAD_SUBDIVS as f32;
-            let u = 1.0 - t;
-            let px = u * u * self.last.0 + 2.0 * u * t * x1 + t * t * x;
-            let py = u * u * self.last.1 + 2.0 * u * t * y1 + t * t * y;
-            self.cur.push((px, py));
-        }
-        self.last = (x, y);
+        self.cmds.push(Cmd::Q(q(x1), q(y1), q(x), q(y)));
     }
     fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
-        for i in 1..=CUBIC_SUBDIVS {
-            let t = i as f32 / CUBIC_SUBDIVS as f32;
-            let u = 1.0 - t;
-            let px = u * u * u * self.last.0
-                + 3.0 * u * u * t * x1
-                + 3.0 * u * t * t * x2
-                + t * t * t * x;
-            let py = u * u * u * self.last.1
-                + 3.0 * u * u * t * y1
-                + 3.0 * u * t * t * y2
-                + t * t * t * y;
-            self.cur.push((px, py));
-        }
-        self.last = (x, y);
+        self.cmds
+            .push(Cmd::C(q(x1), q(y1), q(x2), q(y2), q(x), q(y)));
     }
     fn close(&mut self) {
-        if let Some(&first) = self.cur.first() {
-            self.cur.push(first);
+        // Contours close implicitly; drop an explicit closing line.
+        if let Some(Cmd::L(x, y)) = self.cmds.last() {
+            if (*x, *y) == self.start {
+                self.cmds.pop();
+            }
         }
-        self.flush();
     }
 }